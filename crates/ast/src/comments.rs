@@ -2,7 +2,7 @@ use std::slice::Iter;
 
 use pxp_span::{IsSpanned, Span};
 
-use crate::{Comment, CommentGroup};
+use crate::{Comment, CommentGroup, CommentKind, DocBlock};
 
 impl IsSpanned for CommentGroup {
     fn span(&self) -> Span {
@@ -14,6 +14,17 @@ impl CommentGroup {
     pub fn iter(&self) -> Iter<'_, Comment> {
         self.comments.iter()
     }
+
+    /// Get the docblock that is closest to the node that the comments are attached to.
+    pub fn docblock(&self) -> Option<&DocBlock> {
+        self.comments
+            .iter()
+            .rev()
+            .find_map(|comment| match &comment.kind {
+                CommentKind::DocBlock(docblock) => Some(&docblock.doc),
+                _ => None,
+            })
+    }
 }
 
 impl IntoIterator for CommentGroup {
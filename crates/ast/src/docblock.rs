@@ -1,10 +1,10 @@
 use pxp_bytestring::{ByteStr, ByteString};
 
 use crate::{
-    DocBlock, DocBlockDeprecatedTag, DocBlockExtendsTag, DocBlockGenericTag, DocBlockImplementsTag,
-    DocBlockMethodTag, DocBlockNode, DocBlockParamTag, DocBlockPropertyTag, DocBlockReturnTag,
-    DocBlockTag, DocBlockTagNode, DocBlockTemplateTag, DocBlockTextNode, DocBlockUsesTag,
-    DocBlockVarTag,
+    DataType, DocBlock, DocBlockDeprecatedTag, DocBlockExtendsTag, DocBlockGenericTag,
+    DocBlockImplementsTag, DocBlockMethodTag, DocBlockNode, DocBlockParamTag, DocBlockPropertyTag,
+    DocBlockReturnTag, DocBlockTag, DocBlockTagNode, DocBlockTemplateTag, DocBlockTextNode,
+    DocBlockUsesTag, DocBlockVarTag, SimpleVariable,
 };
 
 pub struct DocBlockTagCollection<'a> {
@@ -198,6 +198,37 @@ impl DocBlock {
             .map(DocBlockTextNode::text)
             .collect()
     }
+
+    /// Get the type of a parameter from its `@param` tag.
+    pub fn get_param_type(&self, variable: &SimpleVariable) -> Option<&DataType> {
+        self.tag_nodes().into_iter().find_map(|node| {
+            let tag = node.tag().as_param()?;
+
+            match &tag.variable {
+                Some(name) if name.symbol == variable.symbol => tag.data_type.as_ref(),
+                _ => None,
+            }
+        })
+    }
+
+    /// Get the type from the first `@return` tag.
+    pub fn get_return_type(&self) -> Option<&DataType> {
+        self.tag_nodes()
+            .into_iter()
+            .find_map(|node| node.tag().as_return()?.data_type.as_ref())
+    }
+
+    /// Get the type of a variable from a `@var` tag that either names the variable or doesn't name one at all.
+    pub fn get_var_type(&self, variable: &SimpleVariable) -> Option<&DataType> {
+        self.tag_nodes().into_iter().find_map(|node| {
+            let tag = node.tag().as_var()?;
+
+            match &tag.variable {
+                Some(name) if name.symbol != variable.symbol => None,
+                _ => tag.data_type.as_ref(),
+            }
+        })
+    }
 }
 
 impl DocBlockTextNode {
@@ -3336,7 +3336,7 @@ impl HasId for Identifier {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SimpleIdentifier {
     pub id: NodeId,
     pub symbol: ByteString,
@@ -4012,7 +4012,7 @@ impl IsSpanned for PropertyModifierGroup {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
 pub enum MethodModifier {
     Public(Span),
     Protected(Span),
@@ -4036,7 +4036,7 @@ impl IsSpanned for MethodModifier {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct MethodModifierGroup {
     pub id: NodeId,
     pub span: Span,
@@ -4092,7 +4092,7 @@ impl IsSpanned for ClassModifierGroup {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
 pub enum ConstantModifier {
     Public(Span),
     Protected(Span),
@@ -4112,7 +4112,7 @@ impl IsSpanned for ConstantModifier {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ConstantModifierGroup {
     pub id: NodeId,
     pub span: Span,
@@ -4666,7 +4666,7 @@ pub struct UnresolvedName {
     pub qualification: NameQualification,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash, serde::Serialize, serde::Deserialize)]
 pub struct ResolvedName {
     pub resolved: ByteString,
     pub original: ByteString,
@@ -5272,7 +5272,7 @@ impl HasId for Variable {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash, serde::Serialize, serde::Deserialize)]
 pub struct SimpleVariable {
     pub id: NodeId,
     pub symbol: ByteString,
@@ -11260,6 +11260,15 @@ impl<'a> Node<'a> {
                 let x = &node.kind;
                 children.push(x.into());
             }
+            NodeKind::PropertyEntryKind(node) => match node {
+                PropertyEntryKind::Uninitialized(inner) => {
+                    children.push(inner.into());
+                }
+                PropertyEntryKind::Initialized(inner) => {
+                    children.push(inner.into());
+                }
+                _ => {}
+            },
             NodeKind::UninitializedPropertyEntry(node) => {
                 let x = &node.variable;
                 children.push(x.into());
@@ -408,6 +408,18 @@ impl FunctionParameter {
     }
 }
 
+impl MethodParameter {
+    pub fn is_variadic(&self) -> bool {
+        self.ellipsis.is_some()
+    }
+
+    pub fn is_promoted(&self) -> bool {
+        self.modifiers
+            .as_ref()
+            .is_some_and(|modifiers| !modifiers.is_empty())
+    }
+}
+
 impl IsSpanned for CommentKind {
     fn span(&self) -> Span {
         match self {
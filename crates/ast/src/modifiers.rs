@@ -3,9 +3,19 @@ use pxp_span::Span;
 use crate::{
     ClassModifier, ClassModifierGroup, ConstantModifier, ConstantModifierGroup, MethodModifier,
     MethodModifierGroup, PromotedPropertyModifier, PromotedPropertyModifierGroup, PropertyModifier,
-    PropertyModifierGroup, Visibility,
+    PropertyModifierGroup, Visibility, VisibilityModifier,
 };
 
+impl VisibilityModifier {
+    pub fn visibility(&self) -> Visibility {
+        match self {
+            VisibilityModifier::Public(_) => Visibility::Public,
+            VisibilityModifier::Protected(_) => Visibility::Protected,
+            VisibilityModifier::Private(_) => Visibility::Private,
+        }
+    }
+}
+
 impl PromotedPropertyModifier {
     pub fn span(&self) -> Span {
         match self {
@@ -62,6 +72,16 @@ impl PromotedPropertyModifierGroup {
             })
             .unwrap_or(Visibility::Public)
     }
+
+    /// Get the explicit `set` visibility of an asymmetrically visible property, e.g. `private(set)`.
+    pub fn set_visibility(&self) -> Option<Visibility> {
+        self.modifiers.iter().find_map(|modifier| match modifier {
+            PromotedPropertyModifier::ProtectedSet { .. } => Some(Visibility::Protected),
+            PromotedPropertyModifier::PrivateSet { .. } => Some(Visibility::Private),
+            PromotedPropertyModifier::PublicSet { .. } => Some(Visibility::Public),
+            _ => None,
+        })
+    }
 }
 
 impl PropertyModifier {
@@ -137,6 +157,16 @@ impl PropertyModifierGroup {
             })
             .unwrap_or(Visibility::Public)
     }
+
+    /// Get the explicit `set` visibility of an asymmetrically visible property, e.g. `private(set)`.
+    pub fn set_visibility(&self) -> Option<Visibility> {
+        self.modifiers.iter().find_map(|modifier| match modifier {
+            PropertyModifier::ProtectedSet { .. } => Some(Visibility::Protected),
+            PropertyModifier::PrivateSet { .. } => Some(Visibility::Private),
+            PropertyModifier::PublicSet { .. } => Some(Visibility::Public),
+            _ => None,
+        })
+    }
 }
 
 impl MethodModifier {
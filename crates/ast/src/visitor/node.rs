@@ -81,4 +81,9 @@ impl<'a> Ancestors<'a> {
     pub fn find(&self, cb: impl Fn(&Node<'a>) -> bool) -> Option<Node<'a>> {
         self.ancestors.iter().rev().find(|node| cb(node)).cloned()
     }
+
+    /// The ancestor `n` levels above the current node, where `0` is its parent.
+    pub fn nth(&self, n: usize) -> Option<Node<'a>> {
+        self.ancestors.iter().rev().nth(n).cloned()
+    }
 }
@@ -0,0 +1,46 @@
+use std::{fmt::Display, path::PathBuf};
+
+#[derive(Debug)]
+pub enum ComposerError {
+    /// A Composer file couldn't be read from disk.
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    /// A Composer file isn't valid JSON, or doesn't have the expected structure.
+    Json {
+        path: PathBuf,
+        error: serde_json::Error,
+    },
+}
+
+impl ComposerError {
+    pub fn path(&self) -> &PathBuf {
+        match self {
+            Self::Io { path, .. } => path,
+            Self::Json { path, .. } => path,
+        }
+    }
+}
+
+impl Display for ComposerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io { path, error } => {
+                write!(f, "Failed to read `{}`: {}", path.display(), error)
+            }
+            Self::Json { path, error } => {
+                write!(f, "Failed to parse `{}`: {}", path.display(), error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ComposerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { error, .. } => Some(error),
+            Self::Json { error, .. } => Some(error),
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use std::{
+    collections::HashSet,
+    io,
+    path::{Path, PathBuf},
+};
+
+use manifest::{InstalledManifest, Manifest};
+use package::join;
+use serde::de::DeserializeOwned;
+
+mod error;
+mod manifest;
+mod package;
+
+pub use error::ComposerError;
+pub use package::{Autoload, Mapping, Package};
+
+/// Directories inside of installed packages that are never needed for analysis.
+const VENDOR_TEST_DIRECTORIES: [&str; 4] = ["tests", "Tests", "test", "Test"];
+
+/// Directories that never contain code that belongs to a project.
+const IGNORED_DIRECTORIES: [&str; 3] = [".git", "node_modules", ".idea"];
+
+/// A project managed by Composer, along with every package that it has installed.
+#[derive(Debug, Clone)]
+pub struct Project {
+    root: PathBuf,
+    vendor: PathBuf,
+    packages: Vec<Package>,
+    /// Every PSR-4 prefix and directory, longest prefix first.
+    psr4: Vec<(String, PathBuf)>,
+    /// Every PSR-0 prefix and directory, longest prefix first.
+    psr0: Vec<(String, PathBuf)>,
+}
+
+impl Project {
+    /// Check whether the given directory contains a `composer.json` file.
+    pub fn exists(root: &Path) -> bool {
+        root.join("composer.json").is_file()
+    }
+
+    /// Read the project's `composer.json` and, if the dependencies have been installed,
+    /// `vendor/composer/installed.json`.
+    pub fn load(root: &Path) -> Result<Self, ComposerError> {
+        let manifest: Manifest = read_json(&root.join("composer.json"))?;
+
+        let vendor = join(
+            root,
+            manifest.config.vendor_dir.as_deref().unwrap_or("vendor"),
+        );
+
+        let mut autoload = Autoload::default();
+        autoload.extend(root, &manifest.autoload);
+        autoload.extend(root, &manifest.autoload_dev);
+
+        let mut packages = vec![Package {
+            name: manifest.name,
+            path: root.to_path_buf(),
+            autoload,
+            root: true,
+        }];
+
+        let installed = vendor.join("composer").join("installed.json");
+
+        if installed.is_file() {
+            let installed: InstalledManifest = read_json(&installed)?;
+
+            for package in installed.into_packages() {
+                let path = match &package.install_path {
+                    Some(install_path) => join(&vendor.join("composer"), install_path),
+                    None => join(&vendor, &package.name),
+                };
+
+                let mut autoload = Autoload::default();
+                autoload.extend(&path, &package.autoload);
+
+                packages.push(Package {
+                    name: Some(package.name),
+                    path,
+                    autoload,
+                    root: false,
+                });
+            }
+        }
+
+        let prefixes = |select: fn(&Autoload) -> &[Mapping]| {
+            let mut prefixes = packages
+                .iter()
+                .flat_map(|package| select(&package.autoload))
+                .flat_map(|(prefix, paths)| paths.iter().map(|path| (prefix.clone(), path.clone())))
+                .collect::<Vec<_>>();
+
+            // More specific prefixes take priority, just like they do in Composer's autoloader.
+            prefixes.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+            prefixes
+        };
+
+        let psr4 = prefixes(Autoload::psr4);
+        let psr0 = prefixes(Autoload::psr0);
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            vendor,
+            packages,
+            psr4,
+            psr0,
+        })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn vendor_dir(&self) -> &Path {
+        &self.vendor
+    }
+
+    /// Get the root package, followed by every installed package.
+    pub fn packages(&self) -> &[Package] {
+        &self.packages
+    }
+
+    pub fn root_package(&self) -> &Package {
+        &self.packages[0]
+    }
+
+    pub fn get_package(&self, name: &str) -> Option<&Package> {
+        self.packages
+            .iter()
+            .find(|package| package.name().is_some_and(|n| n.eq_ignore_ascii_case(name)))
+    }
+
+    /// Get the package that the given file belongs to.
+    pub fn get_package_for(&self, path: &Path) -> Option<&Package> {
+        self.packages
+            .iter()
+            .filter(|package| path.starts_with(&package.path))
+            .max_by_key(|package| package.path.components().count())
+    }
+
+    /// Find the file that should declare the given class, using the PSR-4 and PSR-0 mappings.
+    ///
+    /// This only checks that the file exists, not that it actually declares the class.
+    pub fn find_class(&self, name: &str) -> Option<PathBuf> {
+        let name = name.strip_prefix('\\').unwrap_or(name);
+
+        for (prefix, directory) in self.psr4.iter() {
+            let Some(relative) = name.strip_prefix(prefix.as_str()) else {
+                continue;
+            };
+
+            let path = directory.join(format!("{}.php", relative.replace('\\', "/")));
+
+            if path.is_file() {
+                return Some(path);
+            }
+        }
+
+        // PSR-0 uses the whole class name, and underscores in the class name are directory separators.
+        let relative = match name.rsplit_once('\\') {
+            Some((namespace, class)) => format!(
+                "{}/{}.php",
+                namespace.replace('\\', "/"),
+                class.replace('_', "/")
+            ),
+            None => format!("{}.php", name.replace('_', "/")),
+        };
+
+        for (prefix, directory) in self.psr0.iter() {
+            if !name.starts_with(prefix.as_str()) {
+                continue;
+            }
+
+            let path = directory.join(&relative);
+
+            if path.is_file() {
+                return Some(path);
+            }
+        }
+
+        None
+    }
+
+    /// Find every PHP file that can be autoloaded in the project.
+    ///
+    /// Paths that match a package's `exclude-from-classmap` patterns are skipped, as are test
+    /// directories inside of installed packages.
+    pub fn files(&self) -> Vec<PathBuf> {
+        let mut seen = HashSet::new();
+        let mut files = Vec::new();
+
+        for package in self.packages.iter() {
+            for path in package.autoload.paths() {
+                self.collect(package, path, true, &mut seen, &mut files);
+            }
+        }
+
+        files
+    }
+
+    fn collect(
+        &self,
+        package: &Package,
+        path: &Path,
+        top: bool,
+        seen: &mut HashSet<PathBuf>,
+        files: &mut Vec<PathBuf>,
+    ) {
+        if let Ok(relative) = path.strip_prefix(&package.path) {
+            if package.autoload.is_excluded(relative) {
+                return;
+            }
+        }
+
+        if path.is_dir() {
+            // The root package's autoload paths can include the vendor directory, but installed
+            // packages are handled separately.
+            if package.root && path == self.vendor {
+                return;
+            }
+
+            if !top
+                && !package.root
+                && path
+                    .file_name()
+                    .is_some_and(|name| VENDOR_TEST_DIRECTORIES.iter().any(|test| name == *test))
+            {
+                return;
+            }
+
+            let Ok(entries) = path.read_dir() else {
+                return;
+            };
+
+            let mut entries = entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .collect::<Vec<_>>();
+
+            entries.sort();
+
+            for entry in entries {
+                self.collect(package, &entry, false, seen, files);
+            }
+        } else if (top || path.extension().is_some_and(|ext| ext == "php"))
+            && path.is_file()
+            && seen.insert(path.to_path_buf())
+        {
+            files.push(path.to_path_buf());
+        }
+    }
+}
+
+/// Find every PHP file inside of a directory, for projects that don't use Composer.
+///
+/// Version control and editor directories are skipped, as are any subdirectories that can't be read.
+pub fn find_php_files(directory: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut entries = directory
+        .read_dir()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect::<Vec<_>>();
+
+    entries.sort();
+
+    let mut files = Vec::new();
+
+    for path in entries {
+        if path.is_dir() {
+            let ignored = path
+                .file_name()
+                .is_some_and(|name| IGNORED_DIRECTORIES.iter().any(|ignored| name == *ignored));
+
+            if !ignored {
+                files.append(&mut find_php_files(&path).unwrap_or_default());
+            }
+        } else if path.extension().is_some_and(|ext| ext == "php") {
+            files.push(path);
+        }
+    }
+
+    Ok(files)
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, ComposerError> {
+    let contents = std::fs::read(path).map_err(|error| ComposerError::Io {
+        path: path.to_path_buf(),
+        error,
+    })?;
+
+    serde_json::from_slice(&contents).map_err(|error| ComposerError::Json {
+        path: path.to_path_buf(),
+        error,
+    })
+}
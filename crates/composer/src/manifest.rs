@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+/// The parts of `composer.json` that we care about.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct Manifest {
+    pub(crate) name: Option<String>,
+    pub(crate) autoload: AutoloadManifest,
+    #[serde(rename = "autoload-dev")]
+    pub(crate) autoload_dev: AutoloadManifest,
+    pub(crate) config: ConfigManifest,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct ConfigManifest {
+    #[serde(rename = "vendor-dir")]
+    pub(crate) vendor_dir: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct AutoloadManifest {
+    #[serde(rename = "psr-4")]
+    pub(crate) psr4: BTreeMap<String, OneOrMany>,
+    #[serde(rename = "psr-0")]
+    pub(crate) psr0: BTreeMap<String, OneOrMany>,
+    pub(crate) classmap: Vec<String>,
+    pub(crate) files: Vec<String>,
+    #[serde(rename = "exclude-from-classmap")]
+    pub(crate) exclude_from_classmap: OneOrMany,
+}
+
+/// Composer allows most autoload paths to be written as either a single string or a list of strings.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Default for OneOrMany {
+    fn default() -> Self {
+        Self::Many(Vec::new())
+    }
+}
+
+impl OneOrMany {
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &String> {
+        match self {
+            Self::One(one) => std::slice::from_ref(one).iter(),
+            Self::Many(many) => many.iter(),
+        }
+    }
+}
+
+/// `vendor/composer/installed.json` is a plain list of packages in Composer 1, and an object
+/// with a `packages` key in Composer 2.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum InstalledManifest {
+    V1(Vec<InstalledPackageManifest>),
+    V2 {
+        packages: Vec<InstalledPackageManifest>,
+    },
+}
+
+impl InstalledManifest {
+    pub(crate) fn into_packages(self) -> Vec<InstalledPackageManifest> {
+        match self {
+            Self::V1(packages) => packages,
+            Self::V2 { packages } => packages,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct InstalledPackageManifest {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) autoload: AutoloadManifest,
+    /// The package's directory, relative to `vendor/composer`. Only written by Composer 2.
+    #[serde(rename = "install-path")]
+    pub(crate) install_path: Option<String>,
+}
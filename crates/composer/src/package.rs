@@ -0,0 +1,151 @@
+use std::path::{Component, Path, PathBuf};
+
+use crate::manifest::AutoloadManifest;
+
+/// A package that takes part in autoloading, either the root project or one of its dependencies.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Package {
+    pub(crate) name: Option<String>,
+    pub(crate) path: PathBuf,
+    pub(crate) autoload: Autoload,
+    pub(crate) root: bool,
+}
+
+impl Package {
+    /// The package's name, e.g. `symfony/yaml`. The root package doesn't need to have one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The directory that the package is installed in.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.root
+    }
+
+    pub fn autoload(&self) -> &Autoload {
+        &self.autoload
+    }
+}
+
+/// A namespace prefix and the directories that it maps to.
+pub type Mapping = (String, Vec<PathBuf>);
+
+/// The autoload configuration of a single package, with every path made absolute.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Autoload {
+    pub(crate) psr4: Vec<Mapping>,
+    pub(crate) psr0: Vec<Mapping>,
+    pub(crate) classmap: Vec<PathBuf>,
+    pub(crate) files: Vec<PathBuf>,
+    pub(crate) exclude: Vec<String>,
+}
+
+impl Autoload {
+    pub(crate) fn extend(&mut self, base: &Path, manifest: &AutoloadManifest) {
+        for (prefix, paths) in manifest.psr4.iter() {
+            self.psr4.push((
+                prefix.clone(),
+                paths.iter().map(|p| join(base, p)).collect(),
+            ));
+        }
+
+        for (prefix, paths) in manifest.psr0.iter() {
+            self.psr0.push((
+                prefix.clone(),
+                paths.iter().map(|p| join(base, p)).collect(),
+            ));
+        }
+
+        self.classmap
+            .extend(manifest.classmap.iter().map(|p| join(base, p)));
+        self.files
+            .extend(manifest.files.iter().map(|p| join(base, p)));
+        self.exclude
+            .extend(manifest.exclude_from_classmap.iter().map(|pattern| {
+                pattern
+                    .trim_start_matches("./")
+                    .trim_matches('/')
+                    .to_string()
+            }));
+    }
+
+    /// The PSR-4 namespace prefixes and the directories that they map to.
+    pub fn psr4(&self) -> &[Mapping] {
+        &self.psr4
+    }
+
+    /// The PSR-0 namespace prefixes and the directories that they map to.
+    pub fn psr0(&self) -> &[Mapping] {
+        &self.psr0
+    }
+
+    /// Files and directories that are scanned for classes.
+    pub fn classmap(&self) -> &[PathBuf] {
+        &self.classmap
+    }
+
+    /// Files that are always included by the autoloader.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Every file and directory that's mentioned in the autoload configuration.
+    pub fn paths(&self) -> impl Iterator<Item = &PathBuf> {
+        self.psr4
+            .iter()
+            .chain(self.psr0.iter())
+            .flat_map(|(_, paths)| paths.iter())
+            .chain(self.classmap.iter())
+            .chain(self.files.iter())
+    }
+
+    /// Check whether the given path, relative to the package's directory, matches one of the
+    /// package's `exclude-from-classmap` patterns.
+    pub(crate) fn is_excluded(&self, relative: &Path) -> bool {
+        let relative = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        self.exclude
+            .iter()
+            .any(|pattern| matches(pattern.as_bytes(), relative.as_bytes()))
+    }
+}
+
+/// Match a path against an `exclude-from-classmap` pattern.
+///
+/// `*` matches anything except a `/` and `**` matches anything at all. A pattern that matches a
+/// directory also matches everything inside of it.
+fn matches(pattern: &[u8], path: &[u8]) -> bool {
+    match pattern {
+        [] => path.is_empty() || path[0] == b'/',
+        [b'*', b'*', rest @ ..] => (0..=path.len()).any(|i| matches(rest, &path[i..])),
+        [b'*', rest @ ..] => (0..=path.len())
+            .take_while(|&i| i == 0 || path[i - 1] != b'/')
+            .any(|i| matches(rest, &path[i..])),
+        [c, rest @ ..] => path.first() == Some(c) && matches(rest, &path[1..]),
+    }
+}
+
+/// Join a path from a Composer file onto `base`, resolving any `.` and `..` components.
+pub(crate) fn join(base: &Path, path: &str) -> PathBuf {
+    let mut joined = PathBuf::new();
+
+    for component in base.join(path).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                joined.pop();
+            }
+            component => joined.push(component),
+        }
+    }
+
+    joined
+}
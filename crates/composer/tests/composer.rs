@@ -0,0 +1,149 @@
+use std::path::{Path, PathBuf};
+
+use pxp_composer::{find_php_files, Project};
+
+#[test]
+fn it_reads_the_root_package() {
+    let project = project("project");
+    let root = project.root_package();
+
+    assert!(root.is_root());
+    assert_eq!(root.name(), Some("acme/app"));
+    assert_eq!(root.path(), fixture("project"));
+    assert_eq!(
+        root.autoload().psr4(),
+        &[
+            ("App\\".to_string(), vec![fixture("project/src")]),
+            ("Tests\\".to_string(), vec![fixture("project/tests")]),
+        ]
+    );
+    assert_eq!(root.autoload().classmap(), &[fixture("project/lib")]);
+    assert_eq!(root.autoload().files(), &[fixture("project/helpers.php")]);
+}
+
+#[test]
+fn it_reads_installed_packages() {
+    let project = project("project");
+
+    assert_eq!(project.packages().len(), 3);
+
+    let http = project.get_package("acme/http").unwrap();
+
+    assert!(!http.is_root());
+    assert_eq!(http.path(), fixture("project/vendor/acme/http"));
+    assert_eq!(
+        http.autoload().psr4(),
+        &[(
+            "Acme\\Http\\".to_string(),
+            vec![fixture("project/vendor/acme/http/src")]
+        )]
+    );
+}
+
+#[test]
+fn it_reads_composer_1_installed_packages() {
+    let project = project("composer-v1");
+    let bar = project.get_package("foo/bar").unwrap();
+
+    assert_eq!(project.root_package().name(), None);
+    assert_eq!(bar.path(), fixture("composer-v1/vendor/foo/bar"));
+    assert_eq!(
+        bar.autoload().psr4(),
+        &[(
+            "Foo\\Bar\\".to_string(),
+            vec![
+                fixture("composer-v1/vendor/foo/bar/src"),
+                fixture("composer-v1/vendor/foo/bar/lib")
+            ]
+        )]
+    );
+}
+
+#[test]
+fn it_finds_the_package_for_a_file() {
+    let project = project("project");
+
+    let client = fixture("project/vendor/acme/http/src/Client.php");
+    let user = fixture("project/src/User.php");
+
+    assert_eq!(
+        project.get_package_for(&client).and_then(|p| p.name()),
+        Some("acme/http")
+    );
+    assert_eq!(
+        project.get_package_for(&user).and_then(|p| p.name()),
+        Some("acme/app")
+    );
+    assert!(project
+        .get_package_for(Path::new("/elsewhere/file.php"))
+        .is_none());
+}
+
+#[test]
+fn it_resolves_classes_with_psr4() {
+    let project = project("project");
+
+    assert_eq!(
+        project.find_class("App\\User"),
+        Some(fixture("project/src/User.php"))
+    );
+    assert_eq!(
+        project.find_class("\\App\\Models\\Post"),
+        Some(fixture("project/src/Models/Post.php"))
+    );
+    assert_eq!(
+        project.find_class("Acme\\Http\\Client"),
+        Some(fixture("project/vendor/acme/http/src/Client.php"))
+    );
+    assert_eq!(project.find_class("App\\Missing"), None);
+    assert_eq!(project.find_class("Unknown\\User"), None);
+}
+
+#[test]
+fn it_resolves_classes_with_psr0() {
+    let project = project("project");
+
+    assert_eq!(
+        project.find_class("Legacy_Thing_Widget"),
+        Some(fixture(
+            "project/vendor/legacy/lib/lib/Legacy/Thing/Widget.php"
+        ))
+    );
+}
+
+#[test]
+fn it_finds_autoloadable_files() {
+    let project = project("project");
+
+    assert_eq!(
+        project.files(),
+        vec![
+            fixture("project/src/Models/Post.php"),
+            fixture("project/src/User.php"),
+            fixture("project/tests/UserTest.php"),
+            fixture("project/lib/Thing.php"),
+            fixture("project/helpers.php"),
+            fixture("project/vendor/acme/http/src/Client.php"),
+            fixture("project/vendor/legacy/lib/lib/Legacy/Thing/Widget.php"),
+        ]
+    );
+}
+
+#[test]
+fn it_finds_php_files_outside_of_composer_projects() {
+    assert_eq!(
+        find_php_files(&fixture("plain")).unwrap(),
+        vec![fixture("plain/index.php"), fixture("plain/src/Foo.php")]
+    );
+    assert!(find_php_files(&fixture("missing")).is_err());
+}
+
+fn project(name: &str) -> Project {
+    Project::load(&fixture(name)).unwrap()
+}
+
+fn fixture(path: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(path)
+}
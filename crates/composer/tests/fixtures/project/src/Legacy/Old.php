@@ -0,0 +1,5 @@
+<?php
+
+namespace App\Legacy;
+
+class Old {}
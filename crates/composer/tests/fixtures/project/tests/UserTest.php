@@ -0,0 +1,5 @@
+<?php
+
+namespace Tests;
+
+class UserTest {}
@@ -0,0 +1,65 @@
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::Path,
+};
+
+use crate::{Index, IndexError};
+
+/// Caches written by a different version of pxp are ignored, since the format of the index may have changed.
+const CACHE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+impl Index {
+    /// Load an index that was previously written with `Index::save_cache`.
+    ///
+    /// Returns `None` if the cache doesn't exist or was written by a different version of pxp. The content hash
+    /// of each file is stored alongside its entities, so `Index::index_paths` will only re-index files that have changed.
+    pub fn load_cache(path: &Path) -> Result<Option<Index>, IndexError> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => {
+                return Err(IndexError::Io {
+                    path: path.to_path_buf(),
+                    error,
+                })
+            }
+        };
+
+        let mut reader = BufReader::new(file);
+        let version: String =
+            bincode::deserialize_from(&mut reader).map_err(|error| cache_error(path, error))?;
+
+        if version != CACHE_VERSION {
+            return Ok(None);
+        }
+
+        let index: Index =
+            bincode::deserialize_from(&mut reader).map_err(|error| cache_error(path, error))?;
+
+        Ok(Some(index))
+    }
+
+    /// Write the index to disk so that it can be loaded with `Index::load_cache`.
+    pub fn save_cache(&self, path: &Path) -> Result<(), IndexError> {
+        let file = File::create(path).map_err(|error| IndexError::Io {
+            path: path.to_path_buf(),
+            error,
+        })?;
+
+        let mut writer = BufWriter::new(file);
+
+        bincode::serialize_into(&mut writer, CACHE_VERSION)
+            .map_err(|error| cache_error(path, error))?;
+        bincode::serialize_into(&mut writer, self).map_err(|error| cache_error(path, error))?;
+
+        Ok(())
+    }
+}
+
+fn cache_error(path: &Path, error: bincode::Error) -> IndexError {
+    IndexError::Cache {
+        path: path.to_path_buf(),
+        message: error.to_string(),
+    }
+}
@@ -0,0 +1,18 @@
+use pxp_ast::{ResolvedName, SimpleIdentifier};
+use pxp_type::Type;
+use serde::{Deserialize, Serialize};
+
+use crate::{location::Location, HasFileId};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnumCaseEntity {
+    pub(crate) name: SimpleIdentifier,
+    pub(crate) value: Option<Type<ResolvedName>>,
+    pub(crate) location: Location,
+}
+
+impl HasFileId for EnumCaseEntity {
+    fn file_id(&self) -> crate::FileId {
+        self.location.file_id()
+    }
+}
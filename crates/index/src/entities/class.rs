@@ -1,18 +1,34 @@
 use pxp_ast::ResolvedName;
+use pxp_type::Type;
+use serde::{Deserialize, Serialize};
 
 use crate::{location::Location, HasFileId};
 
-use super::MethodEntity;
+use super::{
+    ClassishConstantEntity, EnumCaseEntity, MethodEntity, PropertyEntity, TemplateEntity,
+    TraitAdaptationEntity,
+};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ClassEntity {
     pub(crate) name: ResolvedName,
     pub(crate) kind: ClassEntityKind,
+    pub(crate) parent: Option<ResolvedName>,
+    pub(crate) interfaces: Vec<ResolvedName>,
+    pub(crate) traits: Vec<ResolvedName>,
+    pub(crate) trait_adaptations: Vec<TraitAdaptationEntity>,
     pub(crate) methods: Vec<MethodEntity>,
+    pub(crate) properties: Vec<PropertyEntity>,
+    pub(crate) constants: Vec<ClassishConstantEntity>,
+    pub(crate) cases: Vec<EnumCaseEntity>,
+    pub(crate) backing_type: Option<Type<ResolvedName>>,
+    pub(crate) templates: Vec<TemplateEntity>,
+    /// The generic arguments given to the parent and interfaces with `@extends` and `@implements`.
+    pub(crate) template_arguments: Vec<(ResolvedName, Vec<Type<ResolvedName>>)>,
     pub(crate) location: Location,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum ClassEntityKind {
     Class,
     Interface,
@@ -0,0 +1,33 @@
+use pxp_ast::{ConstantModifierGroup, ResolvedName, SimpleIdentifier};
+use pxp_type::Type;
+use serde::{Deserialize, Serialize};
+
+use crate::{location::Location, HasFileId};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConstantEntity {
+    pub(crate) name: ResolvedName,
+    pub(crate) value: Type<ResolvedName>,
+    pub(crate) location: Location,
+}
+
+impl HasFileId for ConstantEntity {
+    fn file_id(&self) -> crate::FileId {
+        self.location.file_id()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClassishConstantEntity {
+    pub(crate) name: SimpleIdentifier,
+    pub(crate) r#type: Option<Type<ResolvedName>>,
+    pub(crate) value: Type<ResolvedName>,
+    pub(crate) modifiers: ConstantModifierGroup,
+    pub(crate) location: Location,
+}
+
+impl HasFileId for ClassishConstantEntity {
+    fn file_id(&self) -> crate::FileId {
+        self.location.file_id()
+    }
+}
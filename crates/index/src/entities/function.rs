@@ -1,16 +1,18 @@
 use pxp_ast::{Name, ResolvedName};
 use pxp_type::Type;
+use serde::{Deserialize, Serialize};
 
 use crate::{location::Location, FileId, HasFileId};
 
-use super::parameters::Parameters;
+use super::{parameters::Parameters, TemplateEntity};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FunctionEntity {
     pub(crate) name: ResolvedName,
     pub(crate) parameters: Parameters,
     pub(crate) return_type: Option<Type<ResolvedName>>,
     pub(crate) returns_reference: bool,
+    pub(crate) templates: Vec<TemplateEntity>,
     pub(crate) location: Location,
 }
 
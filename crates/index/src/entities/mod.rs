@@ -1,46 +1,129 @@
+mod case;
 mod class;
+mod constant;
 mod function;
 mod method;
 mod parameters;
+mod property;
+mod table;
+mod template;
+mod trait_adaptation;
 
+use crate::FileId;
+pub use case::EnumCaseEntity;
 pub use class::{ClassEntity, ClassEntityKind};
+pub use constant::{ClassishConstantEntity, ConstantEntity};
 pub use function::FunctionEntity;
 pub use method::MethodEntity;
 pub use parameters::{Parameter, Parameters};
+pub use property::PropertyEntity;
 use pxp_bytestring::ByteString;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use table::EntityTable;
+pub use template::TemplateEntity;
+pub use trait_adaptation::TraitAdaptationEntity;
 
-#[derive(Debug, Clone, Default)]
+/// Entities are stored in a separate table for each kind of entity, keyed by a normalised name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub(crate) struct EntityRegistry {
-    functions: Vec<FunctionEntity>,
-    classes: Vec<ClassEntity>,
+    functions: EntityTable<FunctionEntity>,
+    classes: EntityTable<ClassEntity>,
+    constants: EntityTable<ConstantEntity>,
 }
 
 impl EntityRegistry {
+    /// Normalise the name of a class or function so that it can be used as a lookup key.
+    ///
+    /// Class and function names in PHP are case-insensitive and may be written with a leading `\`.
+    fn case_insensitive_key(name: &[u8]) -> ByteString {
+        ByteString::new(
+            name.strip_prefix(b"\\")
+                .unwrap_or(name)
+                .to_ascii_lowercase(),
+        )
+    }
+
+    /// Normalise the name of a constant so that it can be used as a lookup key.
+    ///
+    /// Constant names in PHP are case-sensitive, so only the leading `\` is removed.
+    fn case_sensitive_key(name: &[u8]) -> ByteString {
+        ByteString::from(name.strip_prefix(b"\\").unwrap_or(name))
+    }
+
+    /// Remove every entity that was declared in the given file.
+    pub fn remove_file(&mut self, file_id: FileId) {
+        self.functions.remove_file(file_id);
+        self.classes.remove_file(file_id);
+        self.constants.remove_file(file_id);
+    }
+
+    /// Remove every entity that was declared in any of the given files.
+    pub fn remove_files(&mut self, file_ids: &HashSet<FileId>) {
+        for file_id in file_ids {
+            self.remove_file(*file_id);
+        }
+    }
+
+    /// Move all of the entities from `other` into this registry, in the order they were added.
+    pub fn extend(&mut self, other: EntityRegistry) {
+        for function in other.functions {
+            self.add_function(function);
+        }
+
+        for class in other.classes {
+            self.add_class(class);
+        }
+
+        for constant in other.constants {
+            self.add_constant(constant);
+        }
+    }
+
     pub fn add_function(&mut self, function: FunctionEntity) {
-        self.functions.push(function);
+        // When the same function is declared more than once, the first declaration wins.
+        self.functions.insert(
+            Self::case_insensitive_key(&function.name.resolved),
+            function,
+        );
     }
 
-    pub fn functions(&self) -> &[FunctionEntity] {
+    pub fn functions(&self) -> &EntityTable<FunctionEntity> {
         &self.functions
     }
 
     pub fn get_function(&self, name: impl Into<ByteString>) -> Option<&FunctionEntity> {
-        let name = name.into();
-
-        self.functions.iter().find(|f| f.name.resolved == name)
+        self.functions
+            .get(&Self::case_insensitive_key(&name.into()))
     }
 
     pub fn add_class(&mut self, class: ClassEntity) {
-        self.classes.push(class);
+        self.classes
+            .insert(Self::case_insensitive_key(&class.name.resolved), class);
     }
 
-    pub fn classes(&self) -> &[ClassEntity] {
+    pub fn classes(&self) -> &EntityTable<ClassEntity> {
         &self.classes
     }
 
     pub fn get_class(&self, name: impl Into<ByteString>) -> Option<&ClassEntity> {
-        let name = name.into();
+        self.classes
+            .get(&Self::case_insensitive_key(&name.into()))
+    }
+
+    pub fn add_constant(&mut self, constant: ConstantEntity) {
+        self.constants.insert(
+            Self::case_sensitive_key(&constant.name.resolved),
+            constant,
+        );
+    }
+
+    pub fn constants(&self) -> &EntityTable<ConstantEntity> {
+        &self.constants
+    }
 
-        self.classes.iter().find(|c| c.name.resolved == name)
+    pub fn get_constant(&self, name: impl Into<ByteString>) -> Option<&ConstantEntity> {
+        self.constants
+            .get(&Self::case_sensitive_key(&name.into()))
     }
 }
@@ -0,0 +1,28 @@
+use pxp_ast::{ResolvedName, SimpleVariable, Visibility};
+use pxp_type::Type;
+use serde::{Deserialize, Serialize};
+
+use crate::{location::Location, HasFileId};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropertyEntity {
+    pub(crate) name: SimpleVariable,
+    pub(crate) r#type: Option<Type<ResolvedName>>,
+    pub(crate) default: Option<Type<ResolvedName>>,
+    pub(crate) visibility: Visibility,
+    pub(crate) set_visibility: Option<Visibility>,
+    pub(crate) r#static: bool,
+    pub(crate) readonly: bool,
+    pub(crate) promoted: bool,
+    /// Whether the property only exists in a `@property` tag, i.e. it's handled by `__get` and `__set`.
+    pub(crate) magic: bool,
+    pub(crate) get_hook: bool,
+    pub(crate) set_hook: bool,
+    pub(crate) location: Location,
+}
+
+impl HasFileId for PropertyEntity {
+    fn file_id(&self) -> crate::FileId {
+        self.location.file_id()
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{FileId, HasFileId};
+use pxp_bytestring::ByteString;
+use serde::{Deserialize, Serialize};
+use std::collections::{hash_map::Entry, HashMap};
+
+/// Storage for a single kind of entity.
+///
+/// Entities keep the same slot for as long as they're stored, and each file remembers which slots
+/// and lookup keys it contributed. Removing a file only touches those entries, instead of shifting
+/// every entity after it and rebuilding the lookup table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct EntityTable<T> {
+    slots: Vec<Option<T>>,
+    vacant: Vec<usize>,
+    /// Every declaration of a name, in the order they were added. The first declaration wins.
+    lookup: HashMap<ByteString, Vec<usize>>,
+    files: HashMap<FileId, Vec<(ByteString, usize)>>,
+}
+
+impl<T> Default for EntityTable<T> {
+    fn default() -> Self {
+        Self {
+            slots: Vec::new(),
+            vacant: Vec::new(),
+            lookup: HashMap::new(),
+            files: HashMap::new(),
+        }
+    }
+}
+
+impl<T: HasFileId> EntityTable<T> {
+    pub fn insert(&mut self, key: ByteString, entity: T) {
+        let file_id = entity.file_id();
+        let slot = match self.vacant.pop() {
+            Some(slot) => {
+                self.slots[slot] = Some(entity);
+                slot
+            }
+            None => {
+                self.slots.push(Some(entity));
+                self.slots.len() - 1
+            }
+        };
+
+        self.lookup.entry(key.clone()).or_default().push(slot);
+        self.files.entry(file_id).or_default().push((key, slot));
+    }
+
+    pub fn get(&self, key: &ByteString) -> Option<&T> {
+        self.lookup
+            .get(key)
+            .and_then(|slots| slots.first())
+            .and_then(|&slot| self.slots[slot].as_ref())
+    }
+
+    /// Remove every entity that was declared in the given file.
+    ///
+    /// If another file declares an entity with the same name, that declaration takes over.
+    pub fn remove_file(&mut self, file_id: FileId) {
+        let Some(entries) = self.files.remove(&file_id) else {
+            return;
+        };
+
+        for (key, slot) in entries {
+            if let Entry::Occupied(mut declarations) = self.lookup.entry(key) {
+                declarations.get_mut().retain(|&declaration| declaration != slot);
+
+                if declarations.get().is_empty() {
+                    declarations.remove();
+                }
+            }
+
+            self.slots[slot] = None;
+            self.vacant.push(slot);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.slots.iter().flatten()
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.vacant.len()
+    }
+}
+
+impl<T> IntoIterator for EntityTable<T> {
+    type Item = T;
+    type IntoIter = std::iter::Flatten<std::vec::IntoIter<Option<T>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.slots.into_iter().flatten()
+    }
+}
@@ -0,0 +1,11 @@
+use pxp_ast::ResolvedName;
+use pxp_bytestring::ByteString;
+use pxp_type::Type;
+use serde::{Deserialize, Serialize};
+
+/// A template declared with `@template` on a class-like, function or method.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateEntity {
+    pub(crate) name: ByteString,
+    pub(crate) bound: Option<Type<ResolvedName>>,
+}
@@ -0,0 +1,27 @@
+use pxp_ast::{ResolvedName, SimpleIdentifier, Visibility};
+use serde::{Deserialize, Serialize};
+
+/// An adaptation applied to the methods of a trait when it is used, i.e. the rules
+/// inside of the braces of a `use A, B { ... }` statement.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TraitAdaptationEntity {
+    /// `A::foo as protected bar;`
+    Alias {
+        r#trait: Option<ResolvedName>,
+        method: SimpleIdentifier,
+        alias: SimpleIdentifier,
+        visibility: Option<Visibility>,
+    },
+    /// `foo as protected;`
+    Visibility {
+        r#trait: Option<ResolvedName>,
+        method: SimpleIdentifier,
+        visibility: Visibility,
+    },
+    /// `A::foo insteadof B;`
+    Precedence {
+        r#trait: Option<ResolvedName>,
+        method: SimpleIdentifier,
+        insteadof: Vec<ResolvedName>,
+    },
+}
@@ -0,0 +1,54 @@
+use std::{fmt::Display, path::PathBuf};
+
+#[derive(Debug)]
+pub enum IndexError {
+    /// The file couldn't be read from disk.
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    /// The file couldn't be parsed or indexed, usually because of a bug in the parser.
+    Parse { path: PathBuf, message: String },
+    /// The index cache couldn't be read or written.
+    Cache { path: PathBuf, message: String },
+}
+
+impl IndexError {
+    pub fn path(&self) -> &PathBuf {
+        match self {
+            Self::Io { path, .. } => path,
+            Self::Parse { path, .. } => path,
+            Self::Cache { path, .. } => path,
+        }
+    }
+}
+
+impl Display for IndexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io { path, error } => {
+                write!(f, "Failed to read `{}`: {}", path.display(), error)
+            }
+            Self::Parse { path, message } => {
+                write!(f, "Failed to index `{}`: {}", path.display(), message)
+            }
+            Self::Cache { path, message } => {
+                write!(
+                    f,
+                    "Failed to use index cache `{}`: {}",
+                    path.display(),
+                    message
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for IndexError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { error, .. } => Some(error),
+            Self::Parse { .. } | Self::Cache { .. } => None,
+        }
+    }
+}
@@ -3,7 +3,9 @@ use std::{
     path::{Path, PathBuf},
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct FileId(usize);
 
 impl FileId {
@@ -22,39 +24,69 @@ impl HasFileId for FileId {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub(crate) struct FileRegistry {
     files: HashMap<PathBuf, FileId>,
+    // FileIds are handed out sequentially, so the path for a given FileId lives at the same position.
+    paths: Vec<PathBuf>,
+    // The hash of each file's contents when it was last indexed, used to skip files that haven't changed.
+    hashes: Vec<Option<u64>>,
 }
 
 impl FileRegistry {
     pub fn get_file_path(&self, id: FileId) -> Option<&Path> {
-        self.files
-            .iter()
-            .find_map(|(path, &file_id)| -> Option<&Path> {
-                if file_id == id {
-                    Some(path)
-                } else {
-                    None
-                }
-            })
+        self.paths.get(id.0).map(PathBuf::as_path)
     }
 
     pub fn get_file_path_unchecked(&self, id: FileId) -> &Path {
         self.get_file_path(id).unwrap()
     }
 
+    /// Resolve symlinks and relative components so that the same file always gets the same id.
+    ///
+    /// Paths that don't exist on disk, such as the bundled stubs, are used as-is.
+    fn normalize(path: &Path) -> PathBuf {
+        std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+    }
+
     pub fn get_or_insert(&mut self, path: &Path) -> FileId {
-        if let Some(&id) = self.files.get(path) {
+        let path = Self::normalize(path);
+
+        if let Some(&id) = self.files.get(&path) {
             id
         } else {
-            let id = FileId(self.files.len());
-            self.files.insert(path.to_path_buf(), id);
+            let id = FileId(self.paths.len());
+            self.files.insert(path.clone(), id);
+            self.paths.push(path);
+            self.hashes.push(None);
             id
         }
     }
 
+    pub fn get_file_id(&self, path: &Path) -> Option<FileId> {
+        self.files.get(&Self::normalize(path)).copied()
+    }
+
+    pub fn get_hash(&self, id: FileId) -> Option<u64> {
+        self.hashes.get(id.0).copied().flatten()
+    }
+
+    pub fn set_hash(&mut self, id: FileId, hash: Option<u64>) {
+        self.hashes[id.0] = hash;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (FileId, &Path)> {
+        self.paths
+            .iter()
+            .enumerate()
+            .map(|(id, path)| (FileId(id), path.as_path()))
+    }
+
     pub fn len(&self) -> usize {
         self.files.len()
     }
 }
+
+pub(crate) fn hash_contents(contents: &[u8]) -> u64 {
+    xxhash_rust::xxh3::xxh3_64(contents)
+}
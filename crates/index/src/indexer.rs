@@ -1,11 +1,27 @@
 use pxp_ast::{
-    visitor::Visitor, ClassStatement, ClassishMember, FunctionParameterList, FunctionStatement,
-    Method, MethodParameterList, ResolvedName, ReturnType,
+    visitor::{
+        walk_backed_enum_statement, walk_class_statement, walk_function_call_expression,
+        walk_function_statement, walk_interface_statement, walk_statement, walk_trait_statement,
+        walk_unit_enum_statement, Visitor,
+    },
+    Argument, BackedEnumMember, BackedEnumStatement, BackedEnumType, ClassStatement,
+    ClassishConstant, ClassishMember, ConstantStatement, DataType, DocBlock, Expression,
+    ExpressionKind, FunctionCallExpression, FunctionParameterList, FunctionStatement,
+    InterfaceStatement, LiteralKind, Method, MethodParameterList, Name, NameKind, Property,
+    PropertyEntry, PropertyEntryKind, PropertyHookKind, PropertyModifierGroup, ResolvedName,
+    ReturnType, SimpleIdentifier, Statement, StatementKind, TraitStatement, TraitUsage,
+    TraitUsageAdaptationKind, UnitEnumMember, UnitEnumStatement, Visibility,
 };
+use pxp_span::Span;
+use pxp_token::TokenKind;
 use pxp_type::Type;
 
 use crate::{
-    entities::{ClassEntity, ClassEntityKind, FunctionEntity, MethodEntity, Parameter, Parameters},
+    entities::{
+        ClassEntity, ClassEntityKind, ClassishConstantEntity, ConstantEntity, EnumCaseEntity,
+        FunctionEntity, MethodEntity, Parameter, Parameters, PropertyEntity, TemplateEntity,
+        TraitAdaptationEntity,
+    },
     location::Location,
     FileId, Index,
 };
@@ -13,23 +29,124 @@ use crate::{
 pub struct IndexingVisitor<'a> {
     file_id: FileId,
     index: &'a mut Index,
+    /// The docblock of the statement that is being indexed, since the comments before class-likes and
+    /// functions are attached to the statement rather than the node itself.
+    docblock: Option<DocBlock>,
 }
 
 impl<'a> IndexingVisitor<'a> {
     pub fn new(file_id: FileId, index: &'a mut Index) -> Self {
-        Self { file_id, index }
+        Self {
+            file_id,
+            index,
+            docblock: None,
+        }
+    }
+
+    fn transform_templates(&self, docblock: Option<&DocBlock>) -> Vec<TemplateEntity> {
+        let Some(docblock) = docblock else {
+            return Vec::new();
+        };
+
+        docblock
+            .tags()
+            .get_template_tags()
+            .into_iter()
+            .map(|tag| TemplateEntity {
+                name: tag.value.template.symbol.clone(),
+                bound: tag
+                    .value
+                    .bound
+                    .as_ref()
+                    .map(|bound| bound.get_type().clone()),
+            })
+            .collect()
+    }
+
+    /// Collect the generic arguments given to ancestors with `@extends` and `@implements` tags.
+    fn transform_template_arguments(
+        &self,
+        docblock: Option<&DocBlock>,
+    ) -> Vec<(ResolvedName, Vec<Type<ResolvedName>>)> {
+        let Some(docblock) = docblock else {
+            return Vec::new();
+        };
+
+        let tags = docblock.tags();
+        let data_types = tags
+            .get_extends_tags()
+            .into_iter()
+            .map(|tag| &tag.data_type)
+            .chain(
+                tags.get_implements_tags()
+                    .into_iter()
+                    .map(|tag| &tag.data_type),
+            );
+
+        data_types
+            .filter_map(|data_type| match data_type.get_type() {
+                Type::Generic(base, arguments) => match base.as_ref() {
+                    Type::Named(name) => Some((
+                        name.clone(),
+                        arguments
+                            .iter()
+                            .map(|argument| argument.r#type.clone())
+                            .collect(),
+                    )),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
     }
 
-    fn transform_function_parameter_list(&self, node: &FunctionParameterList) -> Parameters {
+    /// Determine the type of a parameter or return value.
+    ///
+    /// The docblock type wins whenever it is compatible with the native one, since it can describe
+    /// a value in more detail. Types that refer to templates can only be written in docblocks, so a
+    /// docblock type that mentions a template in scope is always used.
+    ///
+    /// Other files might not have been indexed yet, so a docblock class-like is only compatible with
+    /// a native class-like of the same name.
+    fn transform_type(
+        &self,
+        native: Option<&DataType>,
+        docblock: Option<&DataType>,
+        templates: &[TemplateEntity],
+    ) -> Option<Type<ResolvedName>> {
+        match docblock {
+            Some(docblock)
+                if mentions_template(docblock.get_type(), templates)
+                    || native.map_or(true, |native| {
+                        docblock
+                            .get_type()
+                            .refines_with(native.get_type(), &|a, b| {
+                                a.resolved.eq_ignore_ascii_case(&b.resolved)
+                            })
+                    }) =>
+            {
+                Some(docblock.get_type().clone())
+            }
+            _ => native.map(|data_type| data_type.get_type().clone()),
+        }
+    }
+
+    fn transform_function_parameter_list(
+        &self,
+        node: &FunctionParameterList,
+        docblock: Option<&DocBlock>,
+        templates: &[TemplateEntity],
+    ) -> Parameters {
         let mut parameters = Vec::new();
 
         for parameter in node.parameters.iter() {
             parameters.push(Parameter {
                 name: parameter.name.clone(),
-                r#type: parameter
-                    .data_type
-                    .as_ref()
-                    .map(|data_type| data_type.get_type().clone()),
+                r#type: self.transform_type(
+                    parameter.data_type.as_ref(),
+                    docblock.and_then(|docblock| docblock.get_param_type(&parameter.name)),
+                    templates,
+                ),
                 optional: parameter.default.is_some(),
                 variadic: parameter.ellipsis.is_some(),
                 location: Location::new(self.file_id, parameter.span),
@@ -39,16 +156,22 @@ impl<'a> IndexingVisitor<'a> {
         Parameters::new(parameters)
     }
 
-    fn transform_method_parameter_list(&self, node: &MethodParameterList) -> Parameters {
+    fn transform_method_parameter_list(
+        &self,
+        node: &MethodParameterList,
+        docblock: Option<&DocBlock>,
+        templates: &[TemplateEntity],
+    ) -> Parameters {
         let mut parameters = Vec::new();
 
         for parameter in node.parameters.iter() {
             parameters.push(Parameter {
                 name: parameter.name.clone(),
-                r#type: parameter
-                    .data_type
-                    .as_ref()
-                    .map(|data_type| data_type.get_type().clone()),
+                r#type: self.transform_type(
+                    parameter.data_type.as_ref(),
+                    docblock.and_then(|docblock| docblock.get_param_type(&parameter.name)),
+                    templates,
+                ),
                 optional: parameter.default.is_some(),
                 variadic: parameter.ellipsis.is_some(),
                 location: Location::new(self.file_id, parameter.span),
@@ -58,54 +181,694 @@ impl<'a> IndexingVisitor<'a> {
         Parameters::new(parameters)
     }
 
-    fn transform_return_type(&self, node: Option<&ReturnType>) -> Option<Type<ResolvedName>> {
-        node.map(|return_type| return_type.data_type.get_type().clone())
+    fn transform_return_type(
+        &self,
+        node: Option<&ReturnType>,
+        docblock: Option<&DocBlock>,
+        templates: &[TemplateEntity],
+    ) -> Option<Type<ResolvedName>> {
+        self.transform_type(
+            node.map(|return_type| &return_type.data_type),
+            docblock.and_then(DocBlock::get_return_type),
+            templates,
+        )
     }
 
-    fn transform_method(&self, node: &Method) -> MethodEntity {
+    fn transform_method(&self, node: &Method, class_templates: &[TemplateEntity]) -> MethodEntity {
+        let docblock = node.comments.docblock();
+        let templates = self.transform_templates(docblock);
+
+        // Methods can refer to their own templates, as well as the templates of the class-like.
+        let mut scope = class_templates.to_vec();
+        scope.extend(templates.iter().cloned());
+
         MethodEntity {
             name: node.name.clone(),
-            parameters: self.transform_method_parameter_list(&node.parameters),
-            return_type: self.transform_return_type(node.return_type.as_ref()),
+            parameters: self.transform_method_parameter_list(&node.parameters, docblock, &scope),
+            return_type: self.transform_return_type(node.return_type.as_ref(), docblock, &scope),
             returns_reference: node.ampersand.is_some(),
+            templates,
             modifiers: node.modifiers.clone(),
             location: Location::new(self.file_id, node.span),
         }
     }
 
-    fn transform_classish_members(&self, nodes: &[ClassishMember]) -> (Vec<MethodEntity>, ()) {
+    /// Determine the type of a constant expression without running any inference.
+    ///
+    /// Only the simple expressions that commonly appear in constant initialisers are
+    /// understood here, anything else produces `Type::Mixed`.
+    fn transform_constant_value(&self, node: &Expression) -> Type<ResolvedName> {
+        match &node.kind {
+            ExpressionKind::Literal(literal) => match literal.kind {
+                LiteralKind::Integer => Type::Integer,
+                LiteralKind::Float => Type::Float,
+                LiteralKind::String => Type::LiteralString(
+                    literal
+                        .token
+                        .symbol
+                        .as_bytestr()
+                        .strip_string_quotes()
+                        .to_bytestring(),
+                ),
+                LiteralKind::Missing => Type::Missing,
+            },
+            ExpressionKind::Bool(inner) => match inner.value.kind {
+                TokenKind::True => Type::True,
+                TokenKind::False => Type::False,
+                _ => Type::Boolean,
+            },
+            ExpressionKind::Null(_) => Type::Null,
+            ExpressionKind::Array(_) => Type::Array,
+            ExpressionKind::Concat(_)
+            | ExpressionKind::InterpolatedString(_)
+            | ExpressionKind::Heredoc(_)
+            | ExpressionKind::Nowdoc(_) => Type::String,
+            ExpressionKind::Parenthesized(inner) => self.transform_constant_value(&inner.expr),
+            ExpressionKind::New(inner) => match &inner.target.kind {
+                ExpressionKind::Name(name) if name.is_resolved() => {
+                    Type::Named(name.to_resolved().clone())
+                }
+                _ => Type::Object,
+            },
+            _ => Type::Mixed,
+        }
+    }
+
+    /// Extract the name and value of a constant defined using `define('NAME', value)`.
+    ///
+    /// Constants defined at runtime are always global and aren't affected by the current namespace,
+    /// so the name is taken verbatim from the string literal.
+    fn transform_define_call(&self, node: &FunctionCallExpression) -> Option<ConstantEntity> {
+        let ExpressionKind::Name(target) = &node.target.kind else {
+            return None;
+        };
+
+        let symbol = match &target.kind {
+            NameKind::Resolved(name) => &name.original,
+            NameKind::Unresolved(name) => &name.symbol,
+            NameKind::Special(_) => return None,
+        };
+
+        if !symbol
+            .strip_prefix(b"\\")
+            .unwrap_or(symbol.as_slice())
+            .eq_ignore_ascii_case(b"define")
+        {
+            return None;
+        }
+
+        let mut arguments = node
+            .arguments
+            .arguments
+            .iter()
+            .map(|argument| match argument {
+                Argument::Positional(argument) => &argument.value,
+                Argument::Named(argument) => &argument.value,
+            });
+
+        let (Some(name), Some(value)) = (arguments.next(), arguments.next()) else {
+            return None;
+        };
+
+        let ExpressionKind::Literal(literal) = &name.kind else {
+            return None;
+        };
+
+        if literal.kind != LiteralKind::String {
+            return None;
+        }
+
+        let name = literal.token.symbol.as_bytestr().strip_string_quotes();
+        let name = name.strip_prefix(b'\\').to_bytestring();
+
+        Some(ConstantEntity {
+            name: ResolvedName {
+                resolved: name.clone(),
+                original: name,
+            },
+            value: self.transform_constant_value(value),
+            location: Location::new(self.file_id, node.span),
+        })
+    }
+
+    fn transform_classish_constant(&self, node: &ClassishConstant) -> Vec<ClassishConstantEntity> {
+        node.entries
+            .iter()
+            .map(|entry| ClassishConstantEntity {
+                name: entry.name.clone(),
+                r#type: node
+                    .data_type
+                    .as_ref()
+                    .map(|data_type| data_type.get_type().clone()),
+                value: self.transform_constant_value(&entry.value),
+                modifiers: node.modifiers.clone(),
+                location: Location::new(self.file_id, entry.span),
+            })
+            .collect()
+    }
+
+    fn transform_property_entry(
+        &self,
+        node: &PropertyEntry,
+        data_type: Option<&DataType>,
+        modifiers: &PropertyModifierGroup,
+    ) -> PropertyEntity {
+        PropertyEntity {
+            name: node.kind.variable().clone(),
+            r#type: data_type.map(|data_type| data_type.get_type().clone()),
+            default: match &node.kind {
+                PropertyEntryKind::Initialized(entry) => {
+                    Some(self.transform_constant_value(&entry.value))
+                }
+                PropertyEntryKind::Uninitialized(_) => None,
+            },
+            visibility: modifiers.visibility(),
+            set_visibility: modifiers.set_visibility(),
+            r#static: modifiers.has_static(),
+            readonly: modifiers.has_readonly(),
+            promoted: false,
+            magic: false,
+            get_hook: false,
+            set_hook: false,
+            location: Location::new(self.file_id, node.span),
+        }
+    }
+
+    /// Create properties for the `@property`, `@property-read` and `@property-write` tags of a class-like.
+    fn transform_property_tags(&self, docblock: Option<&DocBlock>) -> Vec<PropertyEntity> {
+        let Some(docblock) = docblock else {
+            return Vec::new();
+        };
+
+        docblock
+            .tags()
+            .get_property_tags()
+            .into_iter()
+            .map(|tag| PropertyEntity {
+                name: tag.variable.clone(),
+                r#type: tag
+                    .data_type
+                    .as_ref()
+                    .map(|data_type| data_type.get_type().clone()),
+                default: None,
+                visibility: Visibility::Public,
+                set_visibility: None,
+                r#static: false,
+                readonly: tag.tag.symbol.ends_with(b"-read"),
+                promoted: false,
+                magic: true,
+                get_hook: false,
+                set_hook: false,
+                location: Location::new(self.file_id, tag.span),
+            })
+            .collect()
+    }
+
+    fn transform_property(&self, node: &Property) -> Vec<PropertyEntity> {
+        match node {
+            Property::Simple(property) => property
+                .entries
+                .iter()
+                .map(|entry| {
+                    self.transform_property_entry(
+                        entry,
+                        property.r#type.as_ref(),
+                        &property.modifiers,
+                    )
+                })
+                .collect(),
+            Property::Hooked(property) => {
+                let hooks = &property.hooks.hooks;
+
+                vec![PropertyEntity {
+                    get_hook: hooks
+                        .iter()
+                        .any(|hook| matches!(hook.kind, PropertyHookKind::Get(_))),
+                    set_hook: hooks
+                        .iter()
+                        .any(|hook| matches!(hook.kind, PropertyHookKind::Set(_))),
+                    ..self.transform_property_entry(
+                        &property.entry,
+                        property.r#type.as_ref(),
+                        &property.modifiers,
+                    )
+                }]
+            }
+        }
+    }
+
+    /// Constructor parameters with visibility or `readonly` modifiers are also properties.
+    fn transform_promoted_properties(&self, node: &Method) -> Vec<PropertyEntity> {
+        if !node.name.symbol.eq_ignore_ascii_case(b"__construct") {
+            return Vec::new();
+        }
+
+        let docblock = node.comments.docblock();
+
+        node.parameters
+            .parameters
+            .iter()
+            .filter(|parameter| parameter.is_promoted())
+            .map(|parameter| {
+                let modifiers = parameter.modifiers.as_ref().unwrap();
+
+                PropertyEntity {
+                    name: parameter.name.clone(),
+                    r#type: self.transform_type(
+                        parameter.data_type.as_ref(),
+                        docblock.and_then(|docblock| docblock.get_param_type(&parameter.name)),
+                        &[],
+                    ),
+                    default: None,
+                    visibility: modifiers.visibility(),
+                    set_visibility: modifiers.set_visibility(),
+                    r#static: false,
+                    readonly: modifiers.has_readonly(),
+                    promoted: true,
+                    magic: false,
+                    get_hook: false,
+                    set_hook: false,
+                    location: Location::new(self.file_id, parameter.span),
+                }
+            })
+            .collect()
+    }
+
+    fn transform_classish_members<'b>(
+        &self,
+        nodes: impl IntoIterator<Item = &'b ClassishMember>,
+        templates: &[TemplateEntity],
+    ) -> (Vec<MethodEntity>, Vec<PropertyEntity>) {
         let mut methods = Vec::new();
+        let mut properties = Vec::new();
 
-        for member in nodes.iter() {
+        for member in nodes {
             match member {
-                ClassishMember::Method(method) => methods.push(self.transform_method(method)),
+                ClassishMember::Method(method) => {
+                    properties.extend(self.transform_promoted_properties(method));
+                    methods.push(self.transform_method(method, templates));
+                }
+                ClassishMember::Property(property) => {
+                    properties.extend(self.transform_property(property))
+                }
                 _ => {}
             }
         }
 
-        (methods, ())
+        (methods, properties)
+    }
+
+    fn transform_classish_constants<'b>(
+        &self,
+        nodes: impl IntoIterator<Item = &'b ClassishMember>,
+    ) -> Vec<ClassishConstantEntity> {
+        let mut constants = Vec::new();
+
+        for member in nodes {
+            if let ClassishMember::Constant(constant) = member {
+                constants.extend(self.transform_classish_constant(constant));
+            }
+        }
+
+        constants
+    }
+
+    fn transform_name(&self, node: &Name) -> Option<ResolvedName> {
+        node.as_resolved().cloned()
+    }
+
+    fn transform_names<'b>(&self, nodes: impl Iterator<Item = &'b Name>) -> Vec<ResolvedName> {
+        nodes.filter_map(|name| self.transform_name(name)).collect()
+    }
+
+    fn builtin_name(name: &[u8]) -> ResolvedName {
+        ResolvedName {
+            resolved: name.into(),
+            original: name.into(),
+        }
+    }
+
+    fn transform_trait_usage(
+        &self,
+        node: &TraitUsage,
+    ) -> (Vec<ResolvedName>, Vec<TraitAdaptationEntity>) {
+        let traits = self.transform_names(node.traits.iter());
+
+        // The traits listed after `insteadof` are plain identifiers, so they need to be
+        // resolved against the traits that are being used.
+        let resolve_trait = |identifier: &SimpleIdentifier| -> ResolvedName {
+            let symbol = identifier
+                .symbol
+                .strip_prefix(b"\\")
+                .unwrap_or(identifier.symbol.as_slice());
+
+            node.traits
+                .iter()
+                .filter_map(|name| name.as_resolved())
+                .find(|name| {
+                    name.original.eq_ignore_ascii_case(symbol)
+                        || name.resolved.eq_ignore_ascii_case(symbol)
+                })
+                .cloned()
+                .unwrap_or_else(|| ResolvedName {
+                    resolved: symbol.into(),
+                    original: identifier.symbol.clone(),
+                })
+        };
+
+        let adaptations = node
+            .adaptations
+            .iter()
+            .map(|adaptation| match &adaptation.kind {
+                TraitUsageAdaptationKind::Alias(alias) => TraitAdaptationEntity::Alias {
+                    r#trait: alias
+                        .r#trait
+                        .as_ref()
+                        .and_then(|name| self.transform_name(name)),
+                    method: alias.method.clone(),
+                    alias: alias.alias.clone(),
+                    visibility: alias
+                        .visibility
+                        .as_ref()
+                        .map(|visibility| visibility.visibility()),
+                },
+                TraitUsageAdaptationKind::Visibility(visibility) => {
+                    TraitAdaptationEntity::Visibility {
+                        r#trait: visibility
+                            .r#trait
+                            .as_ref()
+                            .and_then(|name| self.transform_name(name)),
+                        method: visibility.method.clone(),
+                        visibility: visibility.visibility.visibility(),
+                    }
+                }
+                TraitUsageAdaptationKind::Precedence(precedence) => {
+                    TraitAdaptationEntity::Precedence {
+                        r#trait: precedence
+                            .r#trait
+                            .as_ref()
+                            .and_then(|name| self.transform_name(name)),
+                        method: precedence.method.clone(),
+                        insteadof: precedence.insteadof.iter().map(resolve_trait).collect(),
+                    }
+                }
+            })
+            .collect();
+
+        (traits, adaptations)
+    }
+
+    /// Create a `ClassEntity` for any class-like structure.
+    ///
+    /// Anything that isn't shared between class-likes (parents, interfaces, enum cases, etc)
+    /// is left empty and should be filled in by the caller.
+    fn transform_classish<'b>(
+        &self,
+        name: &Name,
+        kind: ClassEntityKind,
+        members: impl IntoIterator<Item = &'b ClassishMember> + Clone,
+        span: Span,
+    ) -> ClassEntity {
+        let docblock = self.docblock.as_ref();
+        let templates = self.transform_templates(docblock);
+
+        let (methods, mut properties) =
+            self.transform_classish_members(members.clone(), &templates);
+
+        // Properties described in the docblock are only used when there isn't a real property with the same name.
+        for property in self.transform_property_tags(docblock) {
+            if !properties
+                .iter()
+                .any(|declared| declared.name.symbol == property.name.symbol)
+            {
+                properties.push(property);
+            }
+        }
+
+        let constants = self.transform_classish_constants(members.clone());
+
+        let mut traits = Vec::new();
+        let mut trait_adaptations = Vec::new();
+
+        for member in members {
+            if let ClassishMember::TraitUsage(usage) = member {
+                let (used, adaptations) = self.transform_trait_usage(usage);
+
+                traits.extend(used);
+                trait_adaptations.extend(adaptations);
+            }
+        }
+
+        ClassEntity {
+            name: name.to_resolved().clone(),
+            kind,
+            parent: None,
+            interfaces: Vec::new(),
+            traits,
+            trait_adaptations,
+            methods,
+            properties,
+            constants,
+            cases: Vec::new(),
+            backing_type: None,
+            template_arguments: self.transform_template_arguments(docblock),
+            templates,
+            location: Location::new(self.file_id, span),
+        }
     }
 }
 
 impl<'a> Visitor for IndexingVisitor<'a> {
+    fn visit_statement(&mut self, node: &Statement) {
+        self.docblock = match &node.kind {
+            StatementKind::Class(_)
+            | StatementKind::Interface(_)
+            | StatementKind::Trait(_)
+            | StatementKind::UnitEnum(_)
+            | StatementKind::BackedEnum(_)
+            | StatementKind::Function(_) => node.comments.docblock().cloned(),
+            _ => None,
+        };
+
+        walk_statement(self, node);
+    }
+
     fn visit_class_statement(&mut self, node: &ClassStatement) {
-        let (methods, properties) = self.transform_classish_members(&node.body.members);
+        let mut class = self.transform_classish(
+            &node.name,
+            ClassEntityKind::Class,
+            &node.body.members,
+            node.span,
+        );
+
+        // All properties declared in a `readonly` class are implicitly readonly.
+        if node.modifiers.has_readonly() {
+            for property in class.properties.iter_mut() {
+                property.readonly = true;
+            }
+        }
 
         self.index.entities.add_class(ClassEntity {
-            name: node.name.to_resolved().clone(),
-            kind: ClassEntityKind::Class,
-            methods,
-            location: Location::new(self.file_id, node.span),
-        })
+            parent: node
+                .extends
+                .as_ref()
+                .and_then(|extends| self.transform_name(&extends.parent)),
+            interfaces: node
+                .implements
+                .as_ref()
+                .map(|implements| self.transform_names(implements.interfaces.iter()))
+                .unwrap_or_default(),
+            ..class
+        });
+
+        walk_class_statement(self, node);
+    }
+
+    fn visit_interface_statement(&mut self, node: &InterfaceStatement) {
+        let class = self.transform_classish(
+            &node.name,
+            ClassEntityKind::Interface,
+            &node.body.members,
+            node.span,
+        );
+
+        // Interfaces can't have a parent class, the interfaces they extend are
+        // treated in the same way as the interfaces that a class implements.
+        self.index.entities.add_class(ClassEntity {
+            interfaces: node
+                .extends
+                .as_ref()
+                .map(|extends| self.transform_names(extends.parents.iter()))
+                .unwrap_or_default(),
+            ..class
+        });
+
+        walk_interface_statement(self, node);
+    }
+
+    fn visit_trait_statement(&mut self, node: &TraitStatement) {
+        let class = self.transform_classish(
+            &node.name,
+            ClassEntityKind::Trait,
+            &node.body.members,
+            node.span,
+        );
+
+        self.index.entities.add_class(class);
+
+        walk_trait_statement(self, node);
+    }
+
+    fn visit_unit_enum_statement(&mut self, node: &UnitEnumStatement) {
+        let members = node.body.members.iter().filter_map(|member| match member {
+            UnitEnumMember::Classish(member) => Some(member),
+            _ => None,
+        });
+
+        let class = self.transform_classish(&node.name, ClassEntityKind::Enum, members, node.span);
+
+        let cases = node
+            .body
+            .members
+            .iter()
+            .filter_map(|member| match member {
+                UnitEnumMember::Case(case) => Some(EnumCaseEntity {
+                    name: case.name.clone(),
+                    value: None,
+                    location: Location::new(self.file_id, case.span),
+                }),
+                _ => None,
+            })
+            .collect();
+
+        let mut interfaces = self.transform_names(node.implements.iter());
+        interfaces.push(Self::builtin_name(b"UnitEnum"));
+
+        self.index.entities.add_class(ClassEntity {
+            interfaces,
+            cases,
+            ..class
+        });
+
+        walk_unit_enum_statement(self, node);
+    }
+
+    fn visit_backed_enum_statement(&mut self, node: &BackedEnumStatement) {
+        let members = node.body.members.iter().filter_map(|member| match member {
+            BackedEnumMember::Classish(member) => Some(member),
+            _ => None,
+        });
+
+        let class = self.transform_classish(&node.name, ClassEntityKind::Enum, members, node.span);
+
+        let cases = node
+            .body
+            .members
+            .iter()
+            .filter_map(|member| match member {
+                BackedEnumMember::Case(case) => Some(EnumCaseEntity {
+                    name: case.name.clone(),
+                    value: Some(self.transform_constant_value(&case.value)),
+                    location: Location::new(self.file_id, case.span),
+                }),
+                _ => None,
+            })
+            .collect();
+
+        let backing_type = match node.backed_type {
+            BackedEnumType::String(_) => Some(Type::String),
+            BackedEnumType::Int(_) => Some(Type::Integer),
+            BackedEnumType::Invalid => None,
+        };
+
+        // Backed enums implicitly implement `BackedEnum`, which itself extends `UnitEnum`.
+        let mut interfaces = self.transform_names(node.implements.iter());
+        interfaces.push(Self::builtin_name(b"UnitEnum"));
+        interfaces.push(Self::builtin_name(b"BackedEnum"));
+
+        self.index.entities.add_class(ClassEntity {
+            interfaces,
+            cases,
+            backing_type,
+            ..class
+        });
+
+        walk_backed_enum_statement(self, node);
+    }
+
+    fn visit_constant_statement(&mut self, node: &ConstantStatement) {
+        for entry in node.entries.iter() {
+            let Some(name) = self.transform_name(&entry.name) else {
+                continue;
+            };
+
+            self.index.entities.add_constant(ConstantEntity {
+                name,
+                value: self.transform_constant_value(&entry.value),
+                location: Location::new(self.file_id, entry.span),
+            });
+        }
+    }
+
+    fn visit_function_call_expression(&mut self, node: &FunctionCallExpression) {
+        if let Some(constant) = self.transform_define_call(node) {
+            self.index.entities.add_constant(constant);
+        }
+
+        walk_function_call_expression(self, node);
     }
 
     fn visit_function_statement(&mut self, node: &FunctionStatement) {
+        let docblock = self.docblock.as_ref();
+        let templates = self.transform_templates(docblock);
+
         self.index.entities.add_function(FunctionEntity {
             name: node.name.to_resolved().clone(),
-            parameters: self.transform_function_parameter_list(&node.parameters),
-            return_type: self.transform_return_type(node.return_type.as_ref()),
+            parameters: self.transform_function_parameter_list(
+                &node.parameters,
+                docblock,
+                &templates,
+            ),
+            return_type: self.transform_return_type(
+                node.return_type.as_ref(),
+                docblock,
+                &templates,
+            ),
             returns_reference: node.ampersand.is_some(),
+            templates,
             location: Location::new(self.file_id, node.span),
         });
+
+        // Constants can be defined and functions and classes declared when the function is called.
+        walk_function_statement(self, node);
+    }
+}
+
+/// Check if a type refers to any of the given templates.
+fn mentions_template(ty: &Type<ResolvedName>, templates: &[TemplateEntity]) -> bool {
+    match ty {
+        Type::Named(name) => templates
+            .iter()
+            .any(|template| template.name == name.original),
+        Type::Generic(base, arguments) => {
+            mentions_template(base, templates)
+                || arguments
+                    .iter()
+                    .any(|argument| mentions_template(&argument.r#type, templates))
+        }
+        Type::Nullable(inner) => mentions_template(inner, templates),
+        Type::Union(types) | Type::Intersection(types) => {
+            types.iter().any(|ty| mentions_template(ty, templates))
+        }
+        Type::TypedArray(key, value) => {
+            mentions_template(key, templates) || mentions_template(value, templates)
+        }
+        Type::CallableSignature(_, parameters, return_type) => {
+            parameters
+                .iter()
+                .any(|parameter| mentions_template(&parameter.r#type, templates))
+                || mentions_template(return_type, templates)
+        }
+        _ => false,
     }
 }
@@ -1,29 +1,46 @@
-use std::path::Path;
+use std::{
+    collections::HashSet,
+    num::NonZeroUsize,
+    panic::AssertUnwindSafe,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
 use entities::EntityRegistry;
-use file::FileRegistry;
+use file::{hash_contents, FileRegistry};
 
+mod cache;
 mod entities;
+mod error;
 mod file;
 mod indexer;
 mod location;
 mod reflection;
+mod stubs;
 
+pub use error::IndexError;
 pub use file::{FileId, HasFileId};
 use indexer::IndexingVisitor;
 use pxp_ast::{visitor::Visitor, Statement};
 use pxp_bytestring::ByteString;
 use pxp_lexer::Lexer;
 use pxp_parser::Parser;
+use serde::{Deserialize, Serialize};
 
 pub use entities::{FunctionEntity, Parameter, Parameters};
 pub use location::{HasLocation, Location};
 pub use reflection::{
-    ReflectionClass, ReflectionFunction, ReflectionFunctionLike, ReflectionParameter,
-    ReflectsParameters, ReflectionType,
+    CanReflectParameters, ReflectionClass, ReflectionClassConstant, ReflectionConstant,
+    ReflectionEnumCase, ReflectionFunction, ReflectionFunctionLike, ReflectionMethod,
+    ReflectionParameter, ReflectionProperty, ReflectionTemplate, ReflectionType,
+    ReflectsParameters,
 };
+pub use stubs::{is_stub, Extension};
+
+/// The entities found in a single file, along with the hash of the file's contents.
+type IndexedFile = (EntityRegistry, u64);
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Index {
     files: FileRegistry,
     pub(crate) entities: EntityRegistry,
@@ -34,12 +51,221 @@ impl Index {
         Self::default()
     }
 
-    pub fn index_file(&mut self, path: &Path) {
+    pub fn index_file(&mut self, path: &Path) -> Result<FileId, IndexError> {
+        let contents = std::fs::read(path).map_err(|error| IndexError::Io {
+            path: path.to_path_buf(),
+            error,
+        })?;
+
+        Ok(self.update_file(path, &contents))
+    }
+
+    /// Index a set of files in parallel.
+    ///
+    /// Files are read, parsed and indexed on a pool of worker threads. The results are then merged into
+    /// the index in the same order as `paths`, so the result is the same as indexing each file in turn.
+    /// Files whose contents haven't changed since they were last indexed are skipped, and a file that
+    /// appears more than once in `paths` is only indexed once.
+    ///
+    /// Files that can't be read or indexed are skipped and the errors are returned.
+    pub fn index_paths(&mut self, paths: &[PathBuf]) -> Vec<IndexError> {
+        // Overlapping paths, such as a directory and a symlink to it, would otherwise index the same file twice.
+        let mut seen = HashSet::new();
+        let file_ids: Vec<FileId> = paths
+            .iter()
+            .map(|path| self.files.get_or_insert(path))
+            .filter(|file_id| seen.insert(*file_id))
+            .collect();
+
+        let paths: Vec<PathBuf> = file_ids
+            .iter()
+            .map(|file_id| self.files.get_file_path_unchecked(*file_id).to_path_buf())
+            .collect();
+
+        let hashes: Vec<Option<u64>> = file_ids
+            .iter()
+            .map(|file_id| self.files.get_hash(*file_id))
+            .collect();
+
+        let workers = std::thread::available_parallelism()
+            .map_or(1, NonZeroUsize::get)
+            .min(paths.len())
+            .max(1);
+
+        let next = AtomicUsize::new(0);
+        let mut results: Vec<Option<Result<Option<IndexedFile>, IndexError>>> =
+            std::iter::repeat_with(|| None).take(paths.len()).collect();
+
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut indexed = Vec::new();
+
+                        loop {
+                            let i = next.fetch_add(1, Ordering::Relaxed);
+
+                            if i >= paths.len() {
+                                break;
+                            }
+
+                            indexed.push((i, Self::index_path(file_ids[i], &paths[i], hashes[i])));
+                        }
+
+                        indexed
+                    })
+                })
+                .collect();
+
+            for handle in handles {
+                // Panics are caught inside of `index_path`, so the worker itself can't fail.
+                for (i, result) in handle.join().unwrap() {
+                    results[i] = Some(result);
+                }
+            }
+        });
+
+        // Unchanged files keep their existing entities, everything else is replaced or removed.
+        let changed = file_ids
+            .iter()
+            .zip(results.iter())
+            .filter(|(_, result)| !matches!(result, Some(Ok(None))))
+            .map(|(file_id, _)| *file_id)
+            .collect::<HashSet<_>>();
+
+        self.entities.remove_files(&changed);
+
+        let mut errors = Vec::new();
+
+        for (file_id, result) in file_ids.into_iter().zip(results.into_iter().flatten()) {
+            match result {
+                Ok(Some((entities, hash))) => {
+                    self.entities.extend(entities);
+                    self.files.set_hash(file_id, Some(hash));
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    self.files.set_hash(file_id, None);
+                    errors.push(error);
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Read, parse and index a single file into a standalone registry.
+    ///
+    /// If the file's contents match the `previous` hash, the file is skipped and `None` is returned.
+    fn index_path(
+        file_id: FileId,
+        path: &Path,
+        previous: Option<u64>,
+    ) -> Result<Option<IndexedFile>, IndexError> {
+        let contents = std::fs::read(path).map_err(|error| IndexError::Io {
+            path: path.to_path_buf(),
+            error,
+        })?;
+
+        let hash = hash_contents(&contents);
+
+        if previous == Some(hash) {
+            return Ok(None);
+        }
+
+        // The parser shouldn't panic, but one bad file in a vendor directory shouldn't take down the whole process.
+        std::panic::catch_unwind(AssertUnwindSafe(|| {
+            let parse_result = Parser::parse(Lexer::new(&contents));
+            let mut index = Index::new();
+
+            index.index(file_id, &parse_result.ast);
+            Some((index.entities, hash))
+        }))
+        .map_err(|panic| IndexError::Parse {
+            path: path.to_path_buf(),
+            message: panic
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown error".to_string()),
+        })
+    }
+
+    /// Replace everything that the given file contributed to the index with the entities found in `contents`.
+    ///
+    /// Files that haven't been indexed before are added to the index.
+    pub fn update_file(&mut self, path: &Path, contents: &[u8]) -> FileId {
+        let parse_result = Parser::parse(Lexer::new(contents));
+
+        self.update_parsed_file(path, contents, &parse_result.ast)
+    }
+
+    /// Same as `update_file`, but for contents that have already been parsed into the given AST.
+    pub fn update_parsed_file(
+        &mut self,
+        path: &Path,
+        contents: &[u8],
+        ast: &[Statement],
+    ) -> FileId {
         let file_id = self.files.get_or_insert(path);
-        let contents = std::fs::read(path).unwrap();
-        let parse_result = Parser::parse(Lexer::new(&contents));
 
-        self.index(file_id, &parse_result.ast);
+        self.entities.remove_file(file_id);
+        self.index(file_id, ast);
+        self.files.set_hash(file_id, Some(hash_contents(contents)));
+
+        file_id
+    }
+
+    /// Remove everything that the given file contributed to the index.
+    pub fn remove_file(&mut self, file_id: FileId) {
+        self.entities.remove_file(file_id);
+        self.files.set_hash(file_id, None);
+    }
+
+    /// Load the bundled stubs for PHP's built-in functions, classes and constants from the given extensions.
+    ///
+    /// Stubs that are already in the index are left alone, and stubs for any other extensions are removed.
+    pub fn load_stubs(&mut self, extensions: &[Extension]) {
+        for extension in Extension::ALL {
+            let path = extension.path();
+
+            if extensions.contains(&extension) {
+                let contents = extension.contents();
+                let loaded = self
+                    .files
+                    .get_file_id(&path)
+                    .and_then(|file_id| self.files.get_hash(file_id));
+
+                if loaded != Some(hash_contents(contents)) {
+                    self.update_file(&path, contents);
+                }
+            } else if let Some(file_id) = self.files.get_file_id(&path) {
+                self.remove_file(file_id);
+            }
+        }
+    }
+
+    /// Remove everything contributed by files that aren't in `paths`, e.g. files that have been
+    /// deleted since the index was loaded from the cache. Stubs are always kept.
+    pub fn prune_files(&mut self, paths: &[PathBuf]) {
+        let keep = paths
+            .iter()
+            .filter_map(|path| self.files.get_file_id(path))
+            .collect::<HashSet<_>>();
+
+        let removed = self
+            .files
+            .iter()
+            .filter(|(_, path)| !is_stub(path))
+            .map(|(file_id, _)| file_id)
+            .filter(|file_id| !keep.contains(file_id))
+            .collect::<HashSet<_>>();
+
+        for file_id in removed.iter() {
+            self.files.set_hash(*file_id, None);
+        }
+
+        self.entities.remove_files(&removed);
     }
 
     pub fn index(&mut self, file_id: FileId, ast: &[Statement]) {
@@ -61,12 +287,66 @@ impl Index {
             .map(ReflectionFunction::new)
     }
 
+    /// Every function in the index, skipping any that are shadowed by an earlier declaration.
+    pub fn get_functions(&self) -> Vec<ReflectionFunction<'_>> {
+        self.entities
+            .functions()
+            .iter()
+            .filter(|entity| {
+                self.entities
+                    .get_function(entity.name.resolved.as_bytestr())
+                    .is_some_and(|declared| std::ptr::eq(declared, *entity))
+            })
+            .map(ReflectionFunction::new)
+            .collect()
+    }
+
     pub fn number_of_classes(&self) -> usize {
         self.entities.classes().len()
     }
 
     pub fn get_class(&self, name: impl Into<ByteString>) -> Option<ReflectionClass> {
-        self.entities.get_class(name).map(ReflectionClass::new)
+        self.entities
+            .get_class(name)
+            .map(|entity| ReflectionClass::new(entity, self))
+    }
+
+    /// Every class in the index, skipping any that are shadowed by an earlier declaration.
+    pub fn get_classes(&self) -> Vec<ReflectionClass<'_>> {
+        self.entities
+            .classes()
+            .iter()
+            .filter(|entity| {
+                self.entities
+                    .get_class(entity.name.resolved.as_bytestr())
+                    .is_some_and(|declared| std::ptr::eq(declared, *entity))
+            })
+            .map(|entity| ReflectionClass::new(entity, self))
+            .collect()
+    }
+
+    pub fn number_of_constants(&self) -> usize {
+        self.entities.constants().len()
+    }
+
+    pub fn get_constant(&self, name: impl Into<ByteString>) -> Option<ReflectionConstant> {
+        self.entities
+            .get_constant(name)
+            .map(ReflectionConstant::new)
+    }
+
+    /// Every constant in the index, skipping any that are shadowed by an earlier declaration.
+    pub fn get_constants(&self) -> Vec<ReflectionConstant<'_>> {
+        self.entities
+            .constants()
+            .iter()
+            .filter(|entity| {
+                self.entities
+                    .get_constant(entity.name.resolved.as_bytestr())
+                    .is_some_and(|declared| std::ptr::eq(declared, *entity))
+            })
+            .map(ReflectionConstant::new)
+            .collect()
     }
 
     pub fn get_file_path(&self, from: impl HasFileId) -> Option<&std::path::Path> {
@@ -76,4 +356,18 @@ impl Index {
     pub fn get_file_path_unchecked(&self, from: impl HasFileId) -> &std::path::Path {
         self.files.get_file_path_unchecked(from.file_id())
     }
+
+    pub fn get_file_id(&self, path: &Path) -> Option<FileId> {
+        self.files.get_file_id(path)
+    }
+
+    /// The path of every file that's contributing to the index, including stubs. Files that have been
+    /// removed are skipped.
+    pub fn get_file_paths(&self) -> Vec<&Path> {
+        self.files
+            .iter()
+            .filter(|(file_id, _)| self.files.get_hash(*file_id).is_some())
+            .map(|(_, path)| path)
+            .collect()
+    }
 }
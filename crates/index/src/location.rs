@@ -1,8 +1,9 @@
 use pxp_span::{IsSpanned, Span};
+use serde::{Deserialize, Serialize};
 
 use crate::{FileId, HasFileId};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Location {
     file: FileId,
     span: Span,
@@ -0,0 +1,43 @@
+use pxp_bytestring::ByteStr;
+
+use crate::{
+    entities::EnumCaseEntity,
+    location::{HasLocation, Location},
+};
+
+use super::{ReflectionClass, ReflectionType};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReflectionEnumCase<'a> {
+    pub(crate) entity: &'a EnumCaseEntity,
+    pub(crate) owner: ReflectionClass<'a>,
+}
+
+impl<'a> HasLocation for ReflectionEnumCase<'a> {
+    fn location(&self) -> Location {
+        self.entity.location
+    }
+}
+
+impl<'a> ReflectionEnumCase<'a> {
+    pub fn new(entity: &'a EnumCaseEntity, owner: ReflectionClass<'a>) -> Self {
+        Self { entity, owner }
+    }
+
+    pub fn get_name(&self) -> &ByteStr {
+        self.entity.name.symbol.as_ref()
+    }
+
+    pub fn get_enum(&self) -> ReflectionClass<'a> {
+        self.owner
+    }
+
+    pub fn is_backed(&self) -> bool {
+        self.entity.value.is_some()
+    }
+
+    /// Get the type of the case's backing value. Unit enum cases have no backing value.
+    pub fn get_backing_value_type(&self) -> Option<ReflectionType<'a>> {
+        self.entity.value.as_ref().map(ReflectionType::new)
+    }
+}
@@ -1,15 +1,37 @@
+use std::collections::VecDeque;
+
+use pxp_ast::Visibility;
 use pxp_bytestring::ByteStr;
 
 use crate::{
-    entities::{ClassEntity, ClassEntityKind},
+    entities::{ClassEntity, ClassEntityKind, TraitAdaptationEntity},
     location::{HasLocation, Location},
+    Index,
 };
 
-use super::ReflectionMethod;
+use super::{
+    ReflectionClassConstant, ReflectionEnumCase, ReflectionMethod, ReflectionProperty,
+    ReflectionTemplate, ReflectionType,
+};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Clone, Copy)]
 pub struct ReflectionClass<'a> {
     entity: &'a ClassEntity,
+    index: &'a Index,
+}
+
+impl<'a> std::fmt::Debug for ReflectionClass<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReflectionClass")
+            .field("entity", self.entity)
+            .finish()
+    }
+}
+
+impl<'a> PartialEq for ReflectionClass<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.entity, other.entity)
+    }
 }
 
 impl<'a> HasLocation for ReflectionClass<'a> {
@@ -19,15 +41,15 @@ impl<'a> HasLocation for ReflectionClass<'a> {
 }
 
 impl<'a> ReflectionClass<'a> {
-    pub fn new(entity: &'a ClassEntity) -> Self {
-        Self { entity }
+    pub fn new(entity: &'a ClassEntity, index: &'a Index) -> Self {
+        Self { entity, index }
     }
 
-    pub fn name(&self) -> &ByteStr {
+    pub fn name(&self) -> &'a ByteStr {
         self.entity.name.resolved.as_ref()
     }
 
-    pub fn short_name(&self) -> &ByteStr {
+    pub fn short_name(&self) -> &'a ByteStr {
         self.entity.name.original.as_ref()
     }
 
@@ -47,30 +69,447 @@ impl<'a> ReflectionClass<'a> {
         self.entity.kind == ClassEntityKind::Trait
     }
 
-    pub fn get_methods(&self) -> Vec<ReflectionMethod> {
+    /// Get the name of the parent class, even if the parent class isn't in the index.
+    pub fn get_parent_name(&self) -> Option<&'a ByteStr> {
         self.entity
-            .methods
+            .parent
+            .as_ref()
+            .map(|parent| parent.resolved.as_ref())
+    }
+
+    pub fn get_parent(&self) -> Option<ReflectionClass<'a>> {
+        self.entity
+            .parent
+            .as_ref()
+            .and_then(|parent| self.index.get_class(parent.resolved.clone()))
+    }
+
+    /// Get all of the parent classes in the index, starting with the immediate parent.
+    pub fn get_parents(&self) -> Vec<ReflectionClass<'a>> {
+        let mut parents = Vec::new();
+        let mut current = self.get_parent();
+
+        while let Some(parent) = current {
+            // Guard against circular inheritance, which PHP doesn't allow but we can still find in the wild.
+            if parent == *self || parents.contains(&parent) {
+                break;
+            }
+
+            current = parent.get_parent();
+            parents.push(parent);
+        }
+
+        parents
+    }
+
+    /// Get all of the interfaces in the index that this class-like implements, including
+    /// those implemented by parent classes and those extended by other interfaces.
+    pub fn get_interfaces(&self) -> Vec<ReflectionClass<'a>> {
+        let mut interfaces = Vec::new();
+        let mut pending = std::iter::once(*self)
+            .chain(self.get_parents())
+            .flat_map(|class| class.get_declared_interfaces())
+            .collect::<VecDeque<_>>();
+
+        while let Some(interface) = pending.pop_front() {
+            if interface == *self || interfaces.contains(&interface) {
+                continue;
+            }
+
+            pending.extend(interface.get_declared_interfaces());
+            interfaces.push(interface);
+        }
+
+        interfaces
+    }
+
+    /// Get the traits in the index that are used directly by this class-like.
+    pub fn get_traits(&self) -> Vec<ReflectionClass<'a>> {
+        self.entity
+            .traits
             .iter()
-            .map(|m| ReflectionMethod::new(m, self))
+            .filter_map(|name| self.index.get_class(name.resolved.clone()))
             .collect()
     }
 
-    pub fn get_method(&self, name: &ByteStr) -> Option<ReflectionMethod> {
+    /// Check if this class-like extends or implements the given class-like, either directly or through
+    /// one of its ancestors. The given class-like does not need to be in the index.
+    pub fn is_subclass_of(&self, name: &ByteStr) -> bool {
+        let name = name.strip_prefix(b'\\');
+
+        std::iter::once(*self)
+            .chain(self.get_parents())
+            .chain(self.get_interfaces())
+            .any(|class| {
+                class
+                    .get_parent_name()
+                    .is_some_and(|parent| parent.eq_ignore_ascii_case(name))
+                    || class
+                        .entity
+                        .interfaces
+                        .iter()
+                        .any(|interface| interface.resolved.eq_ignore_ascii_case(name))
+            })
+    }
+
+    pub fn get_methods(&self) -> Vec<ReflectionMethod<'a>> {
+        let mut methods: Vec<ReflectionMethod<'a>> = Vec::new();
+
+        for class in self.get_resolution_order() {
+            for method in class.get_declared_methods(&mut Vec::new()) {
+                if !methods
+                    .iter()
+                    .any(|m| m.get_name().eq_ignore_ascii_case(method.get_name()))
+                {
+                    methods.push(method);
+                }
+            }
+        }
+
+        methods
+    }
+
+    pub fn get_method(&self, name: &ByteStr) -> Option<ReflectionMethod<'a>> {
         self.get_methods()
             .into_iter()
-            .find(|method| method.get_name() == name)
+            .find(|method| method.get_name().eq_ignore_ascii_case(name))
     }
 
-    pub fn get_static_methods(&self) -> Vec<ReflectionMethod> {
+    pub fn get_static_methods(&self) -> Vec<ReflectionMethod<'a>> {
         self.get_methods()
             .into_iter()
             .filter(|method| method.is_static())
             .collect()
     }
 
-    pub fn get_static_method(&self, name: &ByteStr) -> Option<ReflectionMethod> {
+    pub fn get_static_method(&self, name: &ByteStr) -> Option<ReflectionMethod<'a>> {
         self.get_static_methods()
             .into_iter()
-            .find(|method| method.get_name() == name)
+            .find(|method| method.get_name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn get_properties(&self) -> Vec<ReflectionProperty<'a>> {
+        let mut properties: Vec<ReflectionProperty<'a>> = Vec::new();
+
+        for class in self.get_resolution_order() {
+            for property in class.get_declared_properties(&mut Vec::new()) {
+                if !properties
+                    .iter()
+                    .any(|p| p.get_name() == property.get_name())
+                {
+                    properties.push(property);
+                }
+            }
+        }
+
+        properties
+    }
+
+    pub fn get_property(&self, name: &ByteStr) -> Option<ReflectionProperty<'a>> {
+        self.get_properties()
+            .into_iter()
+            .find(|property| property.get_name() == name)
+    }
+
+    pub fn get_static_properties(&self) -> Vec<ReflectionProperty<'a>> {
+        self.get_properties()
+            .into_iter()
+            .filter(|property| property.is_static())
+            .collect()
+    }
+
+    pub fn get_static_property(&self, name: &ByteStr) -> Option<ReflectionProperty<'a>> {
+        self.get_static_properties()
+            .into_iter()
+            .find(|property| property.get_name() == name)
+    }
+
+    pub fn get_constants(&self) -> Vec<ReflectionClassConstant<'a>> {
+        let mut constants: Vec<ReflectionClassConstant<'a>> = Vec::new();
+
+        for class in self.get_resolution_order() {
+            for constant in class.get_declared_constants(&mut Vec::new()) {
+                if !constants
+                    .iter()
+                    .any(|c| c.get_name() == constant.get_name())
+                {
+                    constants.push(constant);
+                }
+            }
+        }
+
+        constants
+    }
+
+    pub fn get_constant(&self, name: &ByteStr) -> Option<ReflectionClassConstant<'a>> {
+        self.get_constants()
+            .into_iter()
+            .find(|constant| constant.get_name() == name)
+    }
+
+    pub fn get_cases(&self) -> Vec<ReflectionEnumCase<'a>> {
+        self.entity
+            .cases
+            .iter()
+            .map(|c| ReflectionEnumCase::new(c, *self))
+            .collect()
+    }
+
+    pub fn get_case(&self, name: &ByteStr) -> Option<ReflectionEnumCase<'a>> {
+        self.get_cases()
+            .into_iter()
+            .find(|case| case.get_name() == name)
+    }
+
+    pub fn is_backed_enum(&self) -> bool {
+        self.is_enum() && self.entity.backing_type.is_some()
+    }
+
+    /// Get the backing type of an enum, i.e. `int` or `string`. Unit enums and other class-likes have no backing type.
+    pub fn get_backing_type(&self) -> Option<ReflectionType<'a>> {
+        self.entity.backing_type.as_ref().map(ReflectionType::new)
+    }
+
+    /// Get the templates declared with `@template` tags in the class-like's docblock.
+    pub fn get_templates(&self) -> Vec<ReflectionTemplate<'a>> {
+        self.entity
+            .templates
+            .iter()
+            .map(ReflectionTemplate::new)
+            .collect()
+    }
+
+    /// Get the generic arguments given to a parent or interface with an `@extends` or `@implements` tag.
+    pub fn get_template_arguments(&self, ancestor: &ByteStr) -> Vec<ReflectionType<'a>> {
+        let ancestor = ancestor.strip_prefix(b'\\');
+
+        self.entity
+            .template_arguments
+            .iter()
+            .find(|(name, _)| name.resolved.eq_ignore_ascii_case(ancestor))
+            .map(|(_, arguments)| arguments.iter().map(ReflectionType::new).collect())
+            .unwrap_or_default()
+    }
+
+    /// Get the interfaces in the index that this class-like implements or extends directly.
+    pub fn get_declared_interfaces(&self) -> Vec<ReflectionClass<'a>> {
+        self.entity
+            .interfaces
+            .iter()
+            .filter_map(|name| self.index.get_class(name.resolved.clone()))
+            .collect()
+    }
+
+    /// The order in which PHP resolves members: the class-like itself, then its parents and then its interfaces.
+    fn get_resolution_order(&self) -> Vec<ReflectionClass<'a>> {
+        let mut order = vec![*self];
+        order.extend(self.get_parents());
+        order.extend(self.get_interfaces());
+        order
+    }
+
+    /// Get the traits used by this class-like that haven't been visited yet.
+    ///
+    /// Traits can use other traits, so we need to keep track of the ones we've already seen
+    /// to avoid getting stuck on a trait that (indirectly) uses itself.
+    fn get_unvisited_traits(&self, visited: &mut Vec<&'a ClassEntity>) -> Vec<ReflectionClass<'a>> {
+        visited.push(self.entity);
+
+        let mut traits = Vec::new();
+
+        for r#trait in self.get_traits() {
+            if visited
+                .iter()
+                .any(|entity| std::ptr::eq(*entity, r#trait.entity))
+            {
+                continue;
+            }
+
+            visited.push(r#trait.entity);
+            traits.push(r#trait);
+        }
+
+        traits
+    }
+
+    /// Get the methods declared on this class-like, including those composed from traits.
+    fn get_declared_methods(
+        &self,
+        visited: &mut Vec<&'a ClassEntity>,
+    ) -> Vec<ReflectionMethod<'a>> {
+        let mut methods: Vec<ReflectionMethod<'a>> = self
+            .entity
+            .methods
+            .iter()
+            .map(|m| ReflectionMethod::new(m, *self))
+            .collect();
+
+        // Methods declared on the class-like itself take precedence over methods from traits.
+        for method in self.get_trait_methods(visited) {
+            if !methods
+                .iter()
+                .any(|m| m.get_name().eq_ignore_ascii_case(method.get_name()))
+            {
+                methods.push(method);
+            }
+        }
+
+        methods
+    }
+
+    fn get_trait_methods(&self, visited: &mut Vec<&'a ClassEntity>) -> Vec<ReflectionMethod<'a>> {
+        let mut methods = Vec::new();
+
+        for r#trait in self.get_unvisited_traits(visited) {
+            for method in r#trait.get_declared_methods(visited) {
+                let name = method.get_name();
+
+                if !self.is_trait_method_excluded(&r#trait, name) {
+                    methods.push(method.adapt(
+                        *self,
+                        None,
+                        self.get_trait_method_visibility(&r#trait, name),
+                    ));
+                }
+
+                for (alias, visibility) in self.get_trait_method_aliases(&r#trait, name) {
+                    methods.push(method.adapt(*self, Some(alias), visibility));
+                }
+            }
+        }
+
+        methods
+    }
+
+    fn get_trait_adaptations(
+        &self,
+        r#trait: &ReflectionClass<'a>,
+        name: &ByteStr,
+    ) -> Vec<&'a TraitAdaptationEntity> {
+        let trait_name = r#trait.name();
+
+        self.entity
+            .trait_adaptations
+            .iter()
+            .filter(|adaptation| {
+                let (target, method) = match adaptation {
+                    TraitAdaptationEntity::Alias {
+                        r#trait, method, ..
+                    }
+                    | TraitAdaptationEntity::Visibility {
+                        r#trait, method, ..
+                    }
+                    | TraitAdaptationEntity::Precedence {
+                        r#trait, method, ..
+                    } => (r#trait, method),
+                };
+
+                method.symbol.eq_ignore_ascii_case(name)
+                    && target.as_ref().map_or(true, |target| {
+                        target.resolved.eq_ignore_ascii_case(trait_name)
+                    })
+            })
+            .collect()
+    }
+
+    /// Check if a trait method has been excluded by an `insteadof` rule.
+    fn is_trait_method_excluded(&self, r#trait: &ReflectionClass<'a>, name: &ByteStr) -> bool {
+        let trait_name = r#trait.name();
+
+        self.entity
+            .trait_adaptations
+            .iter()
+            .any(|adaptation| match adaptation {
+                TraitAdaptationEntity::Precedence {
+                    method, insteadof, ..
+                } => {
+                    method.symbol.eq_ignore_ascii_case(name)
+                        && insteadof
+                            .iter()
+                            .any(|excluded| excluded.resolved.eq_ignore_ascii_case(trait_name))
+                }
+                _ => false,
+            })
+    }
+
+    fn get_trait_method_visibility(
+        &self,
+        r#trait: &ReflectionClass<'a>,
+        name: &ByteStr,
+    ) -> Option<Visibility> {
+        self.get_trait_adaptations(r#trait, name)
+            .into_iter()
+            .find_map(|adaptation| match adaptation {
+                TraitAdaptationEntity::Visibility { visibility, .. } => Some(*visibility),
+                _ => None,
+            })
+    }
+
+    fn get_trait_method_aliases(
+        &self,
+        r#trait: &ReflectionClass<'a>,
+        name: &ByteStr,
+    ) -> Vec<(&'a ByteStr, Option<Visibility>)> {
+        self.get_trait_adaptations(r#trait, name)
+            .into_iter()
+            .filter_map(|adaptation| match adaptation {
+                TraitAdaptationEntity::Alias {
+                    alias, visibility, ..
+                } => Some((alias.symbol.as_ref(), *visibility)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Get the properties declared on this class-like, including those composed from traits.
+    fn get_declared_properties(
+        &self,
+        visited: &mut Vec<&'a ClassEntity>,
+    ) -> Vec<ReflectionProperty<'a>> {
+        let mut properties: Vec<ReflectionProperty<'a>> = self
+            .entity
+            .properties
+            .iter()
+            .map(|p| ReflectionProperty::new(p, *self))
+            .collect();
+
+        for r#trait in self.get_unvisited_traits(visited) {
+            for property in r#trait.get_declared_properties(visited) {
+                if !properties
+                    .iter()
+                    .any(|p| p.get_name() == property.get_name())
+                {
+                    properties.push(ReflectionProperty::new(property.entity, *self));
+                }
+            }
+        }
+
+        properties
+    }
+
+    /// Get the constants declared on this class-like, including those composed from traits.
+    fn get_declared_constants(
+        &self,
+        visited: &mut Vec<&'a ClassEntity>,
+    ) -> Vec<ReflectionClassConstant<'a>> {
+        let mut constants: Vec<ReflectionClassConstant<'a>> = self
+            .entity
+            .constants
+            .iter()
+            .map(|c| ReflectionClassConstant::new(c, *self))
+            .collect();
+
+        for r#trait in self.get_unvisited_traits(visited) {
+            for constant in r#trait.get_declared_constants(visited) {
+                if !constants
+                    .iter()
+                    .any(|c| c.get_name() == constant.get_name())
+                {
+                    constants.push(ReflectionClassConstant::new(constant.entity, *self));
+                }
+            }
+        }
+
+        constants
     }
 }
@@ -0,0 +1,94 @@
+use pxp_bytestring::ByteStr;
+
+use crate::{
+    entities::{ClassishConstantEntity, ConstantEntity},
+    location::{HasLocation, Location},
+};
+
+use super::{ReflectionClass, ReflectionType};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReflectionConstant<'a> {
+    pub(crate) entity: &'a ConstantEntity,
+}
+
+impl<'a> HasLocation for ReflectionConstant<'a> {
+    fn location(&self) -> Location {
+        self.entity.location
+    }
+}
+
+impl<'a> ReflectionConstant<'a> {
+    pub fn new(entity: &'a ConstantEntity) -> Self {
+        Self { entity }
+    }
+
+    pub fn get_name(&self) -> &ByteStr {
+        self.entity.name.resolved.as_ref()
+    }
+
+    pub fn get_short_name(&self) -> &ByteStr {
+        self.entity.name.original.as_ref()
+    }
+
+    pub fn in_namespace(&self) -> bool {
+        self.entity.name.resolved != self.entity.name.original
+    }
+
+    /// Get the type of the constant's value, as determined from its initialiser.
+    pub fn get_value_type(&self) -> ReflectionType<'a> {
+        ReflectionType::new(&self.entity.value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReflectionClassConstant<'a> {
+    pub(crate) entity: &'a ClassishConstantEntity,
+    pub(crate) owner: ReflectionClass<'a>,
+}
+
+impl<'a> HasLocation for ReflectionClassConstant<'a> {
+    fn location(&self) -> Location {
+        self.entity.location
+    }
+}
+
+impl<'a> ReflectionClassConstant<'a> {
+    pub fn new(entity: &'a ClassishConstantEntity, owner: ReflectionClass<'a>) -> Self {
+        Self { entity, owner }
+    }
+
+    pub fn get_name(&self) -> &ByteStr {
+        self.entity.name.symbol.as_ref()
+    }
+
+    pub fn get_class(&self) -> ReflectionClass<'a> {
+        self.owner
+    }
+
+    /// Get the declared type of the constant, if it has one.
+    pub fn get_type(&self) -> Option<ReflectionType<'a>> {
+        self.entity.r#type.as_ref().map(ReflectionType::new)
+    }
+
+    /// Get the type of the constant's value, as determined from its initialiser.
+    pub fn get_value_type(&self) -> ReflectionType<'a> {
+        ReflectionType::new(&self.entity.value)
+    }
+
+    pub fn is_public(&self) -> bool {
+        !self.is_protected() && !self.is_private()
+    }
+
+    pub fn is_protected(&self) -> bool {
+        self.entity.modifiers.is_protected()
+    }
+
+    pub fn is_private(&self) -> bool {
+        self.entity.modifiers.is_private()
+    }
+
+    pub fn is_final(&self) -> bool {
+        self.entity.modifiers.has_final()
+    }
+}
@@ -7,7 +7,10 @@ use crate::{
     FunctionEntity,
 };
 
-use super::{parameters::{CanReflectParameters, ReflectionParameter, ReflectsParameters}, ReflectionType};
+use super::{
+    parameters::{CanReflectParameters, ReflectionParameter, ReflectsParameters},
+    ReflectionTemplate, ReflectionType,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ReflectionFunction<'a> {
@@ -60,6 +63,14 @@ impl<'a> ReflectionFunctionLike<'a> for ReflectionFunction<'a> {
     fn returns_reference(&self) -> bool {
         self.entity.returns_reference
     }
+
+    fn get_templates(&self) -> Vec<ReflectionTemplate<'a>> {
+        self.entity
+            .templates
+            .iter()
+            .map(ReflectionTemplate::new)
+            .collect()
+    }
 }
 
 pub trait IsFunctionLike {}
@@ -72,4 +83,7 @@ pub trait ReflectionFunctionLike<'a>: IsFunctionLike {
     }
 
     fn returns_reference(&self) -> bool;
+
+    /// Get the templates declared with `@template` tags in the docblock.
+    fn get_templates(&self) -> Vec<ReflectionTemplate<'a>>;
 }
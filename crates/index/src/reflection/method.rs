@@ -1,4 +1,4 @@
-use pxp_ast::{Name, ResolvedName};
+use pxp_ast::{Name, ResolvedName, Visibility};
 use pxp_bytestring::ByteStr;
 use pxp_type::Type;
 
@@ -10,13 +10,15 @@ use crate::{
 use super::{
     function::{IsFunctionLike, ReflectionFunctionLike},
     parameters::{CanReflectParameters, ReflectsParameters},
-    ReflectionClass, ReflectionParameter, ReflectionType,
+    ReflectionClass, ReflectionParameter, ReflectionTemplate, ReflectionType,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ReflectionMethod<'a> {
     pub(crate) entity: &'a MethodEntity,
-    pub(crate) owner: &'a ReflectionClass<'a>,
+    pub(crate) owner: ReflectionClass<'a>,
+    pub(crate) alias: Option<&'a ByteStr>,
+    pub(crate) visibility: Option<Visibility>,
 }
 
 impl<'a> HasLocation for ReflectionMethod<'a> {
@@ -26,28 +28,65 @@ impl<'a> HasLocation for ReflectionMethod<'a> {
 }
 
 impl<'a> ReflectionMethod<'a> {
-    pub fn new(entity: &'a MethodEntity, owner: &'a ReflectionClass<'a>) -> Self {
-        Self { entity, owner }
+    pub fn new(entity: &'a MethodEntity, owner: ReflectionClass<'a>) -> Self {
+        Self {
+            entity,
+            owner,
+            alias: None,
+            visibility: None,
+        }
+    }
+
+    /// Create a copy of a method that has been composed into `owner` from a trait, with an optional
+    /// alias and visibility from the `use` statement's adaptations.
+    pub(crate) fn adapt(
+        &self,
+        owner: ReflectionClass<'a>,
+        alias: Option<&'a ByteStr>,
+        visibility: Option<Visibility>,
+    ) -> Self {
+        Self {
+            entity: self.entity,
+            owner,
+            alias: alias.or(self.alias),
+            visibility: visibility.or(self.visibility),
+        }
     }
 
     pub fn get_name(&self) -> &ByteStr {
-        self.entity.name.symbol.as_ref()
+        self.alias
+            .unwrap_or_else(|| self.entity.name.symbol.as_ref())
     }
 
-    pub fn get_class(&self) -> &ReflectionClass<'a> {
+    pub fn get_class(&self) -> ReflectionClass<'a> {
         self.owner
     }
 
+    /// Get the visibility of the method, taking any trait adaptations into account.
+    pub fn get_visibility(&self) -> Visibility {
+        if let Some(visibility) = self.visibility {
+            return visibility;
+        }
+
+        if self.entity.modifiers.is_private() {
+            Visibility::Private
+        } else if self.entity.modifiers.is_protected() {
+            Visibility::Protected
+        } else {
+            Visibility::Public
+        }
+    }
+
     pub fn is_public(&self) -> bool {
-        self.entity.modifiers.is_public()
+        self.get_visibility() == Visibility::Public
     }
 
     pub fn is_protected(&self) -> bool {
-        self.entity.modifiers.is_protected()
+        self.get_visibility() == Visibility::Protected
     }
 
     pub fn is_private(&self) -> bool {
-        self.entity.modifiers.is_private()
+        self.get_visibility() == Visibility::Private
     }
 
     pub fn is_static(&self) -> bool {
@@ -85,4 +124,12 @@ impl<'a> ReflectionFunctionLike<'a> for ReflectionMethod<'a> {
     fn returns_reference(&self) -> bool {
         self.entity.returns_reference
     }
+
+    fn get_templates(&self) -> Vec<ReflectionTemplate<'a>> {
+        self.entity
+            .templates
+            .iter()
+            .map(ReflectionTemplate::new)
+            .collect()
+    }
 }
@@ -1,11 +1,19 @@
+mod case;
 mod class;
+mod constant;
 mod function;
 mod method;
 mod parameters;
+mod property;
+mod template;
 mod r#type;
 
+pub use case::ReflectionEnumCase;
 pub use class::ReflectionClass;
+pub use constant::{ReflectionClassConstant, ReflectionConstant};
 pub use function::{ReflectionFunction, ReflectionFunctionLike};
 pub use method::ReflectionMethod;
-pub use parameters::{ReflectionParameter, ReflectsParameters};
+pub use parameters::{CanReflectParameters, ReflectionParameter, ReflectsParameters};
+pub use property::ReflectionProperty;
+pub use template::ReflectionTemplate;
 pub use r#type::ReflectionType;
@@ -35,7 +35,7 @@ impl<'a, O: CanReflectParameters> ReflectionParameter<'a, O> {
     }
 
     pub fn is_variadic(&self) -> bool {
-        todo!()
+        self.entity.variadic
     }
 }
 
@@ -0,0 +1,110 @@
+use pxp_ast::Visibility;
+use pxp_bytestring::ByteStr;
+
+use crate::{
+    entities::PropertyEntity,
+    location::{HasLocation, Location},
+};
+
+use super::{ReflectionClass, ReflectionType};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReflectionProperty<'a> {
+    pub(crate) entity: &'a PropertyEntity,
+    pub(crate) owner: ReflectionClass<'a>,
+}
+
+impl<'a> HasLocation for ReflectionProperty<'a> {
+    fn location(&self) -> Location {
+        self.entity.location
+    }
+}
+
+impl<'a> ReflectionProperty<'a> {
+    pub fn new(entity: &'a PropertyEntity, owner: ReflectionClass<'a>) -> Self {
+        Self { entity, owner }
+    }
+
+    /// Get the name of the property, without the leading `$`.
+    pub fn get_name(&self) -> &ByteStr {
+        self.entity.name.stripped.as_ref()
+    }
+
+    pub fn get_class(&self) -> ReflectionClass<'a> {
+        self.owner
+    }
+
+    pub fn has_type(&self) -> bool {
+        self.entity.r#type.is_some()
+    }
+
+    pub fn get_type(&self) -> Option<ReflectionType<'a>> {
+        self.entity.r#type.as_ref().map(ReflectionType::new)
+    }
+
+    pub fn has_default(&self) -> bool {
+        self.entity.default.is_some()
+    }
+
+    /// Get the type of the property's default value, as determined from its initialiser.
+    pub fn get_default_type(&self) -> Option<ReflectionType<'a>> {
+        self.entity.default.as_ref().map(ReflectionType::new)
+    }
+
+    pub fn get_visibility(&self) -> Visibility {
+        self.entity.visibility
+    }
+
+    /// Get the visibility used when writing to the property.
+    ///
+    /// Properties with asymmetric visibility, e.g. `public private(set)`, have a different
+    /// visibility for writes. Otherwise this is the same as `get_visibility`.
+    pub fn get_set_visibility(&self) -> Visibility {
+        self.entity.set_visibility.unwrap_or(self.entity.visibility)
+    }
+
+    pub fn has_asymmetric_visibility(&self) -> bool {
+        self.get_set_visibility() != self.get_visibility()
+    }
+
+    pub fn is_public(&self) -> bool {
+        self.entity.visibility == Visibility::Public
+    }
+
+    pub fn is_protected(&self) -> bool {
+        self.entity.visibility == Visibility::Protected
+    }
+
+    pub fn is_private(&self) -> bool {
+        self.entity.visibility == Visibility::Private
+    }
+
+    pub fn is_static(&self) -> bool {
+        self.entity.r#static
+    }
+
+    pub fn is_readonly(&self) -> bool {
+        self.entity.readonly
+    }
+
+    pub fn is_promoted(&self) -> bool {
+        self.entity.promoted
+    }
+
+    /// Check if the property is described by a `@property` tag rather than declared on the class-like.
+    pub fn is_magic(&self) -> bool {
+        self.entity.magic
+    }
+
+    pub fn has_hooks(&self) -> bool {
+        self.entity.get_hook || self.entity.set_hook
+    }
+
+    pub fn has_get_hook(&self) -> bool {
+        self.entity.get_hook
+    }
+
+    pub fn has_set_hook(&self) -> bool {
+        self.entity.set_hook
+    }
+}
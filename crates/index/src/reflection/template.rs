@@ -0,0 +1,25 @@
+use pxp_bytestring::ByteStr;
+
+use crate::entities::TemplateEntity;
+
+use super::ReflectionType;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReflectionTemplate<'a> {
+    entity: &'a TemplateEntity,
+}
+
+impl<'a> ReflectionTemplate<'a> {
+    pub fn new(entity: &'a TemplateEntity) -> Self {
+        Self { entity }
+    }
+
+    pub fn get_name(&self) -> &'a ByteStr {
+        self.entity.name.as_ref()
+    }
+
+    /// Get the type that the template is constrained to with `of` or `as`, if there is one.
+    pub fn get_bound(&self) -> Option<ReflectionType<'a>> {
+        self.entity.bound.as_ref().map(ReflectionType::new)
+    }
+}
@@ -0,0 +1,85 @@
+use std::path::{Path, PathBuf};
+
+/// The directory that stub files are registered under, so that they can't clash with real files.
+const STUBS_ROOT: &str = "pxp://stubs";
+
+/// A PHP extension that has bundled stubs for its functions, classes and constants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Extension {
+    Core,
+    Standard,
+    Date,
+    Spl,
+    Json,
+    Pcre,
+    Ctype,
+    Mbstring,
+}
+
+impl Extension {
+    /// Every extension that has bundled stubs.
+    pub const ALL: [Extension; 8] = [
+        Self::Core,
+        Self::Standard,
+        Self::Date,
+        Self::Spl,
+        Self::Json,
+        Self::Pcre,
+        Self::Ctype,
+        Self::Mbstring,
+    ];
+
+    /// The extensions that are always available, since PHP doesn't allow them to be disabled.
+    pub const REQUIRED: [Extension; 6] = [
+        Self::Core,
+        Self::Standard,
+        Self::Date,
+        Self::Spl,
+        Self::Json,
+        Self::Pcre,
+    ];
+
+    /// Get the name of the extension, as reported by `get_loaded_extensions()`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Core => "Core",
+            Self::Standard => "standard",
+            Self::Date => "date",
+            Self::Spl => "SPL",
+            Self::Json => "json",
+            Self::Pcre => "pcre",
+            Self::Ctype => "ctype",
+            Self::Mbstring => "mbstring",
+        }
+    }
+
+    /// Find an extension by name, ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|extension| extension.name().eq_ignore_ascii_case(name))
+    }
+
+    pub(crate) fn contents(&self) -> &'static [u8] {
+        match self {
+            Self::Core => include_bytes!("../stubs/Core.php"),
+            Self::Standard => include_bytes!("../stubs/standard.php"),
+            Self::Date => include_bytes!("../stubs/date.php"),
+            Self::Spl => include_bytes!("../stubs/SPL.php"),
+            Self::Json => include_bytes!("../stubs/json.php"),
+            Self::Pcre => include_bytes!("../stubs/pcre.php"),
+            Self::Ctype => include_bytes!("../stubs/ctype.php"),
+            Self::Mbstring => include_bytes!("../stubs/mbstring.php"),
+        }
+    }
+
+    /// Get the path that the extension's stubs are indexed under.
+    pub fn path(&self) -> PathBuf {
+        Path::new(STUBS_ROOT).join(format!("{}.php", self.name()))
+    }
+}
+
+/// Check whether the given path belongs to a bundled stub file, rather than a file on disk.
+pub fn is_stub(path: &Path) -> bool {
+    path.starts_with(STUBS_ROOT)
+}
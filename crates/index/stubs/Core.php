@@ -0,0 +1,309 @@
+<?php
+
+// Stubs for the `Core` extension.
+
+const PHP_VERSION = '8.3.0';
+const PHP_MAJOR_VERSION = 8;
+const PHP_MINOR_VERSION = 3;
+const PHP_RELEASE_VERSION = 0;
+const PHP_VERSION_ID = 80300;
+const PHP_OS = 'Linux';
+const PHP_OS_FAMILY = 'Linux';
+const PHP_EOL = "\n";
+const PHP_INT_MAX = 9223372036854775807;
+const PHP_INT_MIN = -9223372036854775807;
+const PHP_INT_SIZE = 8;
+const PHP_FLOAT_EPSILON = 2.220446049250313E-16;
+const PHP_FLOAT_MAX = 1.7976931348623157E+308;
+const PHP_FLOAT_MIN = 2.2250738585072014E-308;
+const PHP_FLOAT_DIG = 15;
+const E_ERROR = 1;
+const E_WARNING = 2;
+const E_PARSE = 4;
+const E_NOTICE = 8;
+const E_USER_ERROR = 256;
+const E_USER_WARNING = 512;
+const E_USER_NOTICE = 1024;
+const E_STRICT = 2048;
+const E_DEPRECATED = 8192;
+const E_USER_DEPRECATED = 16384;
+const E_ALL = 32767;
+
+function strlen(string $string): int {}
+
+function strcmp(string $string1, string $string2): int {}
+
+function strcasecmp(string $string1, string $string2): int {}
+
+function func_get_args(): array {}
+
+function func_num_args(): int {}
+
+function function_exists(string $function): bool {}
+
+function class_exists(string $class, bool $autoload = true): bool {}
+
+function interface_exists(string $interface, bool $autoload = true): bool {}
+
+function trait_exists(string $trait, bool $autoload = true): bool {}
+
+function enum_exists(string $enum, bool $autoload = true): bool {}
+
+function method_exists($object_or_class, string $method): bool {}
+
+function property_exists($object_or_class, string $property): bool {}
+
+function get_class(object $object): string {}
+
+function get_parent_class(object|string $object_or_class): string|false {}
+
+function get_object_vars(object $object): array {}
+
+function get_class_methods(object|string $object_or_class): array {}
+
+function is_a(mixed $object_or_class, string $class, bool $allow_string = false): bool {}
+
+function is_subclass_of(mixed $object_or_class, string $class, bool $allow_string = true): bool {}
+
+function define(string $constant_name, mixed $value, bool $case_insensitive = false): bool {}
+
+function defined(string $constant_name): bool {}
+
+function error_reporting(?int $error_level = null): int {}
+
+function trigger_error(string $message, int $error_level = E_USER_NOTICE): bool {}
+
+function set_error_handler(?callable $callback, int $error_levels = E_ALL) {}
+
+function restore_error_handler(): bool {}
+
+function set_exception_handler(?callable $callback) {}
+
+function restore_exception_handler(): bool {}
+
+function extension_loaded(string $extension): bool {}
+
+function gc_collect_cycles(): int {}
+
+interface Traversable {}
+
+interface IteratorAggregate extends Traversable
+{
+    public function getIterator(): Iterator;
+}
+
+interface Iterator extends Traversable
+{
+    public function current(): mixed;
+
+    public function next(): void;
+
+    public function key(): mixed;
+
+    public function valid(): bool;
+
+    public function rewind(): void;
+}
+
+interface ArrayAccess
+{
+    public function offsetExists(mixed $offset): bool;
+
+    public function offsetGet(mixed $offset): mixed;
+
+    public function offsetSet(mixed $offset, mixed $value): void;
+
+    public function offsetUnset(mixed $offset): void;
+}
+
+interface Serializable
+{
+    public function serialize();
+
+    public function unserialize(string $data);
+}
+
+interface Countable
+{
+    public function count(): int;
+}
+
+interface Stringable
+{
+    public function __toString(): string;
+}
+
+interface UnitEnum
+{
+    public static function cases(): array;
+}
+
+interface BackedEnum extends UnitEnum
+{
+    public static function from(int|string $value): static;
+
+    public static function tryFrom(int|string $value): ?static;
+}
+
+interface Throwable extends Stringable
+{
+    public function getMessage(): string;
+
+    public function getCode();
+
+    public function getFile(): string;
+
+    public function getLine(): int;
+
+    public function getTrace(): array;
+
+    public function getTraceAsString(): string;
+
+    public function getPrevious(): ?Throwable;
+}
+
+class Exception implements Throwable
+{
+    protected $message = '';
+    protected $code = 0;
+    protected string $file = '';
+    protected int $line = 0;
+
+    public function __construct(string $message = '', int $code = 0, ?Throwable $previous = null) {}
+
+    final public function getMessage(): string {}
+
+    final public function getCode() {}
+
+    final public function getFile(): string {}
+
+    final public function getLine(): int {}
+
+    final public function getTrace(): array {}
+
+    final public function getTraceAsString(): string {}
+
+    final public function getPrevious(): ?Throwable {}
+
+    public function __toString(): string {}
+}
+
+class ErrorException extends Exception
+{
+    protected int $severity = E_ERROR;
+
+    public function __construct(string $message = '', int $code = 0, int $severity = E_ERROR, ?string $filename = null, ?int $line = null, ?Throwable $previous = null) {}
+
+    final public function getSeverity(): int {}
+}
+
+class Error implements Throwable
+{
+    protected $message = '';
+    protected $code = 0;
+    protected string $file = '';
+    protected int $line = 0;
+
+    public function __construct(string $message = '', int $code = 0, ?Throwable $previous = null) {}
+
+    final public function getMessage(): string {}
+
+    final public function getCode() {}
+
+    final public function getFile(): string {}
+
+    final public function getLine(): int {}
+
+    final public function getTrace(): array {}
+
+    final public function getTraceAsString(): string {}
+
+    final public function getPrevious(): ?Throwable {}
+
+    public function __toString(): string {}
+}
+
+class TypeError extends Error {}
+
+class ValueError extends Error {}
+
+class ArithmeticError extends Error {}
+
+class DivisionByZeroError extends ArithmeticError {}
+
+class ArgumentCountError extends TypeError {}
+
+class UnhandledMatchError extends Error {}
+
+final class Closure
+{
+    private function __construct() {}
+
+    public static function bind(Closure $closure, ?object $newThis, object|string|null $newScope = 'static'): ?Closure {}
+
+    public function bindTo(?object $newThis, object|string|null $newScope = 'static'): ?Closure {}
+
+    public function call(object $newThis, mixed ...$args): mixed {}
+
+    public static function fromCallable(callable $callback): Closure {}
+}
+
+final class Generator implements Iterator
+{
+    public function current(): mixed {}
+
+    public function next(): void {}
+
+    public function key(): mixed {}
+
+    public function valid(): bool {}
+
+    public function rewind(): void {}
+
+    public function send(mixed $value): mixed {}
+
+    public function throw(Throwable $exception): mixed {}
+
+    public function getReturn(): mixed {}
+}
+
+final class WeakReference
+{
+    public static function create(object $object): WeakReference {}
+
+    public function get(): ?object {}
+}
+
+final class WeakMap implements ArrayAccess, Countable, IteratorAggregate
+{
+    public function offsetExists($object): bool {}
+
+    public function offsetGet($object): mixed {}
+
+    public function offsetSet($object, mixed $value): void {}
+
+    public function offsetUnset($object): void {}
+
+    public function count(): int {}
+
+    public function getIterator(): Iterator {}
+}
+
+class stdClass {}
+
+#[Attribute(Attribute::TARGET_CLASS)]
+final class Attribute
+{
+    const TARGET_CLASS = 1;
+    const TARGET_FUNCTION = 2;
+    const TARGET_METHOD = 4;
+    const TARGET_PROPERTY = 8;
+    const TARGET_CLASS_CONSTANT = 16;
+    const TARGET_PARAMETER = 32;
+    const TARGET_ALL = 63;
+    const IS_REPEATABLE = 64;
+
+    public int $flags;
+
+    public function __construct(int $flags = Attribute::TARGET_ALL) {}
+}
@@ -0,0 +1,262 @@
+<?php
+
+// Stubs for the `SPL` extension.
+
+function spl_autoload_register(?callable $callback = null, bool $throw = true, bool $prepend = false): bool {}
+
+function spl_autoload_unregister(callable $callback): bool {}
+
+function spl_object_id(object $object): int {}
+
+function spl_object_hash(object $object): string {}
+
+function iterator_to_array(Traversable|array $iterator, bool $preserve_keys = true): array {}
+
+function iterator_count(Traversable|array $iterator): int {}
+
+function iterator_apply(Traversable $iterator, callable $callback, ?array $args = null): int {}
+
+function class_implements($object_or_class, bool $autoload = true): array|false {}
+
+function class_parents($object_or_class, bool $autoload = true): array|false {}
+
+function class_uses($object_or_class, bool $autoload = true): array|false {}
+
+class LogicException extends Exception {}
+
+class BadFunctionCallException extends LogicException {}
+
+class BadMethodCallException extends BadFunctionCallException {}
+
+class DomainException extends LogicException {}
+
+class InvalidArgumentException extends LogicException {}
+
+class LengthException extends LogicException {}
+
+class OutOfRangeException extends LogicException {}
+
+class RuntimeException extends Exception {}
+
+class OutOfBoundsException extends RuntimeException {}
+
+class OverflowException extends RuntimeException {}
+
+class RangeException extends RuntimeException {}
+
+class UnderflowException extends RuntimeException {}
+
+class UnexpectedValueException extends RuntimeException {}
+
+interface OuterIterator extends Iterator
+{
+    public function getInnerIterator(): ?Iterator;
+}
+
+interface SeekableIterator extends Iterator
+{
+    public function seek(int $offset): void;
+}
+
+class ArrayIterator implements SeekableIterator, ArrayAccess, Countable
+{
+    public function __construct(array|object $array = [], int $flags = 0) {}
+
+    public function offsetExists(mixed $key): bool {}
+
+    public function offsetGet(mixed $key): mixed {}
+
+    public function offsetSet(mixed $key, mixed $value): void {}
+
+    public function offsetUnset(mixed $key): void {}
+
+    public function append(mixed $value): void {}
+
+    public function getArrayCopy(): array {}
+
+    public function count(): int {}
+
+    public function current(): mixed {}
+
+    public function next(): void {}
+
+    public function key(): string|int|null {}
+
+    public function valid(): bool {}
+
+    public function rewind(): void {}
+
+    public function seek(int $offset): void {}
+}
+
+class ArrayObject implements IteratorAggregate, ArrayAccess, Countable
+{
+    const STD_PROP_LIST = 1;
+    const ARRAY_AS_PROPS = 2;
+
+    public function __construct(array|object $array = [], int $flags = 0, string $iteratorClass = ArrayIterator::class) {}
+
+    public function offsetExists(mixed $key): bool {}
+
+    public function offsetGet(mixed $key): mixed {}
+
+    public function offsetSet(mixed $key, mixed $value): void {}
+
+    public function offsetUnset(mixed $key): void {}
+
+    public function append(mixed $value): void {}
+
+    public function getArrayCopy(): array {}
+
+    public function count(): int {}
+
+    public function getIterator(): Iterator {}
+}
+
+class IteratorIterator implements OuterIterator
+{
+    public function __construct(Traversable $iterator, ?string $class = null) {}
+
+    public function getInnerIterator(): ?Iterator {}
+
+    public function current(): mixed {}
+
+    public function next(): void {}
+
+    public function key(): mixed {}
+
+    public function valid(): bool {}
+
+    public function rewind(): void {}
+}
+
+class SplDoublyLinkedList implements Iterator, Countable, ArrayAccess
+{
+    public function push(mixed $value): void {}
+
+    public function pop(): mixed {}
+
+    public function shift(): mixed {}
+
+    public function unshift(mixed $value): void {}
+
+    public function top(): mixed {}
+
+    public function bottom(): mixed {}
+
+    public function isEmpty(): bool {}
+
+    public function toArray(): array {}
+
+    public function offsetExists($index): bool {}
+
+    public function offsetGet($index): mixed {}
+
+    public function offsetSet($index, mixed $value): void {}
+
+    public function offsetUnset($index): void {}
+
+    public function count(): int {}
+
+    public function current(): mixed {}
+
+    public function next(): void {}
+
+    public function key(): int {}
+
+    public function valid(): bool {}
+
+    public function rewind(): void {}
+}
+
+class SplQueue extends SplDoublyLinkedList
+{
+    public function enqueue(mixed $value): void {}
+
+    public function dequeue(): mixed {}
+}
+
+class SplStack extends SplDoublyLinkedList {}
+
+class SplObjectStorage implements Countable, Iterator, ArrayAccess
+{
+    public function attach(object $object, mixed $info = null): void {}
+
+    public function detach(object $object): void {}
+
+    public function contains(object $object): bool {}
+
+    public function getInfo(): mixed {}
+
+    public function setInfo(mixed $info): void {}
+
+    public function offsetExists($object): bool {}
+
+    public function offsetGet($object): mixed {}
+
+    public function offsetSet($object, mixed $info = null): void {}
+
+    public function offsetUnset($object): void {}
+
+    public function count(int $mode = COUNT_NORMAL): int {}
+
+    public function current(): object {}
+
+    public function next(): void {}
+
+    public function key(): int {}
+
+    public function valid(): bool {}
+
+    public function rewind(): void {}
+}
+
+class SplFixedArray implements IteratorAggregate, ArrayAccess, Countable
+{
+    public function __construct(int $size = 0) {}
+
+    public static function fromArray(array $array, bool $preserveKeys = true): SplFixedArray {}
+
+    public function toArray(): array {}
+
+    public function getSize(): int {}
+
+    public function setSize(int $size) {}
+
+    public function offsetExists($index): bool {}
+
+    public function offsetGet($index): mixed {}
+
+    public function offsetSet($index, mixed $value): void {}
+
+    public function offsetUnset($index): void {}
+
+    public function count(): int {}
+
+    public function getIterator(): Iterator {}
+}
+
+class SplFileInfo implements Stringable
+{
+    public function __construct(string $filename) {}
+
+    public function getPath(): string {}
+
+    public function getFilename(): string {}
+
+    public function getExtension(): string {}
+
+    public function getBasename(string $suffix = ''): string {}
+
+    public function getPathname(): string {}
+
+    public function getRealPath(): string|false {}
+
+    public function getSize(): int|false {}
+
+    public function isDir(): bool {}
+
+    public function isFile(): bool {}
+
+    public function __toString(): string {}
+}
@@ -0,0 +1,19 @@
+<?php
+
+// Stubs for the `ctype` extension.
+
+function ctype_alnum(mixed $text): bool {}
+
+function ctype_alpha(mixed $text): bool {}
+
+function ctype_digit(mixed $text): bool {}
+
+function ctype_lower(mixed $text): bool {}
+
+function ctype_upper(mixed $text): bool {}
+
+function ctype_space(mixed $text): bool {}
+
+function ctype_punct(mixed $text): bool {}
+
+function ctype_xdigit(mixed $text): bool {}
@@ -0,0 +1,160 @@
+<?php
+
+// Stubs for the `date` extension.
+
+function time(): int {}
+
+function date(string $format, ?int $timestamp = null): string {}
+
+function gmdate(string $format, ?int $timestamp = null): string {}
+
+function mktime(int $hour, ?int $minute = null, ?int $second = null, ?int $month = null, ?int $day = null, ?int $year = null): int|false {}
+
+function strtotime(string $datetime, ?int $baseTimestamp = null): int|false {}
+
+function checkdate(int $month, int $day, int $year): bool {}
+
+function date_default_timezone_set(string $timezoneId): bool {}
+
+function date_default_timezone_get(): string {}
+
+interface DateTimeInterface
+{
+    const ATOM = 'Y-m-d\TH:i:sP';
+    const COOKIE = 'l, d-M-Y H:i:s T';
+    const ISO8601 = 'Y-m-d\TH:i:sO';
+    const RFC2822 = 'D, d M Y H:i:s O';
+    const RFC3339 = 'Y-m-d\TH:i:sP';
+    const RFC3339_EXTENDED = 'Y-m-d\TH:i:s.vP';
+    const RSS = 'D, d M Y H:i:s O';
+    const W3C = 'Y-m-d\TH:i:sP';
+
+    public function format(string $format): string;
+
+    public function getTimezone(): DateTimeZone|false;
+
+    public function getOffset(): int;
+
+    public function getTimestamp(): int;
+
+    public function diff(DateTimeInterface $targetObject, bool $absolute = false): DateInterval;
+}
+
+class DateTime implements DateTimeInterface
+{
+    public function __construct(string $datetime = 'now', ?DateTimeZone $timezone = null) {}
+
+    public static function createFromFormat(string $format, string $datetime, ?DateTimeZone $timezone = null): DateTime|false {}
+
+    public static function createFromImmutable(DateTimeImmutable $object): static {}
+
+    public static function createFromInterface(DateTimeInterface $object): DateTime {}
+
+    public function format(string $format): string {}
+
+    public function modify(string $modifier): DateTime|false {}
+
+    public function add(DateInterval $interval): DateTime {}
+
+    public function sub(DateInterval $interval): DateTime {}
+
+    public function getTimezone(): DateTimeZone|false {}
+
+    public function setTimezone(DateTimeZone $timezone): DateTime {}
+
+    public function getOffset(): int {}
+
+    public function setTime(int $hour, int $minute, int $second = 0, int $microsecond = 0): DateTime {}
+
+    public function setDate(int $year, int $month, int $day): DateTime {}
+
+    public function setTimestamp(int $timestamp): DateTime {}
+
+    public function getTimestamp(): int {}
+
+    public function diff(DateTimeInterface $targetObject, bool $absolute = false): DateInterval {}
+}
+
+class DateTimeImmutable implements DateTimeInterface
+{
+    public function __construct(string $datetime = 'now', ?DateTimeZone $timezone = null) {}
+
+    public static function createFromFormat(string $format, string $datetime, ?DateTimeZone $timezone = null): DateTimeImmutable|false {}
+
+    public static function createFromMutable(DateTime $object): static {}
+
+    public static function createFromInterface(DateTimeInterface $object): DateTimeImmutable {}
+
+    public function format(string $format): string {}
+
+    public function modify(string $modifier): DateTimeImmutable|false {}
+
+    public function add(DateInterval $interval): DateTimeImmutable {}
+
+    public function sub(DateInterval $interval): DateTimeImmutable {}
+
+    public function getTimezone(): DateTimeZone|false {}
+
+    public function setTimezone(DateTimeZone $timezone): DateTimeImmutable {}
+
+    public function getOffset(): int {}
+
+    public function setTime(int $hour, int $minute, int $second = 0, int $microsecond = 0): DateTimeImmutable {}
+
+    public function setDate(int $year, int $month, int $day): DateTimeImmutable {}
+
+    public function setTimestamp(int $timestamp): DateTimeImmutable {}
+
+    public function getTimestamp(): int {}
+
+    public function diff(DateTimeInterface $targetObject, bool $absolute = false): DateInterval {}
+}
+
+class DateTimeZone
+{
+    const UTC = 1024;
+    const ALL = 2047;
+
+    public function __construct(string $timezone) {}
+
+    public function getName(): string {}
+
+    public function getOffset(DateTimeInterface $datetime): int {}
+
+    public static function listIdentifiers(int $timezoneGroup = DateTimeZone::ALL, ?string $countryCode = null): array {}
+}
+
+class DateInterval
+{
+    public int $y;
+    public int $m;
+    public int $d;
+    public int $h;
+    public int $i;
+    public int $s;
+    public float $f;
+    public int $invert;
+    public mixed $days;
+
+    public function __construct(string $duration) {}
+
+    public function format(string $format): string {}
+
+    public static function createFromDateString(string $datetime): DateInterval|false {}
+}
+
+class DatePeriod implements IteratorAggregate
+{
+    const EXCLUDE_START_DATE = 1;
+    const INCLUDE_END_DATE = 2;
+
+    public function __construct($start, $interval = null, $end = null, $options = null) {}
+
+    public function getStartDate(): DateTimeInterface {}
+
+    public function getEndDate(): ?DateTimeInterface {}
+
+    public function getDateInterval(): DateInterval {}
+
+    public function getIterator(): Iterator {}
+}
@@ -0,0 +1,38 @@
+<?php
+
+// Stubs for the `json` extension.
+
+const JSON_HEX_TAG = 1;
+const JSON_HEX_AMP = 2;
+const JSON_HEX_APOS = 4;
+const JSON_HEX_QUOT = 8;
+const JSON_FORCE_OBJECT = 16;
+const JSON_NUMERIC_CHECK = 32;
+const JSON_UNESCAPED_SLASHES = 64;
+const JSON_PRETTY_PRINT = 128;
+const JSON_UNESCAPED_UNICODE = 256;
+const JSON_PARTIAL_OUTPUT_ON_ERROR = 512;
+const JSON_PRESERVE_ZERO_FRACTION = 1024;
+const JSON_OBJECT_AS_ARRAY = 1;
+const JSON_BIGINT_AS_STRING = 2;
+const JSON_INVALID_UTF8_IGNORE = 1048576;
+const JSON_INVALID_UTF8_SUBSTITUTE = 2097152;
+const JSON_THROW_ON_ERROR = 4194304;
+const JSON_ERROR_NONE = 0;
+
+function json_encode(mixed $value, int $flags = 0, int $depth = 512): string|false {}
+
+function json_decode(string $json, ?bool $associative = null, int $depth = 512, int $flags = 0): mixed {}
+
+function json_validate(string $json, int $depth = 512, int $flags = 0): bool {}
+
+function json_last_error(): int {}
+
+function json_last_error_msg(): string {}
+
+interface JsonSerializable
+{
+    public function jsonSerialize(): mixed;
+}
+
+class JsonException extends Exception {}
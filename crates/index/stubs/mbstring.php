@@ -0,0 +1,35 @@
+<?php
+
+// Stubs for the `mbstring` extension.
+
+const MB_CASE_UPPER = 0;
+const MB_CASE_LOWER = 1;
+const MB_CASE_TITLE = 2;
+
+function mb_strlen(string $string, ?string $encoding = null): int {}
+
+function mb_substr(string $string, int $start, ?int $length = null, ?string $encoding = null): string {}
+
+function mb_strtolower(string $string, ?string $encoding = null): string {}
+
+function mb_strtoupper(string $string, ?string $encoding = null): string {}
+
+function mb_convert_case(string $string, int $mode, ?string $encoding = null): string {}
+
+function mb_strpos(string $haystack, string $needle, int $offset = 0, ?string $encoding = null): int|false {}
+
+function mb_stripos(string $haystack, string $needle, int $offset = 0, ?string $encoding = null): int|false {}
+
+function mb_str_split(string $string, int $length = 1, ?string $encoding = null): array {}
+
+function mb_str_pad(string $string, int $length, string $pad_string = ' ', int $pad_type = STR_PAD_RIGHT, ?string $encoding = null): string {}
+
+function mb_substr_count(string $haystack, string $needle, ?string $encoding = null): int {}
+
+function mb_strwidth(string $string, ?string $encoding = null): int {}
+
+function mb_internal_encoding(?string $encoding = null): string|bool {}
+
+function mb_convert_encoding(array|string $string, string $to_encoding, array|string|null $from_encoding = null): array|string|false {}
+
+function mb_check_encoding(array|string|null $value = null, ?string $encoding = null): bool {}
@@ -0,0 +1,31 @@
+<?php
+
+// Stubs for the `pcre` extension.
+
+const PREG_PATTERN_ORDER = 1;
+const PREG_SET_ORDER = 2;
+const PREG_OFFSET_CAPTURE = 256;
+const PREG_UNMATCHED_AS_NULL = 512;
+const PREG_SPLIT_NO_EMPTY = 1;
+const PREG_SPLIT_DELIM_CAPTURE = 2;
+const PREG_SPLIT_OFFSET_CAPTURE = 4;
+const PREG_GREP_INVERT = 1;
+const PREG_NO_ERROR = 0;
+
+function preg_match(string $pattern, string $subject, &$matches = null, int $flags = 0, int $offset = 0): int|false {}
+
+function preg_match_all(string $pattern, string $subject, &$matches = null, int $flags = 0, int $offset = 0): int|false {}
+
+function preg_replace(string|array $pattern, string|array $replacement, string|array $subject, int $limit = -1, &$count = null): string|array|null {}
+
+function preg_replace_callback(string|array $pattern, callable $callback, string|array $subject, int $limit = -1, &$count = null, int $flags = 0): string|array|null {}
+
+function preg_split(string $pattern, string $subject, int $limit = -1, int $flags = 0): array|false {}
+
+function preg_quote(string $str, ?string $delimiter = null): string {}
+
+function preg_grep(string $pattern, array $array, int $flags = 0): array|false {}
+
+function preg_last_error(): int {}
+
+function preg_last_error_msg(): string {}
@@ -0,0 +1,445 @@
+<?php
+
+// Stubs for the `standard` extension.
+
+const DIRECTORY_SEPARATOR = '/';
+const PATH_SEPARATOR = ':';
+const M_PI = 3.14159265358979323846;
+const M_E = 2.7182818284590452354;
+const PHP_ROUND_HALF_UP = 1;
+const PHP_ROUND_HALF_DOWN = 2;
+const PHP_ROUND_HALF_EVEN = 3;
+const PHP_ROUND_HALF_ODD = 4;
+const SORT_REGULAR = 0;
+const SORT_NUMERIC = 1;
+const SORT_STRING = 2;
+const SORT_FLAG_CASE = 8;
+const COUNT_NORMAL = 0;
+const COUNT_RECURSIVE = 1;
+const ARRAY_FILTER_USE_BOTH = 1;
+const ARRAY_FILTER_USE_KEY = 2;
+const PATHINFO_DIRNAME = 1;
+const PATHINFO_BASENAME = 2;
+const PATHINFO_EXTENSION = 4;
+const PATHINFO_FILENAME = 8;
+const FILE_APPEND = 8;
+const LOCK_EX = 2;
+const ENT_QUOTES = 3;
+const STR_PAD_RIGHT = 1;
+const STR_PAD_LEFT = 0;
+const STR_PAD_BOTH = 2;
+
+function str_contains(string $haystack, string $needle): bool {}
+
+function str_starts_with(string $haystack, string $needle): bool {}
+
+function str_ends_with(string $haystack, string $needle): bool {}
+
+function strtolower(string $string): string {}
+
+function strtoupper(string $string): string {}
+
+function ucfirst(string $string): string {}
+
+function lcfirst(string $string): string {}
+
+function ucwords(string $string, string $separators = " \t\r\n\f\v"): string {}
+
+function trim(string $string, string $characters = " \n\r\t\v\x00"): string {}
+
+function ltrim(string $string, string $characters = " \n\r\t\v\x00"): string {}
+
+function rtrim(string $string, string $characters = " \n\r\t\v\x00"): string {}
+
+function str_replace(array|string $search, array|string $replace, string|array $subject, &$count = null): string|array {}
+
+function str_ireplace(array|string $search, array|string $replace, string|array $subject, &$count = null): string|array {}
+
+function str_repeat(string $string, int $times): string {}
+
+function str_pad(string $string, int $length, string $pad_string = ' ', int $pad_type = STR_PAD_RIGHT): string {}
+
+function str_split(string $string, int $length = 1): array {}
+
+function str_word_count(string $string, int $format = 0, ?string $characters = null): array|int {}
+
+function strrev(string $string): string {}
+
+function substr(string $string, int $offset, ?int $length = null): string {}
+
+function substr_count(string $haystack, string $needle, int $offset = 0, ?int $length = null): int {}
+
+function strpos(string $haystack, string $needle, int $offset = 0): int|false {}
+
+function stripos(string $haystack, string $needle, int $offset = 0): int|false {}
+
+function strrpos(string $haystack, string $needle, int $offset = 0): int|false {}
+
+function strstr(string $haystack, string $needle, bool $before_needle = false): string|false {}
+
+function strtr(string $string, string|array $from, ?string $to = null): string {}
+
+function sprintf(string $format, mixed ...$values): string {}
+
+function vsprintf(string $format, array $values): string {}
+
+function printf(string $format, mixed ...$values): int {}
+
+function implode(array|string $separator, ?array $array = null): string {}
+
+function join(array|string $separator, ?array $array = null): string {}
+
+function explode(string $separator, string $string, int $limit = PHP_INT_MAX): array {}
+
+function nl2br(string $string, bool $use_xhtml = true): string {}
+
+function htmlspecialchars(string $string, int $flags = ENT_QUOTES, ?string $encoding = null, bool $double_encode = true): string {}
+
+function strip_tags(string $string, array|string|null $allowed_tags = null): string {}
+
+function addslashes(string $string): string {}
+
+function number_format(float $num, int $decimals = 0, ?string $decimal_separator = '.', ?string $thousands_separator = ','): string {}
+
+function wordwrap(string $string, int $width = 75, string $break = "\n", bool $cut_long_words = false): string {}
+
+function md5(string $string, bool $binary = false): string {}
+
+function sha1(string $string, bool $binary = false): string {}
+
+function crc32(string $string): int {}
+
+function hash(string $algo, string $data, bool $binary = false, array $options = []): string {}
+
+function base64_encode(string $string): string {}
+
+function base64_decode(string $string, bool $strict = false): string|false {}
+
+function bin2hex(string $string): string {}
+
+function urlencode(string $string): string {}
+
+function rawurlencode(string $string): string {}
+
+function http_build_query(array|object $data, string $numeric_prefix = '', ?string $arg_separator = null, int $encoding_type = 1): string {}
+
+function parse_url(string $url, int $component = -1): int|string|array|null|false {}
+
+function count(Countable|array $value, int $mode = COUNT_NORMAL): int {}
+
+function sizeof(Countable|array $value, int $mode = COUNT_NORMAL): int {}
+
+/**
+ * @template K
+ * @template V
+ * @template R
+ * @param callable(V): R $callback
+ * @param array<K, V> $array
+ * @return array<K, R>
+ */
+function array_map(?callable $callback, array $array, array ...$arrays): array {}
+
+function array_filter(array $array, ?callable $callback = null, int $mode = 0): array {}
+
+function array_reduce(array $array, callable $callback, mixed $initial = null): mixed {}
+
+function array_walk(array|object &$array, callable $callback, mixed $arg = null): bool {}
+
+function array_keys(array $array, mixed $filter_value = null, bool $strict = false): array {}
+
+function array_values(array $array): array {}
+
+function array_merge(array ...$arrays): array {}
+
+function array_merge_recursive(array ...$arrays): array {}
+
+function array_replace(array $array, array ...$replacements): array {}
+
+function array_combine(array $keys, array $values): array {}
+
+function array_flip(array $array): array {}
+
+function array_slice(array $array, int $offset, ?int $length = null, bool $preserve_keys = false): array {}
+
+function array_splice(array &$array, int $offset, ?int $length = null, mixed $replacement = []): array {}
+
+function array_search(mixed $needle, array $haystack, bool $strict = false): int|string|false {}
+
+function in_array(mixed $needle, array $haystack, bool $strict = false): bool {}
+
+function array_key_exists($key, array $array): bool {}
+
+function key_exists($key, array $array): bool {}
+
+function array_key_first(array $array): int|string|null {}
+
+function array_key_last(array $array): int|string|null {}
+
+function array_is_list(array $array): bool {}
+
+function array_unique(array $array, int $flags = SORT_STRING): array {}
+
+function array_reverse(array $array, bool $preserve_keys = false): array {}
+
+function array_sum(array $array): int|float {}
+
+function array_product(array $array): int|float {}
+
+function array_push(array &$array, mixed ...$values): int {}
+
+function array_pop(array &$array): mixed {}
+
+function array_shift(array &$array): mixed {}
+
+function array_unshift(array &$array, mixed ...$values): int {}
+
+function array_fill(int $start_index, int $count, mixed $value): array {}
+
+function array_fill_keys(array $keys, mixed $value): array {}
+
+function array_pad(array $array, int $length, mixed $value): array {}
+
+function array_column(array $array, int|string|null $column_key, int|string|null $index_key = null): array {}
+
+function array_chunk(array $array, int $length, bool $preserve_keys = false): array {}
+
+function array_diff(array $array, array ...$arrays): array {}
+
+function array_diff_key(array $array, array ...$arrays): array {}
+
+function array_intersect(array $array, array ...$arrays): array {}
+
+function array_intersect_key(array $array, array ...$arrays): array {}
+
+function array_rand(array $array, int $num = 1): int|string|array {}
+
+function shuffle(array &$array): bool {}
+
+function range($start, $end, int|float $step = 1): array {}
+
+function compact($var_name, ...$var_names): array {}
+
+function extract(array &$array, int $flags = 0, string $prefix = ''): int {}
+
+function sort(array &$array, int $flags = SORT_REGULAR): bool {}
+
+function rsort(array &$array, int $flags = SORT_REGULAR): bool {}
+
+function usort(array &$array, callable $callback): bool {}
+
+function uasort(array &$array, callable $callback): bool {}
+
+function uksort(array &$array, callable $callback): bool {}
+
+function ksort(array &$array, int $flags = SORT_REGULAR): bool {}
+
+function krsort(array &$array, int $flags = SORT_REGULAR): bool {}
+
+function asort(array &$array, int $flags = SORT_REGULAR): bool {}
+
+function arsort(array &$array, int $flags = SORT_REGULAR): bool {}
+
+function current(array|object $array): mixed {}
+
+function reset(array|object &$array): mixed {}
+
+function end(array|object &$array): mixed {}
+
+function key(array|object $array): int|string|null {}
+
+function is_int(mixed $value): bool {}
+
+function is_integer(mixed $value): bool {}
+
+function is_float(mixed $value): bool {}
+
+function is_string(mixed $value): bool {}
+
+function is_bool(mixed $value): bool {}
+
+function is_array(mixed $value): bool {}
+
+function is_object(mixed $value): bool {}
+
+function is_null(mixed $value): bool {}
+
+function is_numeric(mixed $value): bool {}
+
+function is_scalar(mixed $value): bool {}
+
+function is_iterable(mixed $value): bool {}
+
+function is_countable(mixed $value): bool {}
+
+function is_callable(mixed $value, bool $syntax_only = false, &$callable_name = null): bool {}
+
+function is_resource(mixed $value): bool {}
+
+function intval(mixed $value, int $base = 10): int {}
+
+function floatval(mixed $value): float {}
+
+function boolval(mixed $value): bool {}
+
+function strval(mixed $value): string {}
+
+function gettype(mixed $value): string {}
+
+function get_debug_type(mixed $value): string {}
+
+function settype(mixed &$var, string $type): bool {}
+
+function var_dump(mixed $value, mixed ...$values): void {}
+
+function var_export(mixed $value, bool $return = false): ?string {}
+
+function print_r(mixed $value, bool $return = false): string|bool {}
+
+function serialize(mixed $value): string {}
+
+function unserialize(string $data, array $options = []): mixed {}
+
+function abs(int|float $num): int|float {}
+
+function ceil(int|float $num): float {}
+
+function floor(int|float $num): float {}
+
+function round(int|float $num, int $precision = 0, int $mode = PHP_ROUND_HALF_UP): float {}
+
+function max(mixed $value, mixed ...$values): mixed {}
+
+function min(mixed $value, mixed ...$values): mixed {}
+
+function intdiv(int $num1, int $num2): int {}
+
+function fmod(float $num1, float $num2): float {}
+
+function sqrt(float $num): float {}
+
+function pow(mixed $num, mixed $exponent): int|float|object {}
+
+function rand(int $min = 0, int $max = PHP_INT_MAX): int {}
+
+function mt_rand(int $min = 0, int $max = PHP_INT_MAX): int {}
+
+function random_int(int $min, int $max): int {}
+
+function random_bytes(int $length): string {}
+
+function file_get_contents(string $filename, bool $use_include_path = false, $context = null, int $offset = 0, ?int $length = null): string|false {}
+
+function file_put_contents(string $filename, mixed $data, int $flags = 0, $context = null): int|false {}
+
+function file(string $filename, int $flags = 0, $context = null): array|false {}
+
+function file_exists(string $filename): bool {}
+
+function is_file(string $filename): bool {}
+
+function is_dir(string $filename): bool {}
+
+function is_readable(string $filename): bool {}
+
+function is_writable(string $filename): bool {}
+
+function mkdir(string $directory, int $permissions = 0777, bool $recursive = false, $context = null): bool {}
+
+function rmdir(string $directory, $context = null): bool {}
+
+function unlink(string $filename, $context = null): bool {}
+
+function rename(string $from, string $to, $context = null): bool {}
+
+function copy(string $from, string $to, $context = null): bool {}
+
+function scandir(string $directory, int $sorting_order = 0, $context = null): array|false {}
+
+function glob(string $pattern, int $flags = 0): array|false {}
+
+function dirname(string $path, int $levels = 1): string {}
+
+function basename(string $path, string $suffix = ''): string {}
+
+function pathinfo(string $path, int $flags = 15): array|string {}
+
+function realpath(string $path): string|false {}
+
+function tempnam(string $directory, string $prefix): string|false {}
+
+function sys_get_temp_dir(): string {}
+
+function fopen(string $filename, string $mode, bool $use_include_path = false, $context = null) {}
+
+function fclose($stream): bool {}
+
+function fwrite($stream, string $data, ?int $length = null): int|false {}
+
+function fread($stream, int $length): string|false {}
+
+function fgets($stream, ?int $length = null): string|false {}
+
+function feof($stream): bool {}
+
+function sleep(int $seconds): int {}
+
+function usleep(int $microseconds): void {}
+
+function microtime(bool $as_float = false): string|float {}
+
+function hrtime(bool $as_number = false): array|int|float|false {}
+
+function uniqid(string $prefix = '', bool $more_entropy = false): string {}
+
+function call_user_func(callable $callback, mixed ...$args): mixed {}
+
+function call_user_func_array(callable $callback, array $args): mixed {}
+
+function getenv(?string $name = null, bool $local_only = false): array|string|false {}
+
+function putenv(string $assignment): bool {}
+
+function ini_get(string $option): string|false {}
+
+function ini_set(string $option, string|int|float|bool|null $value): string|false {}
+
+function php_sapi_name(): string|false {}
+
+function phpversion(?string $extension = null): string|false {}
+
+function version_compare(string $version1, string $version2, ?string $operator = null): int|bool {}
+
+function error_log(string $message, int $message_type = 0, ?string $destination = null, ?string $additional_headers = null): bool {}
+
+function header(string $header, bool $replace = true, int $response_code = 0): void {}
+
+function headers_sent(&$filename = null, &$line = null): bool {}
+
+function ob_start($callback = null, int $chunk_size = 0, int $flags = 112): bool {}
+
+function ob_get_clean(): string|false {}
+
+final class __PHP_Incomplete_Class {}
+
+class php_user_filter
+{
+    public string $filtername = '';
+    public mixed $params = '';
+
+    public function filter($in, $out, &$consumed, bool $closing): int {}
+
+    public function onCreate(): bool {}
+
+    public function onClose(): void {}
+}
+
+class Directory
+{
+    public readonly string $path;
+
+    public function close(): void {}
+
+    public function rewind(): void {}
+
+    public function read(): string|false {}
+}
@@ -0,0 +1,26 @@
+<?php
+
+namespace App {
+    const VERSION = '1.0.0';
+}
+
+namespace {
+    const DEBUG = false, LEVEL = 3;
+
+    define('APP_ROOT', __DIR__);
+    define('\TIMEOUT', 30);
+
+    if (! defined('NESTED')) {
+        define('NESTED', [1, 2, 3]);
+    }
+
+    function bootstrap() {
+        define('BOOTSTRAPPED', true);
+    }
+
+    class Kernel {
+        public function boot() {
+            define('KERNEL_BOOTED', 1);
+        }
+    }
+}
@@ -0,0 +1,37 @@
+<?php
+
+class Inventory
+{
+    /**
+     * @param non-empty-string $sku
+     * @param int $quantity
+     * @return non-negative-int
+     */
+    public function add(string $sku, float $quantity): int {}
+
+    /**
+     * @return string
+     */
+    public function isEmpty(): bool {}
+
+    /**
+     * @return list<string>
+     */
+    public function skus() {}
+
+    /**
+     * @param Warehouse $supplier
+     * @return Inventory
+     */
+    public function restock(Supplier $supplier): Inventory {}
+}
+
+class Warehouse {}
+
+class Supplier {}
+
+/**
+ * @param class-string $class
+ * @return bool|null
+ */
+function inventory_has(string $class): ?bool {}
@@ -0,0 +1,22 @@
+<?php
+
+enum Suit
+{
+    case Hearts;
+    case Spades;
+
+    const Wild = self::Spades;
+
+    public function color(): string {}
+}
+
+enum Status: int
+{
+    case Active = 1;
+    case Inactive = 0;
+}
+
+enum Size: string
+{
+    case Small = 'small';
+}
@@ -0,0 +1,32 @@
+<?php
+
+/**
+ * @template TKey of array-key
+ * @template TValue
+ */
+class Collection
+{
+    /**
+     * @template TMapValue
+     * @param callable(TValue): TMapValue $callback
+     * @return Collection<TKey, TMapValue>
+     */
+    public function map(callable $callback): Collection {}
+
+    /**
+     * @return int
+     */
+    public function count(): int {}
+}
+
+/**
+ * @extends Collection<int, User>
+ */
+class UserCollection extends Collection {}
+
+/**
+ * @template T
+ * @param T $value
+ * @return T
+ */
+function identity(mixed $value): mixed {}
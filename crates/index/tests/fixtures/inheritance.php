@@ -0,0 +1,44 @@
+<?php
+
+interface Shape
+{
+    const SIDES = 0;
+
+    public function area(): float;
+}
+
+interface Polygon extends Shape {}
+
+trait HasName
+{
+    protected string $name = 'shape';
+
+    public function name(): string {}
+
+    public function describe(): string {}
+}
+
+trait Describes
+{
+    public function describe(): string {}
+}
+
+abstract class BaseShape implements Polygon
+{
+    use HasName, Describes {
+        Describes::describe insteadof HasName;
+        HasName::describe as protected baseDescribe;
+        name as private;
+    }
+
+    public function draw(): void {}
+}
+
+class Square extends BaseShape implements Countable
+{
+    const SIDES = 4;
+
+    public function area(): float {}
+
+    public function draw(): void {}
+}
@@ -0,0 +1,8 @@
+<?php
+
+interface Contract
+{
+    const VERSION = '1.0';
+
+    public function handle(): void;
+}
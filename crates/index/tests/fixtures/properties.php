@@ -0,0 +1,40 @@
+<?php
+
+class User
+{
+    public string $name = 'Ryan';
+
+    protected static int $count = 0;
+
+    public private(set) ?string $email;
+
+    public string $fullName {
+        get => $this->name;
+    }
+
+    public function __construct(
+        private readonly int $id,
+        protected(set) array $roles = [],
+        string $ignored = '',
+    ) {}
+}
+
+readonly class Point
+{
+    public int $x;
+}
+
+/**
+ * @property string $title
+ * @property-read int $views
+ * @property int $name
+ */
+class Post
+{
+    public string $name;
+
+    /**
+     * @param list<string> $tags
+     */
+    public function __construct(public array $tags) {}
+}
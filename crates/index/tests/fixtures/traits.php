@@ -0,0 +1,6 @@
+<?php
+
+trait Greets
+{
+    public function greet(): string {}
+}
@@ -1,5 +1,13 @@
+use std::path::{Path, PathBuf};
+
 use discoverer::discover;
-use pxp_index::{Index, ReflectionFunctionLike, ReflectsParameters};
+use pxp_bytestring::ByteStr;
+use pxp_ast::Visibility;
+use pxp_index::{
+    is_stub, Extension, HasLocation, Index, IndexError, ReflectionFunctionLike, ReflectsParameters,
+};
+use pxp_lexer::Lexer;
+use pxp_parser::Parser;
 use pxp_type::Type;
 
 #[test]
@@ -52,12 +60,737 @@ fn it_indexes_functions_that_return_by_ref() {
     assert!(d.returns_reference());
 }
 
+#[test]
+fn it_indexes_interfaces() {
+    let index = index();
+
+    let contract = index.get_class("Contract").unwrap();
+
+    assert!(contract.is_interface());
+    assert!(contract.get_method(ByteStr::new(b"handle")).is_some());
+
+    let version = contract.get_constant(ByteStr::new(b"VERSION")).unwrap();
+
+    assert!(version.is_public());
+    assert!(version
+        .get_value_type()
+        .is(&Type::LiteralString(b"1.0".into())));
+}
+
+#[test]
+fn it_indexes_templates_from_docblocks() {
+    let index = index();
+
+    let collection = index.get_class("Collection").unwrap();
+    let templates = collection.get_templates();
+
+    assert_eq!(templates.len(), 2);
+    assert_eq!(templates[0].get_name(), b"TKey");
+    assert!(templates[0].get_bound().unwrap().is(&Type::ArrayKey));
+    assert_eq!(templates[1].get_name(), b"TValue");
+    assert!(templates[1].get_bound().is_none());
+
+    let map = collection.get_method(ByteStr::new(b"map")).unwrap();
+
+    assert_eq!(map.get_templates()[0].get_name(), b"TMapValue");
+    assert!(matches!(
+        map.get_return_type().unwrap().to_type(),
+        Type::Generic(..)
+    ));
+    assert!(matches!(
+        map.get_parameters()[0].get_type().unwrap().to_type(),
+        Type::CallableSignature(..)
+    ));
+
+    let count = collection.get_method(ByteStr::new(b"count")).unwrap();
+
+    assert!(count.get_templates().is_empty());
+    assert!(count.get_return_type().unwrap().is(&Type::Integer));
+
+    let users = index.get_class("UserCollection").unwrap();
+    let arguments = users.get_template_arguments(ByteStr::new(b"Collection"));
+
+    assert_eq!(arguments.len(), 2);
+    assert!(arguments[0].is(&Type::Integer));
+    assert!(users
+        .get_template_arguments(ByteStr::new(b"Countable"))
+        .is_empty());
+
+    let identity = index.get_function("identity").unwrap();
+
+    assert_eq!(identity.get_templates()[0].get_name(), b"T");
+    assert!(!identity.get_return_type().unwrap().is(&Type::Mixed));
+}
+
+#[test]
+fn it_prefers_compatible_docblock_types() {
+    let index = index();
+
+    let inventory = index.get_class("Inventory").unwrap();
+    let add = inventory.get_method(ByteStr::new(b"add")).unwrap();
+    let parameters = add.get_parameters();
+
+    assert!(parameters[0].get_type().unwrap().is(&Type::NonEmptyString));
+    assert!(add.get_return_type().unwrap().is(&Type::NonNegativeInteger));
+
+    // Docblock types that contradict the native type are ignored.
+    assert!(parameters[1].get_type().unwrap().is(&Type::Float));
+
+    let is_empty = inventory.get_method(ByteStr::new(b"isEmpty")).unwrap();
+
+    assert!(is_empty.get_return_type().unwrap().is(&Type::Boolean));
+
+    let skus = inventory.get_method(ByteStr::new(b"skus")).unwrap();
+
+    assert!(matches!(
+        skus.get_return_type().unwrap().to_type(),
+        Type::Generic(..)
+    ));
+
+    // Unrelated class-likes are ignored, even though both are objects.
+    let restock = inventory.get_method(ByteStr::new(b"restock")).unwrap();
+
+    assert!(matches!(
+        restock.get_parameters()[0].get_type().unwrap().to_type(),
+        Type::Named(name) if name.resolved == b"Supplier"
+    ));
+    assert!(matches!(
+        restock.get_return_type().unwrap().to_type(),
+        Type::Named(name) if name.resolved == b"Inventory"
+    ));
+
+    let has = index.get_function("inventory_has").unwrap();
+
+    assert!(has.get_parameters()[0].get_type().unwrap().is(&Type::ClassString));
+    assert!(matches!(
+        has.get_return_type().unwrap().to_type(),
+        Type::Union(..)
+    ));
+}
+
+#[test]
+fn it_indexes_traits() {
+    let index = index();
+
+    let greets = index.get_class("Greets").unwrap();
+
+    assert!(greets.is_trait());
+    assert!(greets.get_method(ByteStr::new(b"greet")).is_some());
+}
+
+#[test]
+fn it_indexes_unit_enums() {
+    let index = index();
+
+    let suit = index.get_class("Suit").unwrap();
+
+    assert!(suit.is_enum());
+    assert!(!suit.is_backed_enum());
+    assert!(suit.get_backing_type().is_none());
+    assert_eq!(suit.get_cases().len(), 2);
+    assert!(!suit.get_case(ByteStr::new(b"Hearts")).unwrap().is_backed());
+    assert!(suit.get_constant(ByteStr::new(b"Wild")).is_some());
+    assert!(suit.get_method(ByteStr::new(b"color")).is_some());
+}
+
+#[test]
+fn it_indexes_backed_enums() {
+    let index = index();
+
+    let status = index.get_class("Status").unwrap();
+
+    assert!(status.is_backed_enum());
+    assert!(status.get_backing_type().unwrap().is(&Type::Integer));
+
+    let active = status.get_case(ByteStr::new(b"Active")).unwrap();
+
+    assert!(active.is_backed());
+    assert!(active.get_backing_value_type().unwrap().is(&Type::Integer));
+
+    let size = index.get_class("Size").unwrap();
+
+    assert!(size.get_backing_type().unwrap().is(&Type::String));
+    assert!(size
+        .get_case(ByteStr::new(b"Small"))
+        .unwrap()
+        .get_backing_value_type()
+        .unwrap()
+        .is(&Type::LiteralString(b"small".into())));
+}
+
+#[test]
+fn it_indexes_properties() {
+    let index = index();
+
+    let user = index.get_class("User").unwrap();
+
+    let name = user.get_property(ByteStr::new(b"name")).unwrap();
+
+    assert!(name.is_public());
+    assert!(name.get_type().unwrap().is(&Type::String));
+    assert!(name
+        .get_default_type()
+        .unwrap()
+        .is(&Type::LiteralString(b"Ryan".into())));
+
+    let count = user.get_static_property(ByteStr::new(b"count")).unwrap();
+
+    assert!(count.is_protected());
+    assert!(count.is_static());
+}
+
+#[test]
+fn it_indexes_properties_with_asymmetric_visibility() {
+    let index = index();
+
+    let user = index.get_class("User").unwrap();
+    let email = user.get_property(ByteStr::new(b"email")).unwrap();
+
+    assert!(email.has_asymmetric_visibility());
+    assert_eq!(email.get_visibility(), Visibility::Public);
+    assert_eq!(email.get_set_visibility(), Visibility::Private);
+    assert!(!email.has_default());
+}
+
+#[test]
+fn it_indexes_hooked_properties() {
+    let index = index();
+
+    let user = index.get_class("User").unwrap();
+    let full_name = user.get_property(ByteStr::new(b"fullName")).unwrap();
+
+    assert!(full_name.has_hooks());
+    assert!(full_name.has_get_hook());
+    assert!(!full_name.has_set_hook());
+}
+
+#[test]
+fn it_indexes_promoted_properties() {
+    let index = index();
+
+    let user = index.get_class("User").unwrap();
+
+    let id = user.get_property(ByteStr::new(b"id")).unwrap();
+
+    assert!(id.is_promoted());
+    assert!(id.is_private());
+    assert!(id.is_readonly());
+    assert!(id.get_type().unwrap().is(&Type::Integer));
+
+    let roles = user.get_property(ByteStr::new(b"roles")).unwrap();
+
+    assert!(roles.is_public());
+    assert_eq!(roles.get_set_visibility(), Visibility::Protected);
+
+    assert!(user.get_property(ByteStr::new(b"ignored")).is_none());
+}
+
+#[test]
+fn it_indexes_properties_from_docblock_tags() {
+    let index = index();
+
+    let post = index.get_class("Post").unwrap();
+
+    let title = post.get_property(ByteStr::new(b"title")).unwrap();
+
+    assert!(title.is_magic());
+    assert!(title.is_public());
+    assert!(!title.is_readonly());
+    assert!(title.get_type().unwrap().is(&Type::String));
+
+    let views = post.get_property(ByteStr::new(b"views")).unwrap();
+
+    assert!(views.is_readonly());
+    assert!(views.get_type().unwrap().is(&Type::Integer));
+
+    // Declared properties take precedence over the docblock.
+    let name = post.get_property(ByteStr::new(b"name")).unwrap();
+
+    assert!(!name.is_magic());
+    assert!(name.get_type().unwrap().is(&Type::String));
+
+    let tags = post.get_property(ByteStr::new(b"tags")).unwrap();
+
+    assert!(tags.is_promoted());
+    assert!(matches!(
+        tags.get_type().unwrap().to_type(),
+        Type::Generic(..)
+    ));
+}
+
+#[test]
+fn it_marks_properties_of_readonly_classes_as_readonly() {
+    let index = index();
+
+    let point = index.get_class("Point").unwrap();
+
+    assert!(point.get_property(ByteStr::new(b"x")).unwrap().is_readonly());
+}
+
+#[test]
+fn it_resolves_parent_classes_and_interfaces() {
+    let index = index();
+
+    let square = index.get_class("Square").unwrap();
+
+    assert_eq!(square.get_parent().unwrap().name(), b"BaseShape");
+    assert_eq!(square.get_parents().len(), 1);
+
+    let interfaces = square.get_interfaces();
+
+    assert_eq!(interfaces.len(), 2);
+    assert!(interfaces.iter().any(|i| i.name() == b"Polygon"));
+    assert!(interfaces.iter().any(|i| i.name() == b"Shape"));
+
+    assert!(square.is_subclass_of(ByteStr::new(b"BaseShape")));
+    assert!(square.is_subclass_of(ByteStr::new(b"Shape")));
+    assert!(square.is_subclass_of(ByteStr::new(b"Countable")));
+    assert!(!square.is_subclass_of(ByteStr::new(b"Square")));
+}
+
+#[test]
+fn it_resolves_inherited_members() {
+    let index = index();
+
+    let square = index.get_class("Square").unwrap();
+
+    let draw = square.get_method(ByteStr::new(b"draw")).unwrap();
+    assert_eq!(draw.get_class().name(), b"Square");
+
+    let name = square.get_method(ByteStr::new(b"name")).unwrap();
+    assert_eq!(name.get_class().name(), b"BaseShape");
+
+    let sides = square.get_constant(ByteStr::new(b"SIDES")).unwrap();
+    assert_eq!(sides.get_class().name(), b"Square");
+
+    let polygon = index.get_class("Polygon").unwrap();
+    let sides = polygon.get_constant(ByteStr::new(b"SIDES")).unwrap();
+    assert_eq!(sides.get_class().name(), b"Shape");
+
+    let property = square.get_property(ByteStr::new(b"name")).unwrap();
+    assert!(property.is_protected());
+}
+
+#[test]
+fn it_applies_trait_adaptations() {
+    let index = index();
+
+    let base = index.get_class("BaseShape").unwrap();
+
+    assert_eq!(base.get_traits().len(), 2);
+
+    let name = base.get_method(ByteStr::new(b"name")).unwrap();
+    assert!(name.is_private());
+
+    let describe = base.get_method(ByteStr::new(b"describe")).unwrap();
+    assert!(describe.is_public());
+
+    let base_describe = base.get_method(ByteStr::new(b"baseDescribe")).unwrap();
+    assert!(base_describe.is_protected());
+
+    assert_eq!(
+        base.get_methods()
+            .iter()
+            .filter(|m| m.get_name() == b"describe")
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn it_indexes_global_constants() {
+    let index = index();
+
+    let debug = index.get_constant("DEBUG").unwrap();
+
+    assert_eq!(debug.get_name(), b"DEBUG");
+    assert!(!debug.in_namespace());
+    assert!(debug.get_value_type().is(&Type::False));
+
+    let level = index.get_constant("LEVEL").unwrap();
+
+    assert!(level.get_value_type().is(&Type::Integer));
+
+    let version = index.get_constant("App\\VERSION").unwrap();
+
+    assert_eq!(version.get_short_name(), b"VERSION");
+    assert!(version.in_namespace());
+}
+
+#[test]
+fn it_indexes_constants_defined_with_define() {
+    let index = index();
+
+    let root = index.get_constant("APP_ROOT").unwrap();
+    let timeout = index.get_constant("TIMEOUT").unwrap();
+    let nested = index.get_constant("NESTED").unwrap();
+
+    assert!(root.get_value_type().is(&Type::Mixed));
+    assert!(timeout.get_value_type().is(&Type::Integer));
+    assert!(nested.get_value_type().is(&Type::Array));
+
+    // Constants defined inside of functions and methods are indexed too.
+    assert!(index
+        .get_constant("BOOTSTRAPPED")
+        .unwrap()
+        .get_value_type()
+        .is(&Type::True));
+    assert!(index
+        .get_constant("KERNEL_BOOTED")
+        .unwrap()
+        .get_value_type()
+        .is(&Type::Integer));
+}
+
+#[test]
+fn it_looks_up_classes_and_functions_case_insensitively() {
+    let index = index();
+
+    assert_eq!(index.get_class("user").unwrap().name(), b"User");
+    assert_eq!(index.get_class("\\USER").unwrap().name(), b"User");
+    assert_eq!(index.get_function("A").unwrap().get_name(), b"a");
+
+    assert!(index.get_constant("debug").is_none());
+    assert!(index.get_constant("\\DEBUG").is_some());
+}
+
+#[test]
+fn it_maps_entities_back_to_their_files() {
+    let index = index();
+
+    let user = index.get_class("User").unwrap();
+    let path = index.get_file_path(user.location()).unwrap();
+
+    assert!(path.ends_with("properties.php"));
+}
+
+#[test]
+fn it_looks_up_methods_case_insensitively() {
+    let index = index();
+
+    let square = index.get_class("square").unwrap();
+
+    let area = square.get_method(ByteStr::new(b"AREA")).unwrap();
+
+    assert_eq!(area.get_name(), b"area");
+    assert!(square.is_subclass_of(ByteStr::new(b"\\baseshape")));
+
+    let base = index.get_class("BaseShape").unwrap();
+
+    assert!(base.get_method(ByteStr::new(b"basedescribe")).is_some());
+}
+
+#[test]
+fn it_replaces_entities_when_a_file_is_updated() {
+    let mut index = Index::new();
+    let path = Path::new("updated.php");
+
+    index.update_file(path, b"<?php function foo() {} class Foo {} const FOO = 1;");
+    let file_id = index.update_file(path, b"<?php function bar() {} class Foo {}");
+
+    assert_eq!(index.number_of_files(), 1);
+    assert_eq!(index.number_of_functions(), 1);
+    assert_eq!(index.number_of_classes(), 1);
+    assert_eq!(index.number_of_constants(), 0);
+    assert!(index.get_function("foo").is_none());
+    assert!(index.get_function("bar").is_some());
+
+    index.remove_file(file_id);
+
+    assert_eq!(index.number_of_functions(), 0);
+    assert!(index.get_class("Foo").is_none());
+}
+
+#[test]
+fn it_indexes_files_that_have_already_been_parsed() {
+    let mut index = Index::new();
+    let path = Path::new("parsed.php");
+    let contents = b"<?php function foo() {} class Foo {}";
+    let result = Parser::parse(Lexer::new(contents));
+
+    let file_id = index.update_parsed_file(path, contents, &result.ast);
+
+    assert_eq!(index.get_file_id(path), Some(file_id));
+    assert!(index.get_function("foo").is_some());
+    assert!(index.get_class("Foo").is_some());
+
+    // Updating the file with the same contents replaces the entities instead of adding to them.
+    index.update_file(path, contents);
+
+    assert_eq!(index.number_of_files(), 1);
+    assert_eq!(index.number_of_functions(), 1);
+    assert_eq!(index.number_of_classes(), 1);
+}
+
+#[test]
+fn it_falls_back_to_other_declarations_when_a_file_is_removed() {
+    let mut index = Index::new();
+
+    let first = index.update_file(Path::new("first.php"), b"<?php function foo(): int {}");
+    index.update_file(Path::new("second.php"), b"<?php function foo(): string {}");
+
+    let foo = index.get_function("foo").unwrap();
+    assert!(foo.get_return_type().unwrap().is(&Type::Integer));
+
+    index.remove_file(first);
+
+    let foo = index.get_function("foo").unwrap();
+    assert!(foo.get_return_type().unwrap().is(&Type::String));
+}
+
+#[test]
+fn it_only_touches_the_updated_file_when_a_file_is_updated_repeatedly() {
+    let mut index = Index::new();
+
+    index.update_file(Path::new("first.php"), b"<?php function foo(): int {} class Foo {}");
+    index.update_file(
+        Path::new("second.php"),
+        b"<?php function foo(): string {} class Bar {} const BAR = 1;",
+    );
+
+    for _ in 0..3 {
+        index.update_file(Path::new("first.php"), b"<?php class Foo {}");
+    }
+
+    // The declaration from the other file takes over, and stays in charge when the function comes back.
+    assert!(index.get_function("foo").unwrap().get_return_type().unwrap().is(&Type::String));
+
+    index.update_file(Path::new("first.php"), b"<?php function foo(): int {} class Foo {}");
+
+    assert_eq!(index.number_of_functions(), 2);
+    assert_eq!(index.number_of_classes(), 2);
+    assert_eq!(index.number_of_constants(), 1);
+    assert!(index.get_function("foo").unwrap().get_return_type().unwrap().is(&Type::String));
+    assert!(index.get_class("Foo").is_some());
+    assert!(index.get_class("Bar").is_some());
+    assert!(index.get_constant("BAR").is_some());
+}
+
+#[test]
+fn it_lists_entities_without_shadowed_declarations() {
+    let mut index = Index::new();
+
+    index.update_file(
+        Path::new("first.php"),
+        b"<?php function foo(): int {} class Foo {} const FOO = 1;",
+    );
+    index.update_file(
+        Path::new("second.php"),
+        b"<?php function foo(): string {} function bar() {} class Foo {} class Bar {}",
+    );
+
+    let functions = index.get_functions();
+    let classes = index.get_classes();
+
+    assert_eq!(functions.len(), 2);
+    assert!(functions[0].get_return_type().unwrap().is(&Type::Integer));
+    assert_eq!(functions[1].get_name(), b"bar");
+    assert_eq!(
+        classes.iter().map(|class| class.name()).collect::<Vec<_>>(),
+        vec![b"Foo", b"Bar"]
+    );
+    assert_eq!(index.get_constants().len(), 1);
+}
+
+#[test]
+fn it_lists_the_paths_of_indexed_files() {
+    let mut index = Index::new();
+
+    index.update_file(Path::new("first.php"), b"<?php class Foo {}");
+    let second = index.update_file(Path::new("second.php"), b"<?php class Bar {}");
+    index.remove_file(second);
+
+    assert_eq!(index.get_file_paths(), vec![Path::new("first.php")]);
+}
+
+#[test]
+fn it_indexes_paths_in_parallel() {
+    let files = discover(&["php"], &["./tests/fixtures"]).expect("Failed to load fixture files.");
+    let mut paths = files
+        .iter()
+        .map(|file| file.to_path_buf())
+        .collect::<Vec<_>>();
+    paths.push(PathBuf::from("./tests/fixtures/missing.php"));
+
+    let mut index = Index::new();
+    let errors = index.index_paths(&paths);
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0], IndexError::Io { .. }));
+    assert!(errors[0].path().ends_with("missing.php"));
+
+    let sequential = self::index();
+
+    assert_eq!(index.number_of_classes(), sequential.number_of_classes());
+    assert_eq!(
+        index.number_of_functions(),
+        sequential.number_of_functions()
+    );
+    assert_eq!(
+        index.number_of_constants(),
+        sequential.number_of_constants()
+    );
+    assert!(index.get_class("Square").unwrap().get_parent().is_some());
+}
+
+#[test]
+fn it_indexes_overlapping_paths_once() {
+    let directory = std::env::temp_dir().join(format!("pxp-index-overlap-{}", std::process::id()));
+    std::fs::create_dir_all(directory.join("src")).unwrap();
+
+    let path = directory.join("src").join("a.php");
+    std::fs::write(&path, "<?php function a(): int {} class A {}").unwrap();
+
+    let mut index = Index::new();
+    let errors = index.index_paths(&[
+        path.clone(),
+        directory.join("src").join("..").join("src").join("a.php"),
+        directory.join(".").join("src").join("a.php"),
+    ]);
+    let file_id = index.get_file_id(&path);
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    assert!(errors.is_empty());
+    assert_eq!(index.number_of_files(), 1);
+    assert_eq!(index.number_of_functions(), 1);
+    assert_eq!(index.number_of_classes(), 1);
+    assert!(file_id.is_some());
+}
+
+#[test]
+fn it_round_trips_through_the_cache() {
+    let index = index();
+    let path = std::env::temp_dir().join(format!("pxp-index-cache-{}.bin", std::process::id()));
+
+    index.save_cache(&path).unwrap();
+    let cached = Index::load_cache(&path).unwrap().unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(cached.number_of_files(), index.number_of_files());
+    assert_eq!(cached.number_of_classes(), index.number_of_classes());
+    assert_eq!(cached.number_of_functions(), index.number_of_functions());
+
+    let user = cached.get_class("User").unwrap();
+
+    let email = user.get_property(ByteStr::new(b"email")).unwrap();
+    let path = cached.get_file_path(user.location()).unwrap();
+
+    assert!(email.has_type());
+    assert!(path.ends_with("properties.php"));
+}
+
+#[test]
+fn it_returns_nothing_when_there_is_no_cache() {
+    let path = std::env::temp_dir().join("pxp-index-cache-that-does-not-exist.bin");
+
+    assert!(Index::load_cache(&path).unwrap().is_none());
+}
+
+#[test]
+fn it_only_reindexes_files_that_have_changed() {
+    let directory = std::env::temp_dir().join(format!("pxp-index-changes-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+
+    let a = directory.join("a.php");
+    let b = directory.join("b.php");
+    std::fs::write(&a, "<?php function a(): int {}").unwrap();
+    std::fs::write(&b, "<?php function b(): int {}").unwrap();
+
+    let mut index = Index::new();
+    let paths = vec![a.clone(), b.clone()];
+
+    assert!(index.index_paths(&paths).is_empty());
+
+    std::fs::write(&b, "<?php function b(): string {}").unwrap();
+    std::fs::remove_file(&a).unwrap();
+
+    let remaining = vec![b];
+    let errors = index.index_paths(&remaining);
+    index.prune_files(&remaining);
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    assert!(errors.is_empty());
+    assert!(index.get_function("a").is_none());
+
+    let b = index.get_function("b").unwrap();
+
+    assert!(b.get_return_type().unwrap().is(&Type::String));
+}
+
+#[test]
+fn it_parses_every_stub_file_without_errors() {
+    for extension in Extension::ALL {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("stubs")
+            .join(format!("{}.php", extension.name()));
+
+        let contents = std::fs::read(&path).unwrap();
+        let result = Parser::parse(Lexer::new(&contents));
+
+        assert!(
+            result.diagnostics.is_empty(),
+            "{} has errors: {:?}",
+            path.display(),
+            result.diagnostics
+        );
+    }
+}
+
+#[test]
+fn it_loads_stubs_for_builtin_functions_and_classes() {
+    let mut index = Index::new();
+    index.load_stubs(&Extension::REQUIRED);
+
+    let strlen = index.get_function("strlen").unwrap();
+    let immutable = index.get_class("DateTimeImmutable").unwrap();
+    let format = immutable.get_method(ByteStr::new(b"format")).unwrap();
+
+    assert!(strlen.get_return_type().unwrap().is(&Type::Integer));
+    assert!(immutable.is_subclass_of(ByteStr::new(b"DateTimeInterface")));
+    assert!(format.get_return_type().unwrap().is(&Type::String));
+    assert!(index
+        .get_class("InvalidArgumentException")
+        .unwrap()
+        .is_subclass_of(ByteStr::new(b"Throwable")));
+    assert!(index.get_constant("PHP_EOL").is_some());
+    assert!(is_stub(index.get_file_path_unchecked(strlen.location())));
+}
+
+#[test]
+fn it_only_loads_stubs_for_selected_extensions() {
+    let mut index = Index::new();
+
+    index.load_stubs(&[Extension::Core, Extension::Mbstring]);
+    assert!(index.get_function("mb_strlen").is_some());
+    assert!(index.get_function("str_contains").is_none());
+
+    index.load_stubs(&[Extension::Core, Extension::Standard]);
+    assert!(index.get_function("mb_strlen").is_none());
+    assert!(index.get_function("str_contains").is_some());
+}
+
+#[test]
+fn it_keeps_stubs_when_pruning_files() {
+    let mut index = Index::new();
+    index.load_stubs(&[Extension::Core]);
+    index.prune_files(&[]);
+
+    assert!(index.get_function("strlen").is_some());
+}
+
+#[test]
+fn it_finds_extensions_by_name() {
+    assert_eq!(Extension::from_name("spl"), Some(Extension::Spl));
+    assert_eq!(Extension::from_name("mbstring"), Some(Extension::Mbstring));
+    assert_eq!(Extension::from_name("xdebug"), None);
+}
+
 fn index() -> Index {
     let mut index = Index::new();
     let files = discover(&["php"], &["./tests/fixtures"]).expect("Failed to load fixture files.");
 
     for file in files.iter() {
-        index.index_file(&file);
+        index.index_file(&file).unwrap();
     }
 
     index
@@ -5,26 +5,35 @@ use std::{
 };
 
 use pxp_ast::{
-    visitor::{walk_expression, Visitor},
+    visitor::{walk_expression, walk_statement, Visitor},
     *,
 };
 use pxp_bytestring::{ByteStr, ByteString};
-use pxp_index::{Index, ReflectionClass, ReflectionFunctionLike};
+use pxp_index::{
+    CanReflectParameters, Index, ReflectionClass, ReflectionFunction, ReflectionFunctionLike,
+    ReflectionMethod, ReflectionParameter, ReflectionProperty, ReflectsParameters,
+};
 use pxp_token::TokenKind;
-use pxp_type::{ConstExpr, Type};
+use pxp_type::{CallableParameter, ConstExpr, GenericTypeArgument, Type};
 use visitor::{
-    walk_array_expression, walk_concat_expression, walk_die_expression, walk_empty_expression,
-    walk_error_suppress_expression, walk_eval_expression, walk_exit_expression,
-    walk_function_call_expression, walk_function_closure_creation_expression,
-    walk_function_statement, walk_include_expression, walk_include_once_expression,
-    walk_instanceof_expression, walk_isset_expression, walk_method_call_expression,
-    walk_method_closure_creation_expression, walk_new_expression,
-    walk_nullsafe_method_call_expression, walk_parenthesized_expression, walk_print_expression,
+    walk_array_expression, walk_arrow_function_expression, walk_backed_enum_statement,
+    walk_braced_namespace, walk_class_statement, walk_closure_expression, walk_concat_expression,
+    walk_constant_fetch_expression, walk_die_expression, walk_empty_expression,
+    walk_error_suppress_expression, walk_eval_expression, walk_exit_expression, walk_for_statement,
+    walk_foreach_statement, walk_function_call_expression,
+    walk_function_closure_creation_expression, walk_function_statement, walk_include_expression,
+    walk_include_once_expression, walk_instanceof_expression, walk_interface_statement,
+    walk_isset_expression, walk_logical_operation_expression, walk_method,
+    walk_method_call_expression, walk_method_closure_creation_expression, walk_new_expression,
+    walk_nullsafe_method_call_expression, walk_nullsafe_property_fetch_expression,
+    walk_parenthesized_expression, walk_print_expression, walk_property_fetch_expression,
     walk_reference_expression, walk_require_expression, walk_require_once_expression,
-    walk_static_method_call_expression, walk_unset_expression,
+    walk_static_method_call_expression, walk_static_property_fetch_expression,
+    walk_throw_expression, walk_trait_statement, walk_unbraced_namespace, walk_unit_enum_statement,
+    walk_unset_expression,
 };
 
-use crate::TypeMap;
+use crate::{generics::Generics, TypeMap};
 
 /// The `TypeEngine` is responsible for generating a `TypeMap` for a given AST.
 /// It uses the provided `Index` to resolve types for method calls, property accesses, etc.
@@ -46,6 +55,9 @@ impl<'a> TypeEngine<'a> {
             map: &mut map,
             index: self.index,
             scopes: ScopeStack::new(),
+            docblock: None,
+            class: None,
+            namespace: None,
         };
 
         generator.visit(ast);
@@ -57,8 +69,16 @@ struct TypeMapGenerator<'a> {
     map: &'a mut TypeMap,
     index: &'a Index,
     scopes: ScopeStack,
+    /// The docblock of the function-like that is being visited, used to find the types of its parameters.
+    docblock: Option<DocBlock>,
+    /// The class-like that is being visited, used to resolve `self`, `static` and `parent`.
+    class: Option<ResolvedName>,
+    /// The namespace that is being visited, used to find unqualified functions and constants.
+    namespace: Option<ByteString>,
 }
 
+const THIS: &[u8] = b"$this";
+
 struct ScopeStack {
     scopes: Vec<Scope>,
 }
@@ -117,21 +137,124 @@ impl Scope {
     }
 
     fn get_variable(&self, variable: &SimpleVariable) -> Option<Type<ResolvedName>> {
-        if let Some(ty) = self.variables.get(&variable.symbol) {
+        self.get(&variable.symbol)
+    }
+
+    fn get(&self, symbol: &ByteString) -> Option<Type<ResolvedName>> {
+        if let Some(ty) = self.variables.get(symbol) {
             return Some(ty.clone());
         }
 
         if let Some(outer) = &self.outer {
-            return outer.borrow().get_variable(variable);
+            return outer.borrow().get(symbol);
         }
 
         None
     }
+
+    fn get_this(&self) -> Option<Type<ResolvedName>> {
+        self.get(&ByteString::from(THIS))
+    }
+
+    fn set_this(&mut self, ty: Type<ResolvedName>) {
+        self.variables.insert(ByteString::from(THIS), ty);
+    }
 }
 
 impl<'a> TypeMapGenerator<'a> {
-    fn unwrap_data_type(&self, data_type: Option<&'a DataType>) -> Type<ResolvedName> {
-        data_type.map_or(Type::Mixed, |ty| ty.get_type().clone())
+    /// Determine the type of a declaration, preferring the docblock type when it is compatible with the native one.
+    fn unwrap_data_type(
+        &self,
+        native: Option<&DataType>,
+        docblock: Option<&DataType>,
+    ) -> Type<ResolvedName> {
+        match (native, docblock) {
+            (Some(native), Some(docblock))
+                if self.refines(docblock.get_type(), native.get_type()) =>
+            {
+                docblock.get_type().clone()
+            }
+            (Some(native), _) => native.get_type().clone(),
+            (None, Some(docblock)) => docblock.get_type().clone(),
+            (None, None) => Type::Mixed,
+        }
+    }
+
+    /// Check if one type refines another, using the index to see how class-likes are related.
+    fn refines(&self, ty: &Type<ResolvedName>, other: &Type<ResolvedName>) -> bool {
+        ty.refines_with(other, &|class, parent| self.is_subclass(class, parent))
+    }
+
+    /// Check if a class-like is the same as another one, or extends or implements it.
+    fn is_subclass(&self, class: &ResolvedName, parent: &ResolvedName) -> bool {
+        class.resolved.eq_ignore_ascii_case(&parent.resolved)
+            || self
+                .index
+                .get_class(class.resolved.clone())
+                .is_some_and(|class| class.is_subclass_of(parent.resolved.as_bytestr()))
+    }
+
+    /// Determine the type of a parameter from its declaration and the docblock of the function-like.
+    fn parameter_type(
+        &self,
+        native: Option<&DataType>,
+        variable: &SimpleVariable,
+    ) -> Type<ResolvedName> {
+        let docblock = self
+            .docblock
+            .as_ref()
+            .and_then(|docblock| docblock.get_param_type(variable));
+
+        self.unwrap_data_type(native, docblock)
+    }
+
+    /// Visit the body of a class-like, keeping track of its name for any `self`, `static` and `parent` references.
+    fn within_class(&mut self, name: &Name, walk: impl FnOnce(&mut Self)) {
+        let outer = self.class.replace(name.to_resolved().clone());
+
+        walk(self);
+
+        self.class = outer;
+    }
+
+    /// Visit the statements of a namespace, keeping track of its name for any unqualified function and constant references.
+    fn within_namespace(&mut self, name: Option<&SimpleIdentifier>, walk: impl FnOnce(&mut Self)) {
+        let outer = std::mem::replace(&mut self.namespace, name.map(|name| name.symbol.clone()));
+
+        walk(self);
+
+        self.namespace = outer;
+    }
+
+    /// Find the function or constant that an unqualified name refers to. PHP looks inside of the
+    /// current namespace first and falls back to the global namespace.
+    fn find_unqualified<T>(
+        &self,
+        name: &UnresolvedName,
+        find: impl Fn(&ByteString) -> Option<T>,
+    ) -> Option<T> {
+        self.namespace
+            .as_ref()
+            .and_then(|namespace| {
+                find(&namespace.coagulate(std::slice::from_ref(&name.symbol), Some(b"\\")))
+            })
+            .or_else(|| find(&name.symbol))
+    }
+
+    /// Apply the type from an inline `@var` tag to a variable, as long as it is compatible with what we already know.
+    fn annotate_variable(&mut self, variable: &SimpleVariable, data_type: &DataType) {
+        let annotated = data_type.get_type();
+        let current = self.scopes.current().get_variable(variable);
+
+        if current
+            .as_ref()
+            .map_or(true, |current| self.refines(annotated, current))
+        {
+            self.scopes
+                .current_mut()
+                .set_variable(variable, annotated.clone());
+            self.map.insert(variable.id, annotated.clone());
+        }
     }
 
     fn is_newable_string(&self, value: &ByteStr) -> bool {
@@ -151,13 +274,14 @@ impl<'a> TypeMapGenerator<'a> {
     fn determine_function_call_target_return_type(
         &self,
         target: &Expression,
+        arguments: &ArgumentList,
     ) -> Type<ResolvedName> {
         match &target.kind {
             ExpressionKind::Name(name) => {
-                self.get_function_call_target_return_type_from_name(name.as_ref())
+                self.get_function_call_target_return_type_from_name(name.as_ref(), arguments)
             }
             ExpressionKind::Parenthesized(inner) => {
-                self.determine_function_call_target_return_type(&inner.expr)
+                self.determine_function_call_target_return_type(&inner.expr, arguments)
             }
             ExpressionKind::Closure(inner) => inner
                 .return_type
@@ -168,11 +292,15 @@ impl<'a> TypeMapGenerator<'a> {
                 LiteralKind::String if self.is_callable_string(inner.token.symbol.as_ref()) => self
                     .get_function_call_target_return_type_from_callable_string(
                         inner.token.symbol.as_ref(),
+                        arguments,
                     ),
                 _ => Type::Mixed,
             },
-            // FIXME: Support other callable types here.
-            _ => Type::Mixed,
+            // Anything else could hold a closure, which carries its return type in its signature.
+            _ => match self.map.resolve(target.id) {
+                Type::CallableSignature(_, _, return_type) => *return_type.clone(),
+                _ => Type::Mixed,
+            },
         }
     }
 
@@ -190,7 +318,9 @@ impl<'a> TypeMapGenerator<'a> {
                     None => return None,
                 }
             }
-            Type::Nullable(inner) => return self.determine_class_from_type(inner),
+            Type::Generic(base, _) | Type::Nullable(base) => {
+                return self.determine_class_from_type(base)
+            }
             Type::Union(inners) | Type::Intersection(inners) => {
                 classes.extend(
                     inners
@@ -199,8 +329,11 @@ impl<'a> TypeMapGenerator<'a> {
                         .flatten(),
                 );
             }
-            Type::SelfReference | Type::StaticReference | Type::ParentReference => todo!(),
-            _ => unreachable!(),
+            // Outside of a member's signature, these can only refer to the class-like that we're inside of.
+            Type::SelfReference | Type::StaticReference => classes.push(self.current_class()?),
+            Type::ParentReference => classes.push(self.current_class()?.get_parent()?),
+            // We don't know which class-like a plain `object` is.
+            _ => return None,
         };
 
         Some(classes)
@@ -209,6 +342,7 @@ impl<'a> TypeMapGenerator<'a> {
     fn get_function_call_target_return_type_from_callable_string(
         &self,
         name: &ByteStr,
+        arguments: &ArgumentList,
     ) -> Type<ResolvedName> {
         let name: &ByteStr = name[1..name.len() - 1].into();
 
@@ -218,33 +352,346 @@ impl<'a> TypeMapGenerator<'a> {
         }
 
         match self.index.get_function(name) {
-            Some(function) => function
-                .get_return_type()
-                .as_ref()
-                .map(|t| t.to_type())
-                .unwrap_or_else(|| &Type::Mixed)
-                .clone(),
+            Some(function) => self.function_return_type(function, arguments),
             None => Type::Mixed,
         }
     }
 
-    fn get_function_call_target_return_type_from_name(&self, name: &Name) -> Type<ResolvedName> {
-        match &name.kind {
-            NameKind::Resolved(inner) => match self.index.get_function(inner.resolved.as_bytestr())
-            {
-                Some(function) => function
-                    .get_return_type()
-                    .as_ref()
-                    .map(|t| t.to_type())
-                    .unwrap_or_else(|| &Type::Mixed)
-                    .clone(),
-                None => Type::Mixed,
-            },
+    fn get_function_call_target_return_type_from_name(
+        &self,
+        name: &Name,
+        arguments: &ArgumentList,
+    ) -> Type<ResolvedName> {
+        let function = match &name.kind {
+            NameKind::Resolved(inner) => self.index.get_function(inner.resolved.as_bytestr()),
+            NameKind::Unresolved(inner) => {
+                self.find_unqualified(inner, |name| self.index.get_function(name.as_bytestr()))
+            }
+            NameKind::Special(_) => None,
+        };
+
+        match function {
+            Some(function) => self.function_return_type(function, arguments),
+            None => Type::Mixed,
+        }
+    }
+
+    /// Determine the return type of a function call, resolving any templates from the arguments.
+    fn function_return_type(
+        &self,
+        function: ReflectionFunction,
+        arguments: &ArgumentList,
+    ) -> Type<ResolvedName> {
+        let Some(return_type) = function.get_return_type() else {
+            return Type::Mixed;
+        };
+
+        let mut generics = Generics::new();
+
+        for template in function.get_templates() {
+            generics.declare(&template);
+        }
+
+        self.bind_arguments(&mut generics, &function.get_parameters(), arguments);
+        generics.substitute(return_type.to_type())
+    }
+
+    /// Determine the return type of a method call, resolving any templates from the type of the
+    /// object that it's called on, the class-like's ancestors and the arguments.
+    fn method_return_type(
+        &self,
+        receiver: &Type<ResolvedName>,
+        class: ReflectionClass<'a>,
+        method: &ReflectionMethod<'a>,
+        arguments: &ArgumentList,
+    ) -> Option<Type<ResolvedName>> {
+        let return_type = method.get_return_type()?;
+        let receiver = class_type(receiver, class);
+        let mut generics = self.receiver_generics(&receiver, class, method.get_class());
+
+        for template in method.get_templates() {
+            generics.declare(&template);
+        }
+
+        self.bind_arguments(&mut generics, &method.get_parameters(), arguments);
+
+        Some(relative_type(
+            &generics.substitute(return_type.to_type()),
+            &receiver,
+            method.get_class(),
+        ))
+    }
+
+    /// Bring the templates of the class-like that declares a member into scope, using the generic
+    /// arguments of the object that the member is accessed on.
+    fn receiver_generics(
+        &self,
+        receiver: &Type<ResolvedName>,
+        class: ReflectionClass<'a>,
+        declaring: ReflectionClass<'a>,
+    ) -> Generics {
+        let class_arguments = match receiver {
+            Type::Generic(_, arguments) => arguments
+                .iter()
+                .map(|argument| argument.r#type.clone())
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        let mut generics = Generics::new();
+
+        generics.declare_with_arguments(
+            &declaring.get_templates(),
+            &ancestor_template_arguments(class, class_arguments, declaring, &mut Vec::new())
+                .unwrap_or_default(),
+        );
+
+        generics
+    }
+
+    /// Determine the type of a property fetched from an object, including the `name` and `value`
+    /// properties of enum cases.
+    fn property_type(&self, target: &Expression, property: &Expression) -> Type<ResolvedName> {
+        let ExpressionKind::Identifier(identifier) = &property.kind else {
+            return Type::Mixed;
+        };
+
+        // FIXME: Can we support dynamic property names here if we know the value of the expression?
+        if !identifier.is_simple() {
+            return Type::Mixed;
+        }
+
+        let name = identifier.to_simple().symbol.as_bytestr();
+        let target = self.map.resolve(target.id);
+
+        if matches!(target, Type::Mixed) || target.is_object() {
+            return Type::Mixed;
+        }
+
+        if !target.is_object_like() {
+            return Type::Invalid;
+        }
+
+        let Some(classes) = self.determine_class_from_type(target) else {
+            return Type::Mixed;
+        };
+
+        let types = classes
+            .iter()
+            .filter_map(|class| match name.as_ref() {
+                b"name" if class.is_enum() => Some(Type::String),
+                b"value" if class.is_backed_enum() => {
+                    Some(class.get_backing_type()?.to_type().clone())
+                }
+                _ => {
+                    let property = class.get_property(name)?;
+                    let receiver = class_type(target, *class);
+                    let generics = self.receiver_generics(&receiver, *class, property.get_class());
+
+                    Some(relative_type(
+                        &generics.substitute(&self.declared_property_type(&property)),
+                        &receiver,
+                        property.get_class(),
+                    ))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if types.is_empty() {
+            return Type::Mixed;
+        }
+
+        self.simplify_union(types)
+    }
+
+    /// Determine the type of a property from its declaration, falling back to the type of its default value.
+    fn declared_property_type(&self, property: &ReflectionProperty) -> Type<ResolvedName> {
+        property
+            .get_type()
+            .or_else(|| property.get_default_type())
+            .map_or(Type::Mixed, |ty| ty.to_type().clone())
+    }
+
+    /// Find the class-likes that a static property or class constant is fetched from.
+    fn static_target_classes(&self, target: &Expression) -> Option<Vec<ReflectionClass<'_>>> {
+        let class = match &target.kind {
+            ExpressionKind::Name(name) if name.is_resolved() => self
+                .index
+                .get_class(name.to_resolved().resolved.as_bytestr())?,
+            ExpressionKind::Self_(_) | ExpressionKind::Static(_) => self.current_class()?,
+            ExpressionKind::Parent(_) => self.current_class()?.get_parent()?,
+            _ => {
+                let ty = self.map.resolve(target.id);
+
+                if !ty.is_object_like() || ty.is_object() {
+                    return None;
+                }
+
+                return self.determine_class_from_type(ty);
+            }
+        };
+
+        Some(vec![class])
+    }
+
+    fn current_class(&self) -> Option<ReflectionClass<'_>> {
+        self.index
+            .get_class(self.class.as_ref()?.resolved.as_bytestr())
+    }
+
+    /// Determine the type of a class constant or enum case, preferring the type of the constant's
+    /// value when it is more specific than the declared type.
+    fn class_constant_type(&self, target: &Expression, name: &ByteStr) -> Type<ResolvedName> {
+        if name.eq_ignore_ascii_case(b"class") {
+            return Type::ClassString;
+        }
+
+        let Some(classes) = self.static_target_classes(target) else {
+            return Type::Mixed;
+        };
+
+        let types = classes
+            .iter()
+            .filter_map(|class| {
+                if let Some(case) = class.get_case(name) {
+                    return Some(named_class(case.get_enum()));
+                }
+
+                let constant = class.get_constant(name)?;
+                let value = constant.get_value_type();
+
+                Some(match constant.get_type() {
+                    Some(declared) if !value.to_type().refines(declared.to_type()) => {
+                        declared.to_type().clone()
+                    }
+                    _ => value.to_type().clone(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if types.is_empty() {
+            return Type::Mixed;
+        }
+
+        self.simplify_union(types)
+    }
+
+    /// Determine the type of a new instance of a class-like, binding its templates from the constructor's arguments.
+    fn instance_type(
+        &self,
+        name: &ResolvedName,
+        arguments: Option<&ArgumentList>,
+    ) -> Type<ResolvedName> {
+        let named = Type::Named(name.clone());
+
+        let Some(class) = self.index.get_class(name.resolved.as_bytestr()) else {
+            return named;
+        };
+
+        let templates = class.get_templates();
+
+        if templates.is_empty() {
+            return named;
+        }
+
+        let mut generics = Generics::new();
+
+        for template in templates.iter() {
+            generics.declare(template);
+        }
+
+        if let (Some(constructor), Some(arguments)) =
+            (class.get_method(ByteStr::new(b"__construct")), arguments)
+        {
+            self.bind_arguments(&mut generics, &constructor.get_parameters(), arguments);
+        }
+
+        Type::Generic(
+            Box::new(named),
+            templates
+                .iter()
+                .map(|template| GenericTypeArgument {
+                    r#type: generics.resolve(template.get_name()),
+                    variance: None,
+                })
+                .collect(),
+        )
+    }
+
+    /// Bind templates that appear in the parameters of a function or method to the types of the arguments,
+    /// and record the arguments so that conditional types can be evaluated.
+    fn bind_arguments<O: CanReflectParameters>(
+        &self,
+        generics: &mut Generics,
+        parameters: &[ReflectionParameter<O>],
+        arguments: &ArgumentList,
+    ) {
+        let mut position = 0;
+
+        for argument in arguments.arguments.iter() {
+            let (parameter, value) = match argument {
+                Argument::Positional(argument) => {
+                    // Once an argument is unpacked, we don't know which parameters the rest of the arguments belong to.
+                    if argument.ellipsis.is_some() {
+                        break;
+                    }
+
+                    let parameter = parameters
+                        .get(position)
+                        .or_else(|| parameters.last().filter(|p| p.is_variadic()));
+
+                    position += 1;
+
+                    (parameter, &argument.value)
+                }
+                Argument::Named(argument) => (
+                    parameters
+                        .iter()
+                        .find(|p| p.get_name() == argument.name.symbol.as_bytestr()),
+                    &argument.value,
+                ),
+            };
+
+            let Some(parameter) = parameter else {
+                continue;
+            };
 
-            _ => todo!(),
+            let argument = self.map.resolve(value.id);
+
+            generics.pass(parameter.get_name(), argument);
+
+            if let Some(r#type) = parameter.get_type() {
+                generics.bind(r#type.to_type(), argument);
+            }
         }
     }
 
+    /// Create the type of a closure or arrow function from its signature.
+    fn closure_type(
+        &self,
+        parameters: &FunctionParameterList,
+        return_type: Type<ResolvedName>,
+    ) -> Type<ResolvedName> {
+        Type::CallableSignature(
+            Box::new(Type::Named(ResolvedName {
+                resolved: ByteString::from("Closure"),
+                original: ByteString::from("Closure"),
+            })),
+            parameters
+                .parameters
+                .iter()
+                .map(|parameter| CallableParameter {
+                    r#type: self.parameter_type(parameter.data_type.as_ref(), &parameter.name),
+                    ellipsis: parameter.ellipsis,
+                    ampersand: parameter.ampersand,
+                    equal: None,
+                    name: Some(parameter.name.symbol.clone()),
+                })
+                .collect(),
+            Box::new(return_type),
+        )
+    }
+
     fn simplify_union(&self, mut types: Vec<Type<ResolvedName>>) -> Type<ResolvedName> {
         if types.len() == 1 {
             return types[0].clone();
@@ -305,18 +752,316 @@ impl<'a> TypeMapGenerator<'a> {
             })
             .collect();
 
-        Type::TypedArray(
-            Box::new(self.simplify_union(key_types)),
-            Box::new(self.simplify_union(value_types)),
-        )
+        Type::TypedArray(
+            Box::new(self.simplify_union(key_types)),
+            Box::new(self.simplify_union(value_types)),
+        )
+    }
+
+    /// Combine types that a value could have into a single type, flattening any nested unions.
+    fn union(&self, types: Vec<Type<ResolvedName>>) -> Type<ResolvedName> {
+        if types.iter().all(|ty| ty == &types[0]) {
+            return types.into_iter().next().unwrap_or(Type::Never);
+        }
+
+        let members = types
+            .iter()
+            .flat_map(members)
+            .filter(|ty| *ty != Type::Never)
+            .collect::<Vec<_>>();
+
+        if members.is_empty() {
+            return Type::Never;
+        }
+
+        if members.contains(&Type::Mixed) {
+            return Type::Mixed;
+        }
+
+        self.simplify_union(members)
+    }
+
+    /// Merge the scopes at the end of each branch that reaches a join point, so that each variable
+    /// has the union of the types it could have.
+    fn merge(&self, scopes: Vec<Scope>) -> Option<Scope> {
+        let mut merged = scopes.first()?.clone();
+
+        for scope in scopes.iter().skip(1) {
+            for symbol in scope.variables.keys() {
+                if !merged.variables.contains_key(symbol) {
+                    merged
+                        .variables
+                        .insert(symbol.clone(), scope.variables[symbol].clone());
+                }
+            }
+        }
+
+        for (symbol, ty) in merged.variables.iter_mut() {
+            *ty = self.union(
+                scopes
+                    .iter()
+                    .filter_map(|scope| scope.variables.get(symbol).cloned())
+                    .collect(),
+            );
+        }
+
+        Some(merged)
+    }
+
+    /// Continue in the merged scope of the given branches. If none of them reach the join point,
+    /// the code that follows is unreachable and the fallback scope is used instead.
+    fn join(&mut self, scopes: Vec<Scope>, fallback: Scope) {
+        *self.scopes.current_mut() = self.merge(scopes).unwrap_or(fallback);
+    }
+
+    /// Visit the statements of a branch in the given scope, returning the scope at the end of the
+    /// branch if control can reach the join point.
+    fn branch(&mut self, scope: Scope, statements: &[Statement]) -> Option<Scope> {
+        *self.scopes.current_mut() = scope;
+
+        for statement in statements {
+            self.visit_statement(statement);
+        }
+
+        (!terminates(statements)).then(|| self.scopes.current().clone())
+    }
+
+    /// Narrow the types of variables in the scope, assuming that the condition is truthy or falsy.
+    fn assume(&self, scope: &mut Scope, condition: &Expression, truthy: bool) {
+        match &condition.kind {
+            ExpressionKind::Parenthesized(inner) => self.assume(scope, &inner.expr, truthy),
+            ExpressionKind::LogicalOperation(inner) => match &inner.kind {
+                LogicalOperationKind::Not { right, .. } => self.assume(scope, right, !truthy),
+                LogicalOperationKind::And { left, right, .. }
+                | LogicalOperationKind::LogicalAnd { left, right, .. } => {
+                    self.assume_logical(scope, left, right, false, truthy)
+                }
+                LogicalOperationKind::Or { left, right, .. }
+                | LogicalOperationKind::LogicalOr { left, right, .. } => {
+                    self.assume_logical(scope, left, right, true, truthy)
+                }
+                _ => {}
+            },
+            ExpressionKind::ComparisonOperation(inner) => match &inner.kind {
+                ComparisonOperationKind::Identical { left, right, .. } => {
+                    if let Some(variable) = null_check(left, right) {
+                        self.narrow(scope, variable, &is_null, Type::Null, truthy);
+                    }
+                }
+                ComparisonOperationKind::NotIdentical { left, right, .. } => {
+                    if let Some(variable) = null_check(left, right) {
+                        self.narrow(scope, variable, &is_null, Type::Null, !truthy);
+                    }
+                }
+                _ => {}
+            },
+            ExpressionKind::Instanceof(inner) => {
+                let Some(variable) = variable(&inner.left) else {
+                    return;
+                };
+
+                let ExpressionKind::Name(name) = &inner.right.kind else {
+                    return;
+                };
+
+                if !name.is_resolved() {
+                    return;
+                }
+
+                self.narrow_instance(scope, variable, name.to_resolved(), truthy);
+            }
+            ExpressionKind::FunctionCall(inner) => {
+                let ExpressionKind::Name(name) = &inner.target.kind else {
+                    return;
+                };
+
+                let Some((matches, asserted)) = type_check(name) else {
+                    return;
+                };
+
+                let Some(Argument::Positional(argument)) = inner.arguments.arguments.first() else {
+                    return;
+                };
+
+                if let Some(variable) = variable(&argument.value) {
+                    self.narrow(scope, variable, &matches, asserted, truthy);
+                }
+            }
+            ExpressionKind::Isset(inner) => {
+                let variables = inner
+                    .arguments
+                    .arguments
+                    .iter()
+                    .filter_map(|argument| match argument {
+                        Argument::Positional(argument) => variable(&argument.value),
+                        Argument::Named(_) => None,
+                    })
+                    .collect::<Vec<_>>();
+
+                // When there's more than one argument, we only know that one of them is null.
+                if truthy || inner.arguments.arguments.len() == 1 {
+                    for variable in variables {
+                        self.narrow(scope, variable, &is_null, Type::Null, !truthy);
+                    }
+                }
+            }
+            _ => {
+                // A variable on its own is truthy when it isn't null or false.
+                if let (Some(variable), true) = (variable(condition), truthy) {
+                    self.narrow(
+                        scope,
+                        variable,
+                        &|ty| matches!(ty, Type::Null | Type::False),
+                        Type::Mixed,
+                        false,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Narrow both sides of a short-circuiting operator. The left-hand side decides the result of the
+    /// whole expression when it evaluates to `short_circuit`, which is `false` for `&&` and `true` for `||`.
+    fn assume_logical(
+        &self,
+        scope: &mut Scope,
+        left: &Expression,
+        right: &Expression,
+        short_circuit: bool,
+        truthy: bool,
+    ) {
+        if truthy != short_circuit {
+            self.assume(scope, left, truthy);
+            self.assume(scope, right, truthy);
+
+            return;
+        }
+
+        let mut decided = scope.clone();
+        self.assume(&mut decided, left, short_circuit);
+
+        self.assume(scope, left, !short_circuit);
+        self.assume(scope, right, truthy);
+
+        if let Some(merged) = self.merge(vec![decided, scope.clone()]) {
+            *scope = merged;
+        }
+    }
+
+    /// Narrow the type of a variable to the parts that match a type check when it passes, or to the
+    /// parts that don't when it fails. If nothing matches, the check tells us the type instead.
+    fn narrow(
+        &self,
+        scope: &mut Scope,
+        variable: &SimpleVariable,
+        matches: &dyn Fn(&Type<ResolvedName>) -> bool,
+        asserted: Type<ResolvedName>,
+        passes: bool,
+    ) {
+        let members = members(&scope.get_variable(variable).unwrap_or(Type::Mixed));
+
+        let narrowed = if passes {
+            let matching = members
+                .into_iter()
+                .filter(|ty| matches(ty))
+                .collect::<Vec<_>>();
+
+            if matching.is_empty() {
+                asserted
+            } else {
+                self.union(matching)
+            }
+        } else {
+            self.union(members.into_iter().filter(|ty| !matches(ty)).collect())
+        };
+
+        scope.set_variable(variable, narrowed);
+    }
+
+    /// Narrow the type of a variable with an `instanceof` check. When the check passes, the parts
+    /// that are already instances of the class are kept and the class-likes that it extends or
+    /// implements are narrowed down to the class itself.
+    fn narrow_instance(
+        &self,
+        scope: &mut Scope,
+        variable: &SimpleVariable,
+        class: &ResolvedName,
+        passes: bool,
+    ) {
+        let is_instance = |ty: &Type<ResolvedName>| {
+            class_name(ty).is_some_and(|name| self.is_subclass(name, class))
+        };
+
+        if !passes {
+            return self.narrow(scope, variable, &is_instance, Type::Mixed, false);
+        }
+
+        let narrowed = members(&scope.get_variable(variable).unwrap_or(Type::Mixed))
+            .into_iter()
+            .filter_map(|ty| match class_name(&ty) {
+                Some(_) if is_instance(&ty) => Some(ty),
+                Some(name) if self.is_subclass(class, name) => Some(Type::Named(class.clone())),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let narrowed = if narrowed.is_empty() {
+            Type::Named(class.clone())
+        } else {
+            self.union(narrowed)
+        };
+
+        scope.set_variable(variable, narrowed);
+    }
+}
+
+impl<'a> Visitor for TypeMapGenerator<'a> {
+    fn visit_statement(&mut self, node: &Statement) {
+        // The comments before a function are attached to the statement, rather than the function itself.
+        if let StatementKind::Function(_) = &node.kind {
+            self.docblock = node.comments.docblock().cloned();
+
+            return walk_statement(self, node);
+        }
+
+        let Some(docblock) = node.comments.docblock() else {
+            return walk_statement(self, node);
+        };
+
+        // An inline `@var` tag describes the variable that the statement assigns to, so it can
+        // only be applied once the assignment itself has been inferred.
+        if let Some(variable) = assigned_variable(&node.kind) {
+            walk_statement(self, node);
+
+            if let Some(data_type) = docblock.get_var_type(variable) {
+                self.annotate_variable(variable, data_type);
+            }
+
+            return;
+        }
+
+        // Otherwise, the tag describes a variable that is already in scope.
+        for node in docblock.tag_nodes() {
+            if let Some(DocBlockVarTag {
+                data_type: Some(data_type),
+                variable: Some(variable),
+                ..
+            }) = node.tag().as_var()
+            {
+                self.annotate_variable(variable, data_type);
+            }
+        }
+
+        walk_statement(self, node);
     }
-}
 
-impl<'a> Visitor for TypeMapGenerator<'a> {
     fn visit_expression(&mut self, node: &Expression) {
         walk_expression(self, node);
 
-        let inner = self.map.resolve(node.kind.id()).clone();
+        let inner = match &node.kind {
+            ExpressionKind::Null(_) => Type::Null,
+            kind => self.map.resolve(kind.id()).clone(),
+        };
 
         self.map.insert(node.id, inner);
     }
@@ -361,8 +1106,8 @@ impl<'a> Visitor for TypeMapGenerator<'a> {
         // We also need the information for the `node.target` to be available in the map.
         walk_function_call_expression(self, node);
 
-        // FIXME: Once we've got this information, we can resolve generics based on the arguments.
-        let return_type = self.determine_function_call_target_return_type(&node.target);
+        let return_type =
+            self.determine_function_call_target_return_type(&node.target, &node.arguments);
 
         self.map.insert(node.id, return_type);
     }
@@ -374,25 +1119,26 @@ impl<'a> Visitor for TypeMapGenerator<'a> {
     }
 
     fn visit_assignment_operation_expression(&mut self, node: &AssignmentOperationExpression) {
-        // Walk the right-hand side of the assignment first to ensure the type is resolved.
-        walk_expression(self, &node.right);
+        // Visit the right-hand side of the assignment first to ensure the type is resolved.
+        self.visit_expression(&node.right);
 
         // Assignment expressions are always resolved to the type of the right-hand side.
         self.map
-            .insert(node.id, self.map.resolve(node.right.kind.id()).clone());
+            .insert(node.id, self.map.resolve(node.right.id).clone());
 
         // If the left-hand side is a variable, we can use that to assign the type in the current scope.
         match &node.left.kind {
             ExpressionKind::Variable(variable) if variable.is_simple() => {
                 let variable = variable.to_simple();
-                let resolved = self.map.resolve(node.right.kind.id());
+                let resolved = self.map.resolve(node.right.id);
 
                 self.scopes
                     .current_mut()
                     .set_variable(variable, resolved.clone());
                 self.map.insert(variable.id, resolved.clone());
             }
-            _ => (),
+            // Anything else, like a property or array access, still has types that need resolving.
+            _ => walk_expression(self, &node.left),
         }
     }
 
@@ -403,7 +1149,9 @@ impl<'a> Visitor for TypeMapGenerator<'a> {
             node.id,
             match &node.target.kind {
                 ExpressionKind::Name(name) => match true {
-                    _ if name.is_resolved() => Type::Named(name.to_resolved().clone()),
+                    _ if name.is_resolved() => {
+                        self.instance_type(name.to_resolved(), node.arguments.as_ref())
+                    }
                     _ => Type::Mixed,
                 },
                 _ => match self.map.resolve(node.target.id) {
@@ -435,7 +1183,36 @@ impl<'a> Visitor for TypeMapGenerator<'a> {
 
     fn visit_function_parameter_list(&mut self, node: &FunctionParameterList) {
         for parameter in node.parameters.iter() {
-            let mut r#type = self.unwrap_data_type(parameter.data_type.as_ref());
+            let mut r#type = self.parameter_type(parameter.data_type.as_ref(), &parameter.name);
+
+            if parameter.is_variadic() {
+                r#type = Type::TypedArray(Box::new(Type::Integer), Box::new(r#type));
+            }
+
+            self.scopes
+                .current_mut()
+                .set_variable(&parameter.name, r#type);
+        }
+    }
+
+    fn visit_method(&mut self, node: &Method) {
+        self.docblock = node.comments.docblock().cloned();
+        self.scopes.start();
+
+        // Instance methods can refer to the object that they're called on through `$this`.
+        if let Some(class) = self.class.as_ref().filter(|_| !node.modifiers.has_static()) {
+            self.scopes
+                .current_mut()
+                .set_this(Type::Named(class.clone()));
+        }
+
+        walk_method(self, node);
+        self.scopes.end();
+    }
+
+    fn visit_method_parameter_list(&mut self, node: &MethodParameterList) {
+        for parameter in node.parameters.iter() {
+            let mut r#type = self.parameter_type(parameter.data_type.as_ref(), &parameter.name);
 
             if parameter.is_variadic() {
                 r#type = Type::TypedArray(Box::new(Type::Integer), Box::new(r#type));
@@ -447,6 +1224,78 @@ impl<'a> Visitor for TypeMapGenerator<'a> {
         }
     }
 
+    fn visit_closure_expression(&mut self, node: &ClosureExpression) {
+        // Closures only have access to the variables that they explicitly capture.
+        let captured = node
+            .uses
+            .iter()
+            .flat_map(|uses| uses.variables.iter())
+            .map(|r#use| {
+                let ty = self.scopes.current().get_variable(&r#use.variable);
+
+                (&r#use.variable, ty.unwrap_or(Type::Mixed))
+            })
+            .collect::<Vec<_>>();
+
+        // Non-static closures are bound to the object that they're created in.
+        let this = match node.r#static {
+            Some(_) => None,
+            None => self.scopes.current().get_this(),
+        };
+
+        let docblock = node.comments.docblock();
+
+        self.docblock = docblock.cloned();
+        self.scopes.start();
+
+        if let Some(this) = this {
+            self.scopes.current_mut().set_this(this);
+        }
+
+        for (variable, ty) in captured {
+            self.scopes.current_mut().set_variable(variable, ty);
+        }
+
+        walk_closure_expression(self, node);
+        self.scopes.end();
+
+        let return_type = self.unwrap_data_type(
+            node.return_type
+                .as_ref()
+                .map(|return_type| &return_type.data_type),
+            docblock.and_then(DocBlock::get_return_type),
+        );
+
+        // Nested closures replace the docblock, so it needs to be restored to build the signature.
+        self.docblock = docblock.cloned();
+
+        self.map
+            .insert(node.id, self.closure_type(&node.parameters, return_type));
+    }
+
+    fn visit_arrow_function_expression(&mut self, node: &ArrowFunctionExpression) {
+        let docblock = node.comments.docblock();
+
+        // Arrow functions capture the variables from the scope that they're defined in by value.
+        self.docblock = docblock.cloned();
+        self.scopes.start_enclosed();
+        walk_arrow_function_expression(self, node);
+        self.scopes.end();
+
+        let return_type = match &node.return_type {
+            Some(return_type) => self.unwrap_data_type(
+                Some(&return_type.data_type),
+                docblock.and_then(DocBlock::get_return_type),
+            ),
+            None => self.map.resolve(node.body.id).clone(),
+        };
+
+        self.docblock = docblock.cloned();
+
+        self.map
+            .insert(node.id, self.closure_type(&node.parameters, return_type));
+    }
+
     fn visit_missing_expression(&mut self, node: &MissingExpression) {
         self.map.insert(node.id, Type::Missing);
     }
@@ -516,6 +1365,194 @@ impl<'a> Visitor for TypeMapGenerator<'a> {
         self.map.insert(node.id, Type::Boolean);
     }
 
+    fn visit_if_statement(&mut self, node: &IfStatement) {
+        let (branches, r#else) = if_branches(node);
+        let mut exits = Vec::new();
+
+        for (condition, statements) in branches {
+            self.visit_expression(condition);
+
+            let mut scope = self.scopes.current().clone();
+            self.assume(&mut scope, condition, true);
+
+            // The next branch is only taken when this condition is falsy.
+            let mut otherwise = self.scopes.current().clone();
+            self.assume(&mut otherwise, condition, false);
+
+            exits.extend(self.branch(scope, statements));
+            *self.scopes.current_mut() = otherwise;
+        }
+
+        let fallback = self.scopes.current().clone();
+
+        match r#else {
+            Some(statements) => exits.extend(self.branch(fallback.clone(), statements)),
+            None => exits.push(fallback.clone()),
+        }
+
+        self.join(exits, fallback);
+    }
+
+    fn visit_while_statement(&mut self, node: &WhileStatement) {
+        self.visit_expression(&node.condition);
+
+        let before = self.scopes.current().clone();
+
+        let mut scope = before.clone();
+        self.assume(&mut scope, &node.condition, true);
+
+        *self.scopes.current_mut() = scope;
+        self.visit_while_statement_body(&node.body);
+
+        // The body might not run at all, or it might run more than once.
+        let after = self.scopes.current().clone();
+        self.join(vec![before.clone(), after], before);
+    }
+
+    fn visit_foreach_statement(&mut self, node: &ForeachStatement) {
+        let before = self.scopes.current().clone();
+        walk_foreach_statement(self, node);
+
+        let after = self.scopes.current().clone();
+        self.join(vec![before.clone(), after], before);
+    }
+
+    fn visit_for_statement(&mut self, node: &ForStatement) {
+        let before = self.scopes.current().clone();
+        walk_for_statement(self, node);
+
+        let after = self.scopes.current().clone();
+        self.join(vec![before.clone(), after], before);
+    }
+
+    fn visit_match_expression(&mut self, node: &MatchExpression) {
+        self.visit_expression(&node.condition);
+
+        // A `match (true)` takes the first arm with a truthy condition, so the conditions narrow types
+        // in the same way as an `if` statement.
+        let narrows = matches!(
+            &node.condition.kind,
+            ExpressionKind::Bool(condition) if condition.value.kind == TokenKind::True
+        );
+
+        let mut exits = Vec::new();
+        let mut types = Vec::new();
+
+        for arm in node.arms.iter() {
+            for condition in arm.conditions.iter() {
+                self.visit_expression(condition);
+            }
+
+            let mut scopes = Vec::new();
+            let mut otherwise = self.scopes.current().clone();
+
+            for condition in arm.conditions.iter().filter(|_| narrows) {
+                let mut scope = otherwise.clone();
+                self.assume(&mut scope, condition, true);
+                scopes.push(scope);
+
+                self.assume(&mut otherwise, condition, false);
+            }
+
+            let scope = self
+                .merge(scopes)
+                .unwrap_or_else(|| self.scopes.current().clone());
+
+            *self.scopes.current_mut() = scope;
+            self.visit_expression(&arm.body);
+
+            let ty = self.map.resolve(arm.body.id).clone();
+
+            if ty != Type::Never {
+                exits.push(self.scopes.current().clone());
+            }
+
+            types.push(ty);
+            *self.scopes.current_mut() = otherwise;
+        }
+
+        let fallback = self.scopes.current().clone();
+
+        if let Some(default) = &node.default {
+            self.visit_expression(&default.body);
+
+            let ty = self.map.resolve(default.body.id).clone();
+
+            if ty != Type::Never {
+                exits.push(self.scopes.current().clone());
+            }
+
+            types.push(ty);
+        }
+
+        self.join(exits, fallback);
+        self.map.insert(node.id, self.union(types));
+    }
+
+    fn visit_ternary_expression(&mut self, node: &TernaryExpression) {
+        self.visit_expression(&node.condition);
+
+        let mut scope = self.scopes.current().clone();
+        self.assume(&mut scope, &node.condition, true);
+
+        let mut otherwise = self.scopes.current().clone();
+        self.assume(&mut otherwise, &node.condition, false);
+
+        *self.scopes.current_mut() = scope;
+        self.visit_expression(&node.then);
+        let then = self.scopes.current().clone();
+
+        *self.scopes.current_mut() = otherwise;
+        self.visit_expression(&node.r#else);
+        let otherwise = self.scopes.current().clone();
+
+        self.join(vec![then, otherwise.clone()], otherwise);
+        self.map.insert(
+            node.id,
+            self.union(vec![
+                self.map.resolve(node.then.id).clone(),
+                self.map.resolve(node.r#else.id).clone(),
+            ]),
+        );
+    }
+
+    fn visit_logical_operation_expression(&mut self, node: &LogicalOperationExpression) {
+        // The right-hand side of `&&` and `||` is only evaluated when the left-hand side doesn't
+        // short-circuit, so it can use the types that the left-hand side narrows.
+        let (left, right, truthy) = match &node.kind {
+            LogicalOperationKind::And { left, right, .. }
+            | LogicalOperationKind::LogicalAnd { left, right, .. } => (left, right, true),
+            LogicalOperationKind::Or { left, right, .. }
+            | LogicalOperationKind::LogicalOr { left, right, .. } => (left, right, false),
+            _ => {
+                walk_logical_operation_expression(self, node);
+                self.map.insert(node.id, Type::Boolean);
+
+                return;
+            }
+        };
+
+        self.visit_expression(left);
+
+        let before = self.scopes.current().clone();
+
+        let mut scope = before.clone();
+        self.assume(&mut scope, left, truthy);
+
+        *self.scopes.current_mut() = scope;
+        self.visit_expression(right);
+
+        let after = self.scopes.current().clone();
+        self.join(vec![before.clone(), after], before);
+        self.map.insert(node.id, Type::Boolean);
+    }
+
+    fn visit_throw_expression(&mut self, node: &ThrowExpression) {
+        walk_throw_expression(self, node);
+
+        self.map.insert(node.id, Type::Never);
+    }
+
     fn visit_reference_expression(&mut self, node: &ReferenceExpression) {
         walk_reference_expression(self, node);
 
@@ -616,7 +1653,7 @@ impl<'a> Visitor for TypeMapGenerator<'a> {
 
         let methods = classes
             .iter()
-            .filter_map(|class| class.get_method(method_name))
+            .filter_map(|class| Some((*class, class.get_method(method_name)?)))
             .collect::<Vec<_>>();
 
         if methods.is_empty() {
@@ -628,7 +1665,9 @@ impl<'a> Visitor for TypeMapGenerator<'a> {
         let return_type = self.simplify_union(
             methods
                 .iter()
-                .filter_map(|method| method.get_return_type().as_ref().map(|t| t.to_type().clone()))
+                .filter_map(|(class, method)| {
+                    self.method_return_type(target, *class, method, &node.arguments)
+                })
                 .collect::<Vec<Type<ResolvedName>>>(),
         );
 
@@ -686,7 +1725,7 @@ impl<'a> Visitor for TypeMapGenerator<'a> {
 
         let methods = classes
             .iter()
-            .filter_map(|class| class.get_method(method_name))
+            .filter_map(|class| Some((*class, class.get_method(method_name)?)))
             .collect::<Vec<_>>();
 
         if methods.is_empty() {
@@ -698,7 +1737,9 @@ impl<'a> Visitor for TypeMapGenerator<'a> {
         let return_type = self.simplify_union(
             methods
                 .iter()
-                .filter_map(|method| method.get_return_type().as_ref().map(|t| t.to_type().clone()))
+                .filter_map(|(class, method)| {
+                    self.method_return_type(target, *class, method, &node.arguments)
+                })
                 .collect::<Vec<Type<ResolvedName>>>(),
         );
 
@@ -714,9 +1755,7 @@ impl<'a> Visitor for TypeMapGenerator<'a> {
 
         // FIXME: If we know that the target is a class-like thing, we can determine a better type here.
         let target = match &node.target.kind {
-            ExpressionKind::Name(name) if name.is_resolved() => {
-                name.to_resolved().resolved.as_ref()
-            }
+            ExpressionKind::Name(name) if name.is_resolved() => name.to_resolved(),
             _ => {
                 self.map.insert(node.id, Type::Mixed);
 
@@ -734,7 +1773,7 @@ impl<'a> Visitor for TypeMapGenerator<'a> {
             return;
         };
 
-        let Some(class) = self.index.get_class(target) else {
+        let Some(class) = self.index.get_class(target.resolved.as_bytestr()) else {
             self.map.insert(node.id, Type::Invalid);
 
             return;
@@ -746,12 +1785,437 @@ impl<'a> Visitor for TypeMapGenerator<'a> {
             return;
         };
 
-        let return_type = method
-            .get_return_type()
-            .as_ref()
-            .map(|t| t.to_type().clone())
-            .unwrap_or_else(|| Type::Mixed);
+        let return_type = self
+            .method_return_type(
+                &Type::Named(target.clone()),
+                class,
+                &method,
+                &node.arguments,
+            )
+            .unwrap_or(Type::Mixed);
 
         self.map.insert(node.id, return_type);
     }
+
+    fn visit_unbraced_namespace(&mut self, node: &UnbracedNamespace) {
+        self.within_namespace(Some(&node.name), |generator| {
+            walk_unbraced_namespace(generator, node)
+        });
+    }
+
+    fn visit_braced_namespace(&mut self, node: &BracedNamespace) {
+        self.within_namespace(node.name.as_ref(), |generator| {
+            walk_braced_namespace(generator, node)
+        });
+    }
+
+    fn visit_class_statement(&mut self, node: &ClassStatement) {
+        self.within_class(&node.name, |generator| {
+            walk_class_statement(generator, node)
+        });
+    }
+
+    fn visit_interface_statement(&mut self, node: &InterfaceStatement) {
+        self.within_class(&node.name, |generator| {
+            walk_interface_statement(generator, node)
+        });
+    }
+
+    fn visit_trait_statement(&mut self, node: &TraitStatement) {
+        self.within_class(&node.name, |generator| {
+            walk_trait_statement(generator, node)
+        });
+    }
+
+    fn visit_unit_enum_statement(&mut self, node: &UnitEnumStatement) {
+        self.within_class(&node.name, |generator| {
+            walk_unit_enum_statement(generator, node)
+        });
+    }
+
+    fn visit_backed_enum_statement(&mut self, node: &BackedEnumStatement) {
+        self.within_class(&node.name, |generator| {
+            walk_backed_enum_statement(generator, node)
+        });
+    }
+
+    fn visit_property_fetch_expression(&mut self, node: &PropertyFetchExpression) {
+        walk_property_fetch_expression(self, node);
+
+        self.map
+            .insert(node.id, self.property_type(&node.target, &node.property));
+    }
+
+    fn visit_nullsafe_property_fetch_expression(&mut self, node: &NullsafePropertyFetchExpression) {
+        walk_nullsafe_property_fetch_expression(self, node);
+
+        let r#type = match self.property_type(&node.target, &node.property) {
+            Type::Mixed => Type::Mixed,
+            // FIXME: If we can determine that the target isn't nullable, we can omit the null type from the union.
+            r#type => self.simplify_union(vec![r#type, Type::Null]),
+        };
+
+        self.map.insert(node.id, r#type);
+    }
+
+    fn visit_static_property_fetch_expression(&mut self, node: &StaticPropertyFetchExpression) {
+        walk_static_property_fetch_expression(self, node);
+
+        // FIXME: Can we support variable variables here if we know the value of the expression?
+        if !node.property.is_simple() {
+            self.map.insert(node.id, Type::Mixed);
+
+            return;
+        }
+
+        let name = node.property.to_simple().stripped.as_bytestr();
+        let types = self
+            .static_target_classes(&node.target)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|class| Some((*class, class.get_static_property(name)?)))
+            .map(|(class, property)| {
+                relative_type(
+                    &self.declared_property_type(&property),
+                    &named_class(class),
+                    property.get_class(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let r#type = match types.is_empty() {
+            true => Type::Mixed,
+            false => self.simplify_union(types),
+        };
+
+        self.map.insert(node.id, r#type);
+    }
+
+    fn visit_constant_fetch_expression(&mut self, node: &ConstantFetchExpression) {
+        walk_constant_fetch_expression(self, node);
+
+        let r#type = match &node.constant {
+            Identifier::SimpleIdentifier(identifier) => {
+                self.class_constant_type(&node.target, identifier.symbol.as_bytestr())
+            }
+            // FIXME: Can we support dynamic constant names here if we know the value of the expression?
+            Identifier::DynamicIdentifier(_) => Type::Mixed,
+        };
+
+        self.map.insert(node.id, r#type);
+    }
+
+    fn visit_name(&mut self, node: &Name) {
+        // Names that appear on their own in an expression context are references to global constants,
+        // so we can use the type of the constant's value if we know about it.
+        let constant = match &node.kind {
+            NameKind::Resolved(inner) => self.index.get_constant(inner.resolved.as_bytestr()),
+            NameKind::Unresolved(inner) => {
+                self.find_unqualified(inner, |name| self.index.get_constant(name.as_bytestr()))
+            }
+            NameKind::Special(_) => None,
+        };
+
+        if let Some(constant) = constant {
+            self.map
+                .insert(node.id, constant.get_value_type().to_type().clone());
+        }
+    }
+}
+
+/// The types that make up a union, with nullable types split into their inner type and `null`.
+fn members(ty: &Type<ResolvedName>) -> Vec<Type<ResolvedName>> {
+    match ty {
+        Type::Union(types) => types.iter().flat_map(members).collect(),
+        Type::Nullable(inner) => {
+            let mut types = members(inner);
+            types.push(Type::Null);
+            types
+        }
+        _ => vec![ty.clone()],
+    }
+}
+
+/// Check if control can never reach the end of the statements, because they always return, throw
+/// or leave the current iteration of a loop.
+fn terminates(statements: &[Statement]) -> bool {
+    statements.iter().any(|statement| match &statement.kind {
+        StatementKind::Return(_) | StatementKind::Break(_) | StatementKind::Continue(_) => true,
+        StatementKind::Expression(inner) => matches!(
+            &inner.expression.kind,
+            ExpressionKind::Throw(_) | ExpressionKind::Exit(_) | ExpressionKind::Die(_)
+        ),
+        StatementKind::Block(inner) => terminates(&inner.statements),
+        StatementKind::If(inner) => {
+            let (branches, r#else) = if_branches(inner);
+
+            r#else.is_some_and(terminates)
+                && branches
+                    .iter()
+                    .all(|(_, statements)| terminates(statements))
+        }
+        _ => false,
+    })
+}
+
+/// The condition of a branch and the statements that run when it's truthy.
+type Branch<'a> = (&'a Expression, &'a [Statement]);
+
+/// The branches of an `if` statement, followed by the statements in the `else` branch if there is one.
+fn if_branches(node: &IfStatement) -> (Vec<Branch<'_>>, Option<&[Statement]>) {
+    match &node.body {
+        IfStatementBody::Statement(body) => {
+            (
+                std::iter::once((&node.condition, std::slice::from_ref(&*body.statement)))
+                    .chain(body.elseifs.iter().map(|elseif| {
+                        (&elseif.condition, std::slice::from_ref(&*elseif.statement))
+                    }))
+                    .collect(),
+                body.r#else
+                    .as_ref()
+                    .map(|r#else| std::slice::from_ref(&*r#else.statement)),
+            )
+        }
+        IfStatementBody::Block(body) => (
+            std::iter::once((&node.condition, &body.statements[..]))
+                .chain(
+                    body.elseifs
+                        .iter()
+                        .map(|elseif| (&elseif.condition, &elseif.statements[..])),
+                )
+                .collect(),
+            body.r#else.as_ref().map(|r#else| &r#else.statements[..]),
+        ),
+    }
+}
+
+/// The variable that an expression reads, including the variable that's assigned to in an assignment.
+fn variable(expression: &Expression) -> Option<&SimpleVariable> {
+    match &expression.kind {
+        ExpressionKind::Variable(variable) if variable.is_simple() => Some(variable.to_simple()),
+        ExpressionKind::Parenthesized(inner) => variable(&inner.expr),
+        ExpressionKind::AssignmentOperation(inner) => variable(&inner.left),
+        _ => None,
+    }
+}
+
+/// The variable that's compared against `null`, on either side of the comparison.
+fn null_check<'e>(left: &'e Expression, right: &'e Expression) -> Option<&'e SimpleVariable> {
+    match (&left.kind, &right.kind) {
+        (_, ExpressionKind::Null(_)) => variable(left),
+        (ExpressionKind::Null(_), _) => variable(right),
+        _ => None,
+    }
+}
+
+fn is_null(ty: &Type<ResolvedName>) -> bool {
+    matches!(ty, Type::Null)
+}
+
+/// The name of the class-like that a type is an instance of, if it refers to one.
+fn class_name(ty: &Type<ResolvedName>) -> Option<&ResolvedName> {
+    match ty {
+        Type::Named(name) => Some(name),
+        Type::Generic(base, _) => class_name(base),
+        _ => None,
+    }
+}
+
+/// A check for the types that pass one of the built-in `is_*()` functions, and the type that a value
+/// has when it passes.
+type TypeCheck = (fn(&Type<ResolvedName>) -> bool, Type<ResolvedName>);
+
+fn type_check(name: &Name) -> Option<TypeCheck> {
+    let symbol = name.symbol().as_bytestr();
+    let function = symbol
+        .iter()
+        .rposition(|b| *b == b'\\')
+        .map_or(&symbol[..], |position| &symbol[position + 1..])
+        .to_ascii_lowercase();
+
+    Some(match &function[..] {
+        b"is_null" => (is_null, Type::Null),
+        b"is_string" => (
+            |ty| {
+                matches!(
+                    ty,
+                    Type::String
+                        | Type::LiteralString(_)
+                        | Type::NumericString
+                        | Type::NonEmptyString
+                        | Type::ClassString
+                        | Type::CallableString
+                )
+            },
+            Type::String,
+        ),
+        b"is_int" | b"is_integer" | b"is_long" => (
+            |ty| match ty {
+                Type::Integer | Type::NonNegativeInteger => true,
+                Type::ConstExpr(inner) => matches!(
+                    **inner,
+                    ConstExpr::Integer(_) | ConstExpr::NegativeInteger(_)
+                ),
+                _ => false,
+            },
+            Type::Integer,
+        ),
+        b"is_float" | b"is_double" => (
+            |ty| match ty {
+                Type::Float => true,
+                Type::ConstExpr(inner) => matches!(**inner, ConstExpr::Float(_)),
+                _ => false,
+            },
+            Type::Float,
+        ),
+        b"is_bool" => (
+            |ty| matches!(ty, Type::Boolean | Type::True | Type::False),
+            Type::Boolean,
+        ),
+        b"is_array" => (
+            |ty| {
+                matches!(
+                    ty,
+                    Type::Array
+                        | Type::NonEmptyArray
+                        | Type::List
+                        | Type::NonEmptyList
+                        | Type::TypedArray(_, _)
+                        | Type::Shaped { .. }
+                )
+            },
+            Type::Array,
+        ),
+        b"is_object" => (
+            |ty| {
+                matches!(
+                    ty,
+                    Type::Named(_)
+                        | Type::Generic(_, _)
+                        | Type::Object
+                        | Type::SelfReference
+                        | Type::StaticReference
+                        | Type::ParentReference
+                        | Type::This
+                )
+            },
+            Type::Object,
+        ),
+        _ => return None,
+    })
+}
+
+/// Find the part of a type that refers to a class-like, e.g. `Collection<int, User>` in `Collection<int, User>|null`.
+fn class_type(ty: &Type<ResolvedName>, class: ReflectionClass) -> Type<ResolvedName> {
+    fn find<'t>(ty: &'t Type<ResolvedName>, name: &ByteStr) -> Option<&'t Type<ResolvedName>> {
+        match ty {
+            Type::Named(named) if named.resolved.eq_ignore_ascii_case(name) => Some(ty),
+            Type::Generic(base, _) => find(base, name).map(|_| ty),
+            Type::Nullable(inner) => find(inner, name),
+            Type::Union(types) | Type::Intersection(types) => {
+                types.iter().find_map(|ty| find(ty, name))
+            }
+            _ => None,
+        }
+    }
+
+    find(ty, class.name())
+        .cloned()
+        .unwrap_or_else(|| named_class(class))
+}
+
+fn named_class(class: ReflectionClass) -> Type<ResolvedName> {
+    Type::Named(ResolvedName {
+        resolved: class.name().to_bytestring(),
+        original: class.short_name().to_bytestring(),
+    })
+}
+
+/// Replace `static`, `self` and `parent` in the type of a member with the class-likes that they refer to.
+///
+/// Fluent methods return the object that they're called on, along with its generic arguments, whereas
+/// `self` and `parent` always refer to the class-like that declares the member.
+fn relative_type(
+    ty: &Type<ResolvedName>,
+    receiver: &Type<ResolvedName>,
+    declaring: ReflectionClass,
+) -> Type<ResolvedName> {
+    match ty {
+        Type::StaticReference | Type::This => receiver.clone(),
+        Type::SelfReference => named_class(declaring),
+        Type::ParentReference => declaring.get_parent().map_or(Type::Mixed, named_class),
+        Type::Nullable(inner) => {
+            Type::Nullable(Box::new(relative_type(inner, receiver, declaring)))
+        }
+        Type::Union(types) => Type::Union(
+            types
+                .iter()
+                .map(|ty| relative_type(ty, receiver, declaring))
+                .collect(),
+        ),
+        Type::Intersection(types) => Type::Intersection(
+            types
+                .iter()
+                .map(|ty| relative_type(ty, receiver, declaring))
+                .collect(),
+        ),
+        _ => ty.clone(),
+    }
+}
+
+/// Follow the `@extends` and `@implements` tags from a class-like up to one of its ancestors, to find
+/// the types that are given to the ancestor's templates.
+fn ancestor_template_arguments<'a>(
+    class: ReflectionClass<'a>,
+    arguments: Vec<Type<ResolvedName>>,
+    ancestor: ReflectionClass<'a>,
+    visited: &mut Vec<ReflectionClass<'a>>,
+) -> Option<Vec<Type<ResolvedName>>> {
+    if class == ancestor {
+        return Some(arguments);
+    }
+
+    if visited.contains(&class) {
+        return None;
+    }
+
+    visited.push(class);
+
+    let mut generics = Generics::new();
+    generics.declare_with_arguments(&class.get_templates(), &arguments);
+
+    class
+        .get_parent()
+        .into_iter()
+        .chain(class.get_declared_interfaces())
+        .find_map(|parent| {
+            let arguments = class
+                .get_template_arguments(parent.name())
+                .iter()
+                .map(|argument| generics.substitute(argument.to_type()))
+                .collect();
+
+            ancestor_template_arguments(parent, arguments, ancestor, visited)
+        })
+}
+
+/// Find the variable that an expression statement assigns a value to, e.g. `$user = find_user();`.
+fn assigned_variable(statement: &StatementKind) -> Option<&SimpleVariable> {
+    let StatementKind::Expression(statement) = statement else {
+        return None;
+    };
+
+    match &statement.expression.kind {
+        ExpressionKind::AssignmentOperation(assignment)
+            if matches!(assignment.kind, AssignmentOperationKind::Assign(_)) =>
+        {
+            match &assignment.left.kind {
+                ExpressionKind::Variable(variable) if variable.is_simple() => {
+                    Some(variable.to_simple())
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
 }
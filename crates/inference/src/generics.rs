@@ -0,0 +1,342 @@
+use std::collections::HashMap;
+
+use pxp_ast::ResolvedName;
+use pxp_bytestring::{ByteStr, ByteString};
+use pxp_index::ReflectionTemplate;
+use pxp_type::{CallableParameter, GenericTypeArgument, Type};
+
+/// The templates that are in scope for a call, along with the types that they've been bound to.
+#[derive(Debug, Default)]
+pub(crate) struct Generics {
+    /// The type that a template resolves to when nothing is bound to it, i.e. its bound or `mixed`.
+    templates: HashMap<ByteString, Type<ResolvedName>>,
+    bindings: HashMap<ByteString, Type<ResolvedName>>,
+    /// The types of the arguments passed to each parameter, used to evaluate conditional types.
+    arguments: HashMap<ByteString, Type<ResolvedName>>,
+}
+
+impl Generics {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bring a template into scope, replacing any other template with the same name.
+    pub(crate) fn declare(&mut self, template: &ReflectionTemplate) {
+        let name = template.get_name().to_bytestring();
+        let fallback = template
+            .get_bound()
+            .map_or(Type::Mixed, |bound| bound.to_type().clone());
+
+        self.bindings.remove(&name);
+        self.templates.insert(name, fallback);
+    }
+
+    /// Bring templates into scope with the types that were given to them explicitly, e.g. `Collection<int, User>`.
+    pub(crate) fn declare_with_arguments(
+        &mut self,
+        templates: &[ReflectionTemplate],
+        arguments: &[Type<ResolvedName>],
+    ) {
+        for (position, template) in templates.iter().enumerate() {
+            self.declare(template);
+
+            if let Some(argument) = arguments.get(position) {
+                self.bindings
+                    .insert(template.get_name().to_bytestring(), argument.clone());
+            }
+        }
+    }
+
+    /// Record the type of the argument that was passed to a parameter.
+    pub(crate) fn pass(&mut self, parameter: &ByteStr, argument: &Type<ResolvedName>) {
+        let parameter = parameter.to_bytestring();
+        let argument = match self.arguments.remove(&parameter) {
+            // Variadic parameters receive more than one argument.
+            Some(existing) => union(vec![existing, argument.clone()]),
+            None => argument.clone(),
+        };
+
+        self.arguments.insert(parameter, argument);
+    }
+
+    /// Infer the types of templates by matching a declared type against the type of a value.
+    pub(crate) fn bind(&mut self, declared: &Type<ResolvedName>, actual: &Type<ResolvedName>) {
+        if matches!(actual, Type::Mixed | Type::Missing) {
+            return;
+        }
+
+        match declared {
+            Type::Named(name) if self.templates.contains_key(&name.original) => {
+                let bound = match self.bindings.remove(&name.original) {
+                    Some(existing) => union(vec![existing, actual.clone()]),
+                    None => actual.clone(),
+                };
+
+                self.bindings.insert(name.original.clone(), bound);
+            }
+            Type::Nullable(inner) => self.bind(inner, &without(actual, &[Type::Null])),
+            // Only one member of a union can be matched up unambiguously, e.g. the `T` in `T|null`.
+            Type::Union(types) => {
+                let (templated, rest): (Vec<_>, Vec<_>) =
+                    types.iter().cloned().partition(|ty| self.mentions(ty));
+
+                if let [declared] = templated.as_slice() {
+                    self.bind(declared, &without(actual, &rest));
+                }
+            }
+            Type::Generic(base, arguments) => match actual {
+                Type::Generic(actual_base, actual_arguments) if base == actual_base => {
+                    for (argument, actual) in arguments.iter().zip(actual_arguments) {
+                        self.bind(&argument.r#type, &actual.r#type);
+                    }
+                }
+                Type::TypedArray(key, value) if is_array_like(base) => {
+                    if let [declared_key, _] = arguments.as_slice() {
+                        self.bind(&declared_key.r#type, key);
+                    }
+
+                    if let Some(declared_value) = arguments.last() {
+                        self.bind(&declared_value.r#type, value);
+                    }
+                }
+                _ => {}
+            },
+            Type::TypedArray(key, value) => {
+                if let Type::TypedArray(actual_key, actual_value) = actual {
+                    self.bind(key, actual_key);
+                    self.bind(value, actual_value);
+                }
+            }
+            Type::CallableSignature(_, parameters, return_type) => {
+                if let Type::CallableSignature(_, actual_parameters, actual_return_type) = actual {
+                    for (parameter, actual) in parameters.iter().zip(actual_parameters) {
+                        self.bind(&parameter.r#type, &actual.r#type);
+                    }
+
+                    self.bind(return_type, actual_return_type);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Replace the templates in a type with the types that they've been bound to.
+    pub(crate) fn substitute(&self, ty: &Type<ResolvedName>) -> Type<ResolvedName> {
+        match ty {
+            Type::Named(name) => match self.templates.get(&name.original) {
+                Some(fallback) => self
+                    .bindings
+                    .get(&name.original)
+                    .unwrap_or(fallback)
+                    .clone(),
+                None => ty.clone(),
+            },
+            Type::Generic(base, arguments) => Type::Generic(
+                Box::new(self.substitute(base)),
+                arguments
+                    .iter()
+                    .map(|argument| GenericTypeArgument {
+                        r#type: self.substitute(&argument.r#type),
+                        variance: argument.variance,
+                    })
+                    .collect(),
+            ),
+            Type::Nullable(inner) => match self.substitute(inner) {
+                Type::Mixed => Type::Mixed,
+                inner if inner.allows_null() => inner,
+                inner @ (Type::Union(_) | Type::Intersection(_)) => union(vec![inner, Type::Null]),
+                inner => Type::Nullable(Box::new(inner)),
+            },
+            Type::Union(types) => union(types.iter().map(|ty| self.substitute(ty)).collect()),
+            Type::Intersection(types) => {
+                Type::Intersection(types.iter().map(|ty| self.substitute(ty)).collect())
+            }
+            Type::TypedArray(key, value) => Type::TypedArray(
+                Box::new(self.substitute(key)),
+                Box::new(self.substitute(value)),
+            ),
+            Type::CallableSignature(base, parameters, return_type) => Type::CallableSignature(
+                base.clone(),
+                parameters
+                    .iter()
+                    .map(|parameter| CallableParameter {
+                        r#type: self.substitute(&parameter.r#type),
+                        ..parameter.clone()
+                    })
+                    .collect(),
+                Box::new(self.substitute(return_type)),
+            ),
+            Type::ConditionalForParameter {
+                parameter,
+                negated,
+                target,
+                then,
+                otherwise,
+            } => {
+                let argument = self
+                    .arguments
+                    .get(&parameter.as_bytestr().strip_prefix(b'$').to_bytestring())
+                    .cloned()
+                    .unwrap_or(Type::Mixed);
+
+                self.evaluate(&argument, *negated, target, then, otherwise)
+            }
+            Type::Conditional {
+                subject,
+                negated,
+                target,
+                then,
+                otherwise,
+            } => self.evaluate(&self.substitute(subject), *negated, target, then, otherwise),
+            _ => ty.clone(),
+        }
+    }
+
+    /// Pick a branch of a conditional type based on the type of its subject, or both of them when
+    /// the subject's type isn't specific enough to decide.
+    fn evaluate(
+        &self,
+        subject: &Type<ResolvedName>,
+        negated: bool,
+        target: &Type<ResolvedName>,
+        then: &Type<ResolvedName>,
+        otherwise: &Type<ResolvedName>,
+    ) -> Type<ResolvedName> {
+        match is(subject, &self.substitute(target)) {
+            Some(matches) if matches != negated => self.substitute(then),
+            Some(_) => self.substitute(otherwise),
+            None => union(vec![self.substitute(then), self.substitute(otherwise)]),
+        }
+    }
+
+    /// Get the type that a template has been bound to, falling back to its bound.
+    pub(crate) fn resolve(&self, name: &ByteStr) -> Type<ResolvedName> {
+        let name = name.to_bytestring();
+
+        self.bindings
+            .get(&name)
+            .or_else(|| self.templates.get(&name))
+            .cloned()
+            .unwrap_or(Type::Mixed)
+    }
+
+    /// Check if a type refers to any of the templates that are in scope.
+    fn mentions(&self, ty: &Type<ResolvedName>) -> bool {
+        match ty {
+            Type::Named(name) => self.templates.contains_key(&name.original),
+            Type::Generic(base, arguments) => {
+                self.mentions(base)
+                    || arguments
+                        .iter()
+                        .any(|argument| self.mentions(&argument.r#type))
+            }
+            Type::Nullable(inner) => self.mentions(inner),
+            Type::Union(types) | Type::Intersection(types) => {
+                types.iter().any(|ty| self.mentions(ty))
+            }
+            Type::TypedArray(key, value) => self.mentions(key) || self.mentions(value),
+            Type::CallableSignature(_, parameters, return_type) => {
+                parameters
+                    .iter()
+                    .any(|parameter| self.mentions(&parameter.r#type))
+                    || self.mentions(return_type)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Check if a type is always, never or only sometimes a value of the target type.
+fn is(subject: &Type<ResolvedName>, target: &Type<ResolvedName>) -> Option<bool> {
+    if subject == target {
+        return Some(true);
+    }
+
+    match (subject, target) {
+        (Type::Mixed | Type::Missing, _) => None,
+        (Type::Union(types), _) => all(types.iter().map(|ty| is(ty, target))),
+        (Type::Nullable(inner), _) => all([is(&Type::Null, target), is(inner, target)].into_iter()),
+        (_, Type::Union(types)) => any(types.iter().map(|ty| is(subject, ty))),
+        (_, Type::Nullable(inner)) => {
+            any([is(subject, &Type::Null), is(subject, inner)].into_iter())
+        }
+        // Without knowing how classes are related, only the same class is definitely a match.
+        (Type::Named(_), Type::Named(_) | Type::Iterable | Type::Callable) => None,
+        _ if subject.refines(target) => Some(true),
+        _ if target.refines(subject) => None,
+        _ => Some(false),
+    }
+}
+
+/// Combine the results for the members of a union subject, which all need to agree.
+fn all(mut results: impl Iterator<Item = Option<bool>>) -> Option<bool> {
+    let first = results.next()?;
+
+    results.all(|result| result == first).then_some(first)?
+}
+
+/// Combine the results for the members of a union target, where a single match is enough.
+fn any(results: impl Iterator<Item = Option<bool>>) -> Option<bool> {
+    let mut decided = Some(false);
+
+    for result in results {
+        match result {
+            Some(true) => return Some(true),
+            Some(false) => {}
+            None => decided = None,
+        }
+    }
+
+    decided
+}
+
+fn is_array_like(ty: &Type<ResolvedName>) -> bool {
+    matches!(
+        ty,
+        Type::Array | Type::NonEmptyArray | Type::List | Type::NonEmptyList | Type::Iterable
+    )
+}
+
+/// Remove the given types from a type, so that the rest can be matched against the templated part of a union.
+fn without(ty: &Type<ResolvedName>, excluded: &[Type<ResolvedName>]) -> Type<ResolvedName> {
+    match ty {
+        Type::Nullable(inner) if excluded.contains(&Type::Null) => *inner.clone(),
+        Type::Union(types) => union(
+            types
+                .iter()
+                .filter(|ty| !excluded.contains(ty))
+                .cloned()
+                .collect(),
+        ),
+        _ => ty.clone(),
+    }
+}
+
+/// Combine types into a flat union without any duplicates.
+fn union(types: Vec<Type<ResolvedName>>) -> Type<ResolvedName> {
+    if types.contains(&Type::Mixed) {
+        return Type::Mixed;
+    }
+
+    let mut members = Vec::new();
+
+    for ty in types {
+        let flattened = match ty {
+            Type::Union(types) => types,
+            Type::Nullable(inner) => vec![*inner, Type::Null],
+            ty => vec![ty],
+        };
+
+        for ty in flattened {
+            if !members.contains(&ty) {
+                members.push(ty);
+            }
+        }
+    }
+
+    match members.len() {
+        0 => Type::Never,
+        1 => members.remove(0),
+        _ => Type::Union(members),
+    }
+}
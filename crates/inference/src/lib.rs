@@ -1,4 +1,5 @@
 mod engine;
+mod generics;
 mod map;
 
 pub use engine::TypeEngine;
@@ -7,11 +8,13 @@ pub use map::TypeMap;
 #[cfg(test)]
 mod tests {
     use pxp_ast::{HasId, ResolvedName, Statement, StatementKind};
-    use pxp_index::{FileId, Index};
+    use std::path::Path;
+
+    use pxp_index::{Extension, FileId, Index};
     use pxp_lexer::Lexer;
     use pxp_node_finder::NodeFinder;
     use pxp_parser::Parser;
-    use pxp_type::{ConstExpr, Type};
+    use pxp_type::{ConstExpr, GenericTypeArgument, Type};
 
     use crate::TypeEngine;
 
@@ -205,6 +208,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_infers_types_of_method_parameters() {
+        assert_eq!(
+            infer_at(
+                r#"
+        class A {
+            public function a(string $b, int ...$c) {
+                $b^^
+            }
+        }
+        "#
+            ),
+            Type::String
+        );
+    }
+
     #[test]
     fn outer_variables_are_not_accessible_inside_of_functions() {
         assert_eq!(
@@ -396,36 +415,1096 @@ mod tests {
         );
     }
 
-    /// Parse the given code, infer the types and return the type of the expression suffixed with a ^^ sequence.
-    fn infer_at(code: &str) -> Type<ResolvedName> {
-        let code = format!("<?php {};", code);
-        let marker = code
-            .find("^^")
-            .expect("Code does not contain a ^^ sequence.");
-        let code = code.replace("^^", "");
-        let result = Parser::parse(Lexer::new(code.as_bytes()));
+    #[test]
+    fn it_infers_type_of_global_constant() {
+        assert_eq!(
+            infer(
+                r#"
+        const FOO = 1;
 
-        let mut index = Index::new();
-        index.index(FileId::new(0), &result.ast);
+        FOO
+        "#
+            ),
+            Type::Integer
+        );
+    }
 
-        let engine = TypeEngine::new(&index);
-        let map = engine.infer(&result.ast);
+    #[test]
+    fn it_infers_type_of_constant_defined_with_define() {
+        assert_eq!(
+            infer(
+                r#"
+        define('BAR', 'bar');
 
-        let Some((node, _)) = NodeFinder::find_at_byte_offset(&result.ast, marker) else {
-            panic!("Could not find a node at the given marker.");
-        };
+        BAR
+        "#
+            ),
+            Type::LiteralString(b"bar".into())
+        );
+    }
 
-        map.resolve(node.id).clone()
+    #[test]
+    fn it_infers_type_of_unqualified_constant_inside_of_namespace() {
+        let code = "namespace { const FOO = 1.5; }";
+
+        assert_eq!(
+            infer_at(&format!("{code} namespace App {{ const FOO = 1; F^^OO; }}")),
+            Type::Integer
+        );
+        assert_eq!(
+            infer_at(&format!(r"{code} namespace App {{ define('App\FOO', 1); F^^OO; }}")),
+            Type::Integer
+        );
+        assert_eq!(
+            infer_at(&format!("{code} namespace App {{ F^^OO; }}")),
+            Type::Float
+        );
+        assert_eq!(
+            infer_at(&format!("{code} namespace App {{ const FOO = 1; }} namespace Other {{ F^^OO; }}")),
+            Type::Float
+        );
     }
 
-    /// Parse the given code, infer the types and return the type of the last expression in the code.
-    fn infer(code: &str) -> Type<ResolvedName> {
-        // Parse the code.
-        let result = Parser::parse(Lexer::new(format!("<?php {};", code).as_bytes()));
+    #[test]
+    fn it_infers_type_of_unqualified_function_call_inside_of_namespace() {
+        let code = "namespace { function a(): float {} }";
 
-        // Create an index and index the generated AST.
-        let mut index = Index::new();
-        index.index(FileId::new(0), &result.ast);
+        assert_eq!(
+            infer_at(&format!("{code} namespace App {{ function a(): int {{}} $b = a(); $^^b; }}")),
+            Type::Integer
+        );
+        assert_eq!(
+            infer_at(&format!("{code} namespace App {{ $b = a(); $^^b; }}")),
+            Type::Float
+        );
+    }
+
+    #[test]
+    fn it_infers_type_of_function_call_with_different_casing() {
+        assert_eq!(
+            infer(
+                r#"
+        function foo(): int {}
+
+        FOO()
+        "#
+            ),
+            Type::Integer
+        );
+    }
+
+    #[test]
+    fn it_infers_type_of_method_call_with_different_casing() {
+        assert_eq!(
+            infer(
+                r#"
+        class Foo {
+            function getBar(): int {}
+        }
+
+        (new foo)->GETBAR()
+        "#
+            ),
+            Type::Integer
+        );
+    }
+
+    #[test]
+    fn it_infers_type_of_static_method_call_with_different_casing() {
+        assert_eq!(
+            infer(
+                r#"
+        class Foo {
+            static function bar(): int {}
+        }
+
+        FOO::Bar()
+        "#
+            ),
+            Type::Integer
+        );
+    }
+
+    #[test]
+    fn it_infers_type_of_builtin_function_calls() {
+        assert_eq!(infer_with_stubs("strlen('Hello, world!')"), Type::Integer);
+        assert_eq!(
+            infer_with_stubs("\\str_contains('abc', 'b')"),
+            Type::Boolean
+        );
+    }
+
+    #[test]
+    fn it_infers_type_of_builtin_method_calls() {
+        assert_eq!(
+            infer_with_stubs("(new DateTimeImmutable())->format('Y-m-d')"),
+            Type::String
+        );
+    }
+
+    #[test]
+    fn it_narrows_types_with_instanceof() {
+        assert_eq!(
+            infer_at(
+                r#"
+        function a(Foo|Bar $a) {
+            if ($a instanceof Foo) {
+                $a^^;
+            }
+        }
+        "#
+            ),
+            named("Foo")
+        );
+
+        assert_eq!(
+            infer_at(
+                r#"
+        function a(Foo|Bar $a) {
+            if (! $a instanceof Foo) {
+                $a^^;
+            }
+        }
+        "#
+            ),
+            named("Bar")
+        );
+
+        assert_eq!(
+            infer_at(
+                r#"
+        function a($a) {
+            if ($a instanceof Foo) {
+                $a^^;
+            }
+        }
+        "#
+            ),
+            named("Foo")
+        );
+    }
+
+    #[test]
+    fn it_narrows_types_with_instanceof_using_subclasses() {
+        let code = "class Base {} class Child extends Base {} class Other {}";
+
+        assert_eq!(
+            infer_at(&format!(
+                "{code} function a(Base $a) {{ if ($a instanceof Child) {{ $^^a; }} }}"
+            )),
+            named("Child")
+        );
+        assert_eq!(
+            infer_at(&format!(
+                "{code} function a(Base|Other $a) {{ if ($a instanceof Child) {{ $^^a; }} }}"
+            )),
+            named("Child")
+        );
+        assert_eq!(
+            infer_at(&format!(
+                "{code} function a(Base|Other $a) {{ if (! $a instanceof Child) {{ $^^a; }} }}"
+            )),
+            Type::Union(vec![named("Base"), named("Other")])
+        );
+        assert_eq!(
+            infer_at(&format!(
+                "{code} function a(Child|Other $a) {{ if ($a instanceof Base) {{ $^^a; }} }}"
+            )),
+            named("Child")
+        );
+        assert_eq!(
+            infer_at(&format!(
+                "{code} function a(Child|Other $a) {{ if (! $a instanceof Base) {{ $^^a; }} }}"
+            )),
+            named("Other")
+        );
+    }
+
+    #[test]
+    fn it_narrows_types_with_instanceof_using_interfaces() {
+        let code = "interface Model {} class User implements Model {} class Admin extends User {} class Post {}";
+
+        assert_eq!(
+            infer_at(&format!(
+                "{code} function a(Model $a) {{ if ($a instanceof User) {{ $^^a; }} }}"
+            )),
+            named("User")
+        );
+        assert_eq!(
+            infer_at(&format!(
+                "{code} function a(Admin|Post $a) {{ if ($a instanceof Model) {{ $^^a; }} }}"
+            )),
+            named("Admin")
+        );
+        assert_eq!(
+            infer_at(&format!(
+                "{code} function a(Admin|Post $a) {{ if (! $a instanceof Model) {{ $^^a; }} }}"
+            )),
+            named("Post")
+        );
+    }
+
+    #[test]
+    fn it_narrows_types_with_type_checking_functions() {
+        assert_eq!(
+            infer_at(
+                r#"
+        function a(string|int|null $a) {
+            if (is_string($a)) {
+                $a^^;
+            }
+        }
+        "#
+            ),
+            Type::String
+        );
+
+        assert_eq!(
+            infer_at(
+                r#"
+        function a(string|int|null $a) {
+            if (is_string($a)) {
+                return;
+            }
+
+            $a^^;
+        }
+        "#
+            ),
+            Type::Union(vec![Type::Integer, Type::Null])
+        );
+    }
+
+    #[test]
+    fn it_removes_null_after_an_early_return() {
+        assert_eq!(
+            infer_at(
+                r#"
+        function a(?Foo $a) {
+            if ($a === null) {
+                return;
+            }
+
+            $a^^;
+        }
+        "#
+            ),
+            named("Foo")
+        );
+
+        assert_eq!(
+            infer_at(
+                r#"
+        function a(?Foo $a) {
+            if (null === $a) throw new Exception();
+            $a^^;
+        }
+        "#
+            ),
+            named("Foo")
+        );
+
+        assert_eq!(
+            infer_at(
+                r#"
+        function a(?Foo $a) {
+            if (! $a) {
+                return;
+            }
+
+            $a^^;
+        }
+        "#
+            ),
+            named("Foo")
+        );
+    }
+
+    #[test]
+    fn it_narrows_types_with_isset() {
+        assert_eq!(
+            infer_at(
+                r#"
+        function a(?Foo $a) {
+            if (isset($a)) {
+                $a^^;
+            }
+        }
+        "#
+            ),
+            named("Foo")
+        );
+
+        assert_eq!(
+            infer_at(
+                r#"
+        function a(?Foo $a) {
+            if (! isset($a)) {
+                $a^^;
+            }
+        }
+        "#
+            ),
+            Type::Null
+        );
+    }
+
+    #[test]
+    fn it_narrows_types_in_elseif_and_else_branches() {
+        assert_eq!(
+            infer_at(
+                r#"
+        function a(Foo|Bar|null $a) {
+            if ($a === null) {
+            } elseif ($a instanceof Foo) {
+            } else {
+                $a^^;
+            }
+        }
+        "#
+            ),
+            named("Bar")
+        );
+    }
+
+    #[test]
+    fn it_narrows_the_right_hand_side_of_logical_operators() {
+        assert_eq!(
+            infer_at(
+                r#"
+        function a(?Foo $a) {
+            $a !== null && $a^^;
+        }
+        "#
+            ),
+            named("Foo")
+        );
+
+        assert_eq!(
+            infer_at(
+                r#"
+        function a(?Foo $a) {
+            $a === null || $a^^;
+        }
+        "#
+            ),
+            named("Foo")
+        );
+    }
+
+    #[test]
+    fn it_merges_branches_into_unions() {
+        assert_eq!(
+            infer(
+                r#"
+        if (rand()) {
+            $a = 1;
+        } else {
+            $a = 'a';
+        }
+
+        $a
+        "#
+            ),
+            Type::Union(vec![Type::Integer, Type::LiteralString(b"a".into())])
+        );
+
+        assert_eq!(
+            infer(
+                r#"
+        $a = null;
+
+        if (rand()) {
+            $a = 1;
+        }
+
+        $a
+        "#
+            ),
+            Type::Union(vec![Type::Integer, Type::Null])
+        );
+
+        assert_eq!(
+            infer(
+                r#"
+        $a = 1;
+
+        if (rand()) {
+            $a = 'a';
+        } else {
+            return;
+        }
+
+        $a
+        "#
+            ),
+            Type::LiteralString(b"a".into())
+        );
+    }
+
+    #[test]
+    fn it_narrows_types_in_match_arms() {
+        assert_eq!(
+            infer_at(
+                r#"
+        function a(Foo|Bar $a) {
+            return match (true) {
+                $a instanceof Foo => 1,
+                default => $a^^,
+            };
+        }
+        "#
+            ),
+            named("Bar")
+        );
+
+        assert_eq!(
+            infer(
+                r#"
+        match ($a) {
+            1 => 1,
+            2 => 'a',
+            default => throw new Exception(),
+        }
+        "#
+            ),
+            Type::Union(vec![Type::Integer, Type::LiteralString(b"a".into())])
+        );
+    }
+
+    #[test]
+    fn it_narrows_types_in_ternaries() {
+        assert_eq!(
+            infer(
+                r#"
+        $a = rand() ? 1 : null;
+        $a
+        "#
+            ),
+            Type::Union(vec![Type::Integer, Type::Null])
+        );
+
+        assert_eq!(
+            infer_at(
+                r#"
+        function a(?Foo $a) {
+            return $a ? $a^^ : null;
+        }
+        "#
+            ),
+            named("Foo")
+        );
+    }
+
+    #[test]
+    fn it_resolves_function_templates_from_arguments() {
+        let code = r#"
+        /**
+         * @template T
+         * @param T $value
+         * @return T
+         */
+        function identity($value) {}
+        "#;
+
+        assert_eq!(
+            infer(&format!("{code} identity(new Foo())")),
+            named("Foo")
+        );
+        assert_eq!(infer(&format!("{code} identity(value: 1)")), Type::Integer);
+        assert_eq!(infer(&format!("{code} identity()")), Type::Mixed);
+    }
+
+    #[test]
+    fn it_falls_back_to_the_bound_of_unresolved_templates() {
+        assert_eq!(
+            infer(
+                r#"
+        /**
+         * @template T of Foo
+         * @return T
+         */
+        function make() {}
+        make()
+        "#
+            ),
+            named("Foo")
+        );
+    }
+
+    #[test]
+    fn it_resolves_class_templates() {
+        let code = r#"
+        /**
+         * @template TKey
+         * @template TValue
+         */
+        class Collection {
+            /** @param array<TKey, TValue> $items */
+            public function __construct(array $items) {}
+
+            /** @return TValue|null */
+            public function first() {}
+
+            /**
+             * @template TMapValue
+             * @param callable(TValue): TMapValue $callback
+             * @return Collection<TKey, TMapValue>
+             */
+            public function map(callable $callback) {}
+
+            public function values(): static {}
+        }
+        "#;
+
+        let collection = |key, value| generic(named("Collection"), vec![key, value]);
+
+        assert_eq!(
+            infer(&format!("{code} new Collection([new User()])")),
+            collection(Type::Integer, named("User"))
+        );
+        assert_eq!(
+            infer(&format!("{code} (new Collection([new User()]))->first()")),
+            Type::Union(vec![named("User"), Type::Null])
+        );
+        assert_eq!(
+            infer(&format!(
+                "{code} (new Collection([new User()]))->map(fn (User $user) => 'a')"
+            )),
+            collection(Type::Integer, Type::LiteralString(b"a".into()))
+        );
+        assert_eq!(
+            infer(&format!("{code} (new Collection([new User()]))->values()")),
+            collection(Type::Integer, named("User"))
+        );
+    }
+
+    #[test]
+    fn it_resolves_class_templates_through_extends_and_implements() {
+        let code = r#"
+        /** @template T */
+        interface Repository {
+            /** @return T */
+            public function find(int $id);
+        }
+
+        /**
+         * @template TKey
+         * @template TValue
+         */
+        class Collection {
+            /** @return TValue|null */
+            public function first() {}
+        }
+
+        /**
+         * @template TModel
+         * @extends Collection<int, TModel>
+         */
+        class ModelCollection extends Collection {}
+
+        /** @extends ModelCollection<User> */
+        class UserCollection extends ModelCollection {}
+
+        /** @extends Repository<User> */
+        interface UserRepository extends Repository {}
+
+        /** @implements Repository<Post> */
+        abstract class PostRepository implements Repository {}
+
+        function users(): UserRepository {}
+        function posts(): PostRepository {}
+        "#;
+
+        assert_eq!(
+            infer(&format!("{code} (new UserCollection())->first()")),
+            Type::Union(vec![named("User"), Type::Null])
+        );
+        assert_eq!(
+            infer(&format!("{code} (new ModelCollection())->first()")),
+            Type::Mixed
+        );
+        assert_eq!(infer(&format!("{code} users()->find(1)")), named("User"));
+        assert_eq!(infer(&format!("{code} posts()->find(1)")), named("Post"));
+    }
+
+    #[test]
+    fn it_resolves_callable_templates() {
+        assert_eq!(
+            infer_with_stubs("array_map(fn (Foo $foo): string => $foo->name, [new Foo()])"),
+            generic(Type::Array, vec![Type::Integer, Type::String])
+        );
+        assert_eq!(
+            infer_with_stubs("array_map(function (int $a) { return 'a'; }, [1, 2])"),
+            generic(Type::Array, vec![Type::Integer, Type::Mixed])
+        );
+    }
+
+    #[test]
+    fn it_uses_docblock_types_for_parameters_and_return_types() {
+        let code = r#"
+        /**
+         * @param non-empty-string $name
+         * @param int $count
+         * @return list<User>
+         */
+        function users(string $name, float $count, $options): array {}
+        "#;
+
+        assert_eq!(
+            infer_at("/** @param non-empty-string $name */ function users(string $name) { $na^^me; }"),
+            Type::NonEmptyString
+        );
+        // Docblock types that contradict the native type are ignored.
+        assert_eq!(
+            infer_at("/** @param int $count */ function users(float $count) { $cou^^nt; }"),
+            Type::Float
+        );
+        assert_eq!(
+            infer_at("/** @param Foo $foo */ function users($foo) { $fo^^o; }"),
+            named("Foo")
+        );
+        assert_eq!(
+            infer(&format!("{code} users('a', 1, [])")),
+            generic(Type::List, vec![named("User")])
+        );
+        assert_eq!(
+            infer_at(
+                "class Foo { /** @param positive-int $id */ public function find(int $id) { $i^^d; } }"
+            ),
+            Type::NonNegativeInteger
+        );
+        assert_eq!(
+            infer("$f = /** @return non-empty-string */ fn (): string => 'a'; $f"),
+            Type::CallableSignature(
+                Box::new(named("Closure")),
+                vec![],
+                Box::new(Type::NonEmptyString)
+            )
+        );
+    }
+
+    #[test]
+    fn it_only_uses_docblock_classes_that_extend_the_native_class() {
+        let code = "interface Model {} class User implements Model {} class Admin extends User {} class Post {}";
+
+        assert_eq!(
+            infer_at(&format!(
+                "{code} /** @param Post $user */ function f(User $user) {{ $us^^er; }}"
+            )),
+            named("User")
+        );
+        assert_eq!(
+            infer_at(&format!(
+                "{code} /** @param Admin $user */ function f(User $user) {{ $us^^er; }}"
+            )),
+            named("Admin")
+        );
+        assert_eq!(
+            infer_at(&format!(
+                "{code} /** @param Admin $user */ function f(Model $user) {{ $us^^er; }}"
+            )),
+            named("Admin")
+        );
+        assert_eq!(
+            infer(&format!("{code} function user(): User {{}} /** @var Post $user */ $user = user(); $user")),
+            named("User")
+        );
+        assert_eq!(
+            infer(&format!("{code} function user(): User {{}} /** @var Admin $user */ $user = user(); $user")),
+            named("Admin")
+        );
+    }
+
+    #[test]
+    fn it_uses_inline_var_annotations() {
+        assert_eq!(
+            infer("function user() {} /** @var User $user */ $user = user(); $user"),
+            named("User")
+        );
+        assert_eq!(
+            infer("function user() {} /** @var User */ $user = user(); $user"),
+            named("User")
+        );
+        assert_eq!(
+            infer("/** @var User $user */ echo $user; $user"),
+            named("User")
+        );
+        // Annotations that contradict the assigned value are ignored.
+        assert_eq!(infer("/** @var string $count */ $count = 1; $count"), Type::Integer);
+        assert_eq!(
+            infer("/** @var Foo $foo */ $user = 1; $user"),
+            Type::Integer
+        );
+    }
+
+    #[test]
+    fn it_evaluates_conditional_return_types() {
+        let code = r#"
+        /**
+         * @return ($value is string ? int : float)
+         */
+        function length($value) {}
+
+        /**
+         * @return ($strict is not true ? string|null : string)
+         */
+        function env(string $key, bool $strict = false) {}
+
+        class Container {
+            /**
+             * @template T
+             * @param T $id
+             * @return (T is int ? User : Post)
+             */
+            public function get($id) {}
+        }
+        "#;
+
+        assert_eq!(infer(&format!("{code} length('a')")), Type::Integer);
+        assert_eq!(infer(&format!("{code} length(value: 1.5)")), Type::Float);
+        assert_eq!(infer(&format!("{code} length([1])")), Type::Float);
+        assert_eq!(infer(&format!("{code} env('APP_KEY', true)")), Type::String);
+        assert_eq!(
+            infer(&format!("{code} env('APP_KEY')")),
+            Type::Union(vec![Type::String, Type::Null])
+        );
+        assert_eq!(
+            infer(&format!("{code} (new Container())->get(1)")),
+            named("User")
+        );
+    }
+
+    #[test]
+    fn it_falls_back_to_both_branches_of_undecidable_conditional_types() {
+        let code = r#"
+        /**
+         * @return ($value is string ? int : float)
+         */
+        function length($value) {}
+        "#;
+
+        let both = Type::Union(vec![Type::Integer, Type::Float]);
+
+        assert_eq!(infer(&format!("{code} length()")), both);
+        assert_eq!(infer(&format!("{code} length($unknown)")), both);
+        assert_eq!(
+            infer(&format!("{code} length(rand() ? 1 : 'a')")),
+            both
+        );
+    }
+
+    #[test]
+    fn it_infers_type_of_property_fetch_expression() {
+        let code = r#"
+        /**
+         * @property-read int $views
+         */
+        class Post {
+            public string $title;
+
+            public $draft = false;
+
+            public function __construct(public ?User $author) {}
+        }
+
+        function post(): Post {}
+        "#;
+
+        assert_eq!(infer(&format!("{code} post()->title")), Type::String);
+        assert_eq!(infer(&format!("{code} post()->draft")), Type::False);
+        assert_eq!(
+            infer(&format!("{code} post()->author")),
+            Type::Nullable(Box::new(named("User")))
+        );
+        assert_eq!(infer(&format!("{code} post()->views")), Type::Integer);
+        assert_eq!(infer(&format!("{code} post()->missing")), Type::Mixed);
+        assert_eq!(
+            infer(&format!("{code} post()?->title")),
+            Type::Union(vec![Type::String, Type::Null])
+        );
+        assert_eq!(infer("$a = 1; $a->foo"), Type::Invalid);
+    }
+
+    #[test]
+    fn it_resolves_class_templates_in_property_types() {
+        let code = r#"
+        /** @template T */
+        class Box {
+            /** @param T $value */
+            public function __construct(public mixed $value) {}
+        }
+        "#;
+
+        assert_eq!(
+            infer(&format!("{code} (new Box(new User()))->value")),
+            named("User")
+        );
+    }
+
+    #[test]
+    fn it_infers_type_of_static_property_fetch_expression() {
+        let code = r#"
+        class Counter {
+            public static int $count = 0;
+        }
+        "#;
+
+        assert_eq!(infer(&format!("{code} Counter::$count")), Type::Integer);
+        assert_eq!(
+            infer_at(
+                "class Counter { protected static $name = 'a'; function test() { return static:^^:$name; } }"
+            ),
+            Type::LiteralString(b"a".into())
+        );
+        assert_eq!(infer(&format!("{code} Counter::$missing")), Type::Mixed);
+    }
+
+    #[test]
+    fn it_infers_type_of_constant_fetch_expression() {
+        let code = r#"
+        class Config {
+            const VERSION = '1.0';
+
+            const int TIMEOUT = 30;
+
+            const string DRIVER = self::DEFAULT;
+        }
+
+        class Child extends Config {}
+
+        enum Status: int {
+            case Active = 1;
+        }
+
+        enum Suit {
+            case Hearts;
+        }
+        "#;
+
+        assert_eq!(
+            infer(&format!("{code} Config::VERSION")),
+            Type::LiteralString(b"1.0".into())
+        );
+        assert_eq!(infer(&format!("{code} Config::TIMEOUT")), Type::Integer);
+        assert_eq!(infer(&format!("{code} Config::DRIVER")), Type::String);
+        assert_eq!(
+            infer(&format!("{code} Child::VERSION")),
+            Type::LiteralString(b"1.0".into())
+        );
+        assert_eq!(infer(&format!("{code} Config::class")), Type::ClassString);
+        assert_eq!(infer(&format!("{code} Status::Active")), named("Status"));
+        assert_eq!(infer(&format!("{code} Status::Active->value")), Type::Integer);
+        assert_eq!(infer(&format!("{code} Suit::Hearts->name")), Type::String);
+        assert_eq!(infer(&format!("{code} Suit::Hearts->value")), Type::Mixed);
+        assert_eq!(infer(&format!("{code} Config::MISSING")), Type::Mixed);
+        assert_eq!(
+            infer_at(
+                "class Config { const VERSION = 1; } class Child extends Config { function version() { return parent:^^:VERSION; } }"
+            ),
+            Type::Integer
+        );
+        assert_eq!(
+            infer_at("class Config { const VERSION = 1; function version() { return self:^^:VERSION; } }"),
+            Type::Integer
+        );
+    }
+
+    #[test]
+    fn it_resolves_self_and_parent_return_types_to_the_declaring_class() {
+        let code = r#"
+        class Model {
+            public static function make(): self {}
+
+            public function me(): ?self {}
+
+            public function number(): int {}
+        }
+
+        class User extends Model {
+            public function model(): parent {}
+        }
+
+        function model(): Model {}
+        "#;
+
+        assert_eq!(infer(&format!("{code} Model::make()")), named("Model"));
+        assert_eq!(infer(&format!("{code} Model::make()->number()")), Type::Integer);
+        assert_eq!(
+            infer(&format!("{code} model()->me()")),
+            Type::Nullable(Box::new(named("Model")))
+        );
+        assert_eq!(infer(&format!("{code} model()->me()->number()")), Type::Integer);
+        assert_eq!(infer(&format!("{code} (new User())->me()")), Type::Nullable(Box::new(named("Model"))));
+        assert_eq!(infer(&format!("{code} (new User())->model()")), named("Model"));
+        assert_eq!(infer(&format!("{code} (new User())->model()->number()")), Type::Integer);
+    }
+
+    #[test]
+    fn it_infers_the_type_of_this_inside_of_instance_methods() {
+        let code = "class User { public string $name; public function id(): int {} public static function make(): static {}";
+
+        assert_eq!(
+            infer_at(&format!("{code} function name() {{ return $this-^^>name; }} }}")),
+            Type::String
+        );
+        assert_eq!(
+            infer_at(&format!("{code} function key() {{ return $this-^^>id(); }} }}")),
+            Type::Integer
+        );
+        assert_eq!(
+            infer_at(&format!("{code} function me() {{ return $th^^is; }} }}")),
+            named("User")
+        );
+        assert_eq!(
+            infer_at(&format!(
+                "{code} function names() {{ return function () {{ return $this-^^>name; }}; }} }}"
+            )),
+            Type::String
+        );
+        assert_eq!(
+            infer_at(&format!(
+                "{code} function names() {{ return fn () => $this-^^>name; }} }}"
+            )),
+            Type::String
+        );
+    }
+
+    #[test]
+    fn this_is_not_bound_inside_of_static_methods_and_closures() {
+        let code = "class User { public string $name;";
+
+        assert_eq!(
+            infer_at(&format!("{code} static function me() {{ return $th^^is; }} }}")),
+            Type::Mixed
+        );
+        assert_eq!(
+            infer_at(&format!(
+                "{code} function names() {{ return static function () {{ return $th^^is; }}; }} }}"
+            )),
+            Type::Mixed
+        );
+        assert_eq!(infer_at("function me() { return $th^^is; }"), Type::Mixed);
+    }
+
+    #[test]
+    fn it_resolves_self_types_when_fetching_properties() {
+        assert_eq!(
+            infer_at(
+                "class A { public int $id; function eq(self $other) { return $other-^^>id; } }"
+            ),
+            Type::Integer
+        );
+        assert_eq!(
+            infer_at(
+                "class A { public int $id; function eq(?self $other) { return $other?-^^>id; } }"
+            ),
+            Type::Union(vec![Type::Integer, Type::Null])
+        );
+        assert_eq!(
+            infer_at(
+                "class A { public int $id; } class B extends A { function eq(parent $other) { return $other-^^>id; } }"
+            ),
+            Type::Integer
+        );
+
+        let code = r#"
+        class Node {
+            public ?self $next;
+
+            public int $value;
+
+            public static ?self $root;
+
+            public function me(): ?self {}
+        }
+
+        function node(): Node {}
+        "#;
+
+        assert_eq!(
+            infer(&format!("{code} node()->next")),
+            Type::Nullable(Box::new(named("Node")))
+        );
+        assert_eq!(infer(&format!("{code} node()->next->value")), Type::Integer);
+        assert_eq!(
+            infer(&format!("{code} node()->me()?->value")),
+            Type::Union(vec![Type::Integer, Type::Null])
+        );
+        assert_eq!(
+            infer(&format!("{code} node()->me()::$root")),
+            Type::Nullable(Box::new(named("Node")))
+        );
+        assert_eq!(
+            infer(&format!("{code} Node::$root")),
+            Type::Nullable(Box::new(named("Node")))
+        );
+    }
+
+    /// Create a named type for a class in the global namespace.
+    fn named(name: &str) -> Type<ResolvedName> {
+        Type::Named(ResolvedName {
+            resolved: name.as_bytes().into(),
+            original: name.as_bytes().into(),
+        })
+    }
+
+    /// Create a generic type with invariant arguments.
+    fn generic(base: Type<ResolvedName>, arguments: Vec<Type<ResolvedName>>) -> Type<ResolvedName> {
+        Type::Generic(
+            Box::new(base),
+            arguments
+                .into_iter()
+                .map(|r#type| GenericTypeArgument {
+                    r#type,
+                    variance: None,
+                })
+                .collect(),
+        )
+    }
+
+    /// Parse the given code, infer the types and return the type of the expression suffixed with a ^^ sequence.
+    fn infer_at(code: &str) -> Type<ResolvedName> {
+        let code = format!("<?php {};", code);
+        let marker = code
+            .find("^^")
+            .expect("Code does not contain a ^^ sequence.");
+        let code = code.replace("^^", "");
+        let result = Parser::parse(Lexer::new(code.as_bytes()));
+
+        let mut index = Index::new();
+        index.index(FileId::new(0), &result.ast);
+
+        let engine = TypeEngine::new(&index);
+        let map = engine.infer(&result.ast);
+
+        let Some((node, _)) = NodeFinder::find_at_byte_offset(&result.ast, marker) else {
+            panic!("Could not find a node at the given marker.");
+        };
+
+        map.resolve(node.id).clone()
+    }
+
+    /// Parse the given code, infer the types and return the type of the last expression in the code.
+    fn infer(code: &str) -> Type<ResolvedName> {
+        infer_with_index(Index::new(), code)
+    }
+
+    /// Same as `infer`, but with the stubs for PHP's required extensions loaded into the index.
+    fn infer_with_stubs(code: &str) -> Type<ResolvedName> {
+        let mut index = Index::new();
+        index.load_stubs(&Extension::REQUIRED);
+
+        infer_with_index(index, code)
+    }
+
+    fn infer_with_index(mut index: Index, code: &str) -> Type<ResolvedName> {
+        // Parse the code.
+        let code = format!("<?php {};", code);
+        let result = Parser::parse(Lexer::new(code.as_bytes()));
+
+        // Index the code alongside anything that's already in the index.
+        index.update_file(Path::new("test.php"), code.as_bytes());
 
         // Create a `TypeEngine` and infer the types.
         let engine = TypeEngine::new(&index);
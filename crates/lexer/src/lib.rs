@@ -77,6 +77,11 @@ impl<'a> Lexer<'a> {
             .push(Diagnostic::new(diagnostic, severity, span));
     }
 
+    /// Take the diagnostics that have been produced so far, leaving the lexer with none.
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic<LexerDiagnostic>> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
     pub fn collect(&'a mut self) -> Vec<OwnedToken> {
         let mut tokens = Vec::new();
 
@@ -163,7 +168,12 @@ impl<'a> Lexer<'a> {
             // In this state, all the text that follows is no longer parsed as PHP as is collected
             // into a single "InlineHtml" token (kind of cheating, oh well).
             StackFrame::Halted => {
+                if self.source.eof() {
+                    return Token::new_without_symbol(TokenKind::Eof, self.source.span());
+                }
+
                 let symbol = self.source.read_remaining();
+                self.source.skip(symbol.len());
 
                 Token::new(TokenKind::InlineHtml, self.source.span(), symbol)
             }
@@ -1450,8 +1460,12 @@ impl<'a> Lexer<'a> {
                 }
             }
             [b, ..] => {
+                let b = *b;
+
+                // Skip over the character so that we don't produce the same invalid token forever.
+                self.source.next();
                 self.diagnostic(
-                    LexerDiagnostic::UnexpectedCharacter(*b),
+                    LexerDiagnostic::UnexpectedCharacter(b),
                     Severity::Error,
                     self.source.span(),
                 );
@@ -1533,6 +1547,7 @@ impl<'a> Lexer<'a> {
                 &[b'\\', b'"' | b'\\' | b'$', ..] => {
                     self.source.skip(2);
                 }
+                &[b'\\', b'u' | b'0'..=b'7', ..] => self.escape_sequence(),
                 // If we spot any of these, we want to break to make sure they get picked up in the next iteration.
                 &[b'"', ..] | [b'$', b'{', ..] | [b'{', b'$', ..] | [b'$', ident_start!(), ..] => {
                     break;
@@ -1618,6 +1633,7 @@ impl<'a> Lexer<'a> {
                 &[b'\\', b'`' | b'\\' | b'$', ..] => {
                     self.source.skip(2);
                 }
+                &[b'\\', b'u' | b'0'..=b'7', ..] => self.escape_sequence(),
                 // If we spot any of these, we want to break to make sure they get picked up in the next iteration.
                 &[b'`', ..] | [b'$', b'{', ..] | [b'{', b'$', ..] | [b'$', ident_start!(), ..] => {
                     break;
@@ -1708,6 +1724,7 @@ impl<'a> Lexer<'a> {
                 [b'\\', b'"' | b'\\' | b'$', ..] => {
                     self.source.skip(2);
                 }
+                [b'\\', b'u' | b'0'..=b'7', ..] => self.escape_sequence(),
                 // These characters start interpolation sequences, so if we find them
                 // here we need to break out of the loop and let them get picked up
                 // in the next iteration of the lexer.
@@ -1913,6 +1930,7 @@ impl<'a> Lexer<'a> {
                 [b'\\', b'"' | b'\\' | b'$', ..] => {
                     self.source.skip(2);
                 }
+                [b'\\', b'u' | b'0'..=b'7', ..] => self.escape_sequence(),
                 [b'"', ..] => {
                     self.source.next();
                     break true;
@@ -1944,6 +1962,56 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Check a `\u{...}` or octal escape sequence in an interpolated string, reporting the ones that
+    /// PHP rejects. Only the backslash is consumed, the rest of the sequence is part of the string.
+    fn escape_sequence(&mut self) {
+        let start = self.source.offset();
+
+        match self.source.peek(1, 2) {
+            // `\u` without a brace isn't an escape sequence, so PHP leaves it alone.
+            [b'u', b'{'] => {
+                let digits = self.source.read_remaining()[3..]
+                    .iter()
+                    .take_while(|byte| byte.is_ascii_hexdigit())
+                    .count();
+                let closed = self.source.peek(3 + digits, 1) == b"}";
+                let codepoint = std::str::from_utf8(self.source.peek(3, digits))
+                    .ok()
+                    .and_then(|digits| u32::from_str_radix(digits, 16).ok());
+
+                if !closed || codepoint.map_or(true, |codepoint| codepoint > 0x10FFFF) {
+                    self.diagnostic(
+                        LexerDiagnostic::InvalidUnicodeEscapeSequence,
+                        Severity::Error,
+                        Span::new(start, start + 3 + digits + closed as usize),
+                    );
+                }
+            }
+            [b'0'..=b'7', ..] => {
+                let digits = self.source.read_remaining()[1..]
+                    .iter()
+                    .take(3)
+                    .take_while(|byte| matches!(byte, b'0'..=b'7'))
+                    .count();
+                let value = self.source.peek(1, digits).iter().fold(0u32, |value, digit| {
+                    value * 8 + (digit - b'0') as u32
+                });
+
+                // PHP only warns about these, and keeps the lowest byte of the value.
+                if value > 0o377 {
+                    self.diagnostic(
+                        LexerDiagnostic::InvalidOctalSequence,
+                        Severity::Warning,
+                        Span::new(start, start + 1 + digits),
+                    );
+                }
+            }
+            _ => {}
+        }
+
+        self.source.next();
+    }
+
     fn peek_identifier(&self) -> Option<&[u8]> {
         let mut size = 0;
 
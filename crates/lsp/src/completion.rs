@@ -0,0 +1,671 @@
+use std::collections::HashMap;
+
+use lsp_types::{CompletionItem, CompletionItemKind, CompletionTextEdit, Range, TextEdit};
+use pxp_ast::{
+    Expression, ExpressionKind, NamespaceStatement, Node, Statement, StatementKind, Use, UseKind,
+    Visibility,
+};
+use pxp_index::{Index, ReflectionClass, ReflectionFunctionLike, ReflectionType};
+use pxp_inference::TypeEngine;
+use pxp_lexer::Lexer;
+use pxp_node_finder::NodeFinder;
+use pxp_parser::Parser;
+use pxp_span::{ByteOffset, Span};
+
+use crate::{
+    document::Document,
+    resolver::{enclosing_class, Resolver},
+};
+
+/// Suggest what could be typed at the given offset: the members of a class after `->`, `?->` or
+/// `::`, and the names of classes, functions, constants and namespaces everywhere else.
+pub fn completion(document: &Document, index: &Index, offset: ByteOffset) -> Vec<CompletionItem> {
+    let contents = document.contents().as_bytes();
+    let offset = offset.min(contents.len());
+
+    let Some(context) = Context::at(contents, offset) else {
+        return Vec::new();
+    };
+
+    let range = document
+        .lines()
+        .range(document.contents(), Span::new(context.start(), offset));
+    let mut items = Items::new(&contents[context.start()..offset], range);
+
+    match context {
+        Context::Member {
+            operator,
+            target_end,
+            ..
+        } => members(&mut items, document, index, offset, operator, target_end),
+        Context::Name {
+            name,
+            keyword,
+            start,
+        } => names(
+            &mut items,
+            index,
+            &Scope::at(&document.result().ast, start),
+            name,
+            keyword,
+        ),
+    }
+
+    items.items
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Arrow,
+    NullsafeArrow,
+    DoubleColon,
+}
+
+/// The keyword in front of a name, which changes what the name can refer to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Keyword {
+    Other,
+    /// Only classes can follow `new`, `extends`, `implements` and `instanceof`.
+    Class,
+    /// Names in `use` statements are always fully qualified.
+    Use,
+}
+
+/// What's being typed at the cursor, worked out from the text in front of it. The document is
+/// usually too broken at this point for the AST to be much help.
+#[derive(Debug)]
+enum Context<'s> {
+    Member {
+        operator: Operator,
+        /// Where the expression that the member is being accessed on ends.
+        target_end: ByteOffset,
+        /// Where the member's name starts, including the `$` of a static property.
+        start: ByteOffset,
+    },
+    Name {
+        /// The name typed so far, which can be qualified.
+        name: &'s [u8],
+        keyword: Keyword,
+        /// Where the last segment of the name starts.
+        start: ByteOffset,
+    },
+}
+
+impl<'s> Context<'s> {
+    fn at(contents: &'s [u8], offset: ByteOffset) -> Option<Self> {
+        let word_start = skip_back(contents, offset, is_identifier_byte);
+
+        if contents.get(word_start).is_some_and(u8::is_ascii_digit) {
+            return None;
+        }
+
+        let (before, sigil) = match contents[..word_start].strip_suffix(b"$") {
+            Some(before) => (before, 1),
+            None => (&contents[..word_start], 0),
+        };
+
+        let trimmed = trim_end(before);
+        let operator = if trimmed.ends_with(b"?->") {
+            Some((Operator::NullsafeArrow, 3))
+        } else if trimmed.ends_with(b"->") {
+            Some((Operator::Arrow, 2))
+        } else if trimmed.ends_with(b"::") {
+            Some((Operator::DoubleColon, 2))
+        } else {
+            None
+        };
+
+        if let Some((operator, length)) = operator {
+            // Only static properties start with a `$`.
+            if sigil == 1 && operator != Operator::DoubleColon {
+                return None;
+            }
+
+            return Some(Context::Member {
+                operator,
+                target_end: trim_end(&trimmed[..trimmed.len() - length]).len(),
+                start: word_start - sigil,
+            });
+        }
+
+        // Variables aren't names.
+        if sigil == 1 {
+            return None;
+        }
+
+        let name_start = skip_back(contents, offset, |byte| {
+            is_identifier_byte(byte) || byte == b'\\'
+        });
+
+        if contents[..name_start].ends_with(b"$") {
+            return None;
+        }
+
+        let name = &contents[name_start..offset];
+        let keyword = match previous_word(&contents[..name_start]) {
+            b"new" | b"extends" | b"implements" | b"instanceof" => Keyword::Class,
+            b"use" => Keyword::Use,
+            // The name is being declared, so there's nothing to complete.
+            b"function" | b"fn" | b"class" | b"interface" | b"trait" | b"enum" | b"namespace"
+            | b"const" | b"as" | b"goto" => return None,
+            _ if name.is_empty() => return None,
+            _ => Keyword::Other,
+        };
+
+        Some(Context::Name {
+            name,
+            keyword,
+            start: word_start,
+        })
+    }
+
+    fn start(&self) -> ByteOffset {
+        match self {
+            Context::Member { start, .. } | Context::Name { start, .. } => *start,
+        }
+    }
+}
+
+fn is_identifier_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte >= 0x80
+}
+
+fn skip_back(contents: &[u8], offset: ByteOffset, predicate: impl Fn(u8) -> bool) -> ByteOffset {
+    contents[..offset]
+        .iter()
+        .rposition(|&byte| !predicate(byte))
+        .map_or(0, |position| position + 1)
+}
+
+fn trim_end(bytes: &[u8]) -> &[u8] {
+    &bytes[..skip_back(bytes, bytes.len(), |byte| byte.is_ascii_whitespace())]
+}
+
+/// The word in front of the given text, ignoring whitespace, e.g. the `new` in `new Foo`.
+fn previous_word(before: &[u8]) -> &[u8] {
+    let trimmed = trim_end(before);
+
+    &trimmed[skip_back(trimmed, trimmed.len(), is_identifier_byte)..]
+}
+
+/// The namespace and imports that are in effect at a given offset.
+struct Scope<'a> {
+    namespace: &'a [u8],
+    imports: Vec<&'a Use>,
+}
+
+impl<'a> Scope<'a> {
+    fn at(ast: &'a [Statement], offset: ByteOffset) -> Self {
+        let mut namespace: &[u8] = b"";
+        let mut statements = ast;
+
+        for statement in ast {
+            let StatementKind::Namespace(inner) = &statement.kind else {
+                continue;
+            };
+
+            match inner.as_ref() {
+                NamespaceStatement::Unbraced(unbraced) if unbraced.span.start <= offset => {
+                    namespace = &unbraced.name.symbol;
+                    statements = &unbraced.statements;
+                }
+                NamespaceStatement::Braced(braced) if braced.span.contains_offset(offset) => {
+                    namespace = braced.name.as_ref().map_or(b"", |name| &name.symbol);
+                    statements = &braced.body.statements;
+                }
+                _ => {}
+            }
+        }
+
+        let imports = statements
+            .iter()
+            .flat_map(|statement| match &statement.kind {
+                StatementKind::Use(statement) => statement.uses.iter().collect(),
+                StatementKind::GroupUse(statement) => statement.uses.iter().collect(),
+                _ => Vec::new(),
+            })
+            .collect();
+
+        Self {
+            namespace: strip_leading_backslash(namespace),
+            imports,
+        }
+    }
+}
+
+fn strip_leading_backslash(name: &[u8]) -> &[u8] {
+    name.strip_prefix(b"\\").unwrap_or(name)
+}
+
+/// The alias that an import is referred to by, e.g. `Bar` for `use Foo\Bar`.
+fn alias(import: &Use) -> &[u8] {
+    match &import.alias {
+        Some(alias) => &alias.symbol,
+        None => last_segment(import.name.symbol()),
+    }
+}
+
+fn last_segment(name: &[u8]) -> &[u8] {
+    name.rsplit(|&byte| byte == b'\\').next().unwrap_or(name)
+}
+
+/// The completion items found so far, filtered by what's been typed.
+struct Items<'s> {
+    prefix: &'s [u8],
+    range: Range,
+    items: Vec<CompletionItem>,
+    /// The kinds of item that have been added under each label, ignoring case.
+    seen: HashMap<String, Vec<CompletionItemKind>>,
+}
+
+impl<'s> Items<'s> {
+    fn new(prefix: &'s [u8], range: Range) -> Self {
+        Self {
+            prefix,
+            range,
+            items: Vec::new(),
+            seen: HashMap::new(),
+        }
+    }
+
+    fn push(&mut self, label: &[u8], kind: CompletionItemKind, detail: Option<String>) {
+        self.push_with_text(label, kind, detail, None);
+    }
+
+    fn push_with_text(
+        &mut self,
+        label: &[u8],
+        kind: CompletionItemKind,
+        detail: Option<String>,
+        text: Option<String>,
+    ) {
+        let matches = label.len() >= self.prefix.len()
+            && label[..self.prefix.len()].eq_ignore_ascii_case(self.prefix);
+
+        if !matches {
+            return;
+        }
+
+        let label = String::from_utf8_lossy(label).into_owned();
+
+        let kinds = self.seen.entry(label.to_ascii_lowercase()).or_default();
+
+        if kinds.contains(&kind) {
+            return;
+        }
+
+        kinds.push(kind);
+
+        self.items.push(CompletionItem {
+            text_edit: Some(CompletionTextEdit::Edit(TextEdit::new(
+                self.range,
+                text.unwrap_or_else(|| label.clone()),
+            ))),
+            filter_text: Some(label.clone()),
+            label,
+            kind: Some(kind),
+            detail,
+            ..Default::default()
+        });
+    }
+}
+
+fn members(
+    items: &mut Items,
+    document: &Document,
+    index: &Index,
+    offset: ByteOffset,
+    operator: Operator,
+    target_end: ByteOffset,
+) {
+    // A member access without a name doesn't parse, so we give it a statement to end.
+    let mut contents = document.contents().to_string();
+    contents.insert(offset, ';');
+
+    let result = Parser::parse(Lexer::new(contents.as_bytes()));
+    let ast = &result.ast;
+
+    let Some((node, ancestors)) =
+        NodeFinder::find_at_byte_offset(ast, target_end.saturating_sub(1))
+    else {
+        return;
+    };
+
+    let is_access = |node: &Node| member_target(*node).is_some_and(|t| t.span.end == target_end);
+    let Some(target) = std::iter::once(node)
+        .filter(is_access)
+        .chain(ancestors.find(is_access))
+        .find_map(member_target)
+    else {
+        return;
+    };
+
+    let map = TypeEngine::new(index).infer(ast);
+    let resolver = Resolver {
+        index,
+        map: &map,
+        ancestors: &ancestors,
+    };
+
+    let scope = enclosing_class(&ancestors, index);
+    // `parent::foo()` and friends call instance methods, not static ones.
+    let is_special = matches!(
+        &target.kind,
+        ExpressionKind::Self_(_) | ExpressionKind::Static(_) | ExpressionKind::Parent(_)
+    ) || matches!(&target.kind, ExpressionKind::Name(name) if name.is_special());
+
+    for class in resolver.classes(target) {
+        match operator {
+            Operator::Arrow | Operator::NullsafeArrow => {
+                instance_members(items, class, scope);
+            }
+            Operator::DoubleColon => static_members(items, class, scope, is_special),
+        }
+    }
+
+    if operator == Operator::DoubleColon {
+        items.push(b"class", CompletionItemKind::KEYWORD, None);
+    }
+}
+
+/// The expression that a member is being accessed on.
+fn member_target<'a>(node: Node<'a>) -> Option<&'a Expression> {
+    node.as_property_fetch_expression()
+        .map(|fetch| fetch.target.as_ref())
+        .or_else(|| {
+            node.as_nullsafe_property_fetch_expression()
+                .map(|fetch| fetch.target.as_ref())
+        })
+        .or_else(|| {
+            node.as_method_call_expression()
+                .map(|call| call.target.as_ref())
+        })
+        .or_else(|| {
+            node.as_nullsafe_method_call_expression()
+                .map(|call| call.target.as_ref())
+        })
+        .or_else(|| {
+            node.as_static_method_call_expression()
+                .map(|call| call.target.as_ref())
+        })
+        .or_else(|| {
+            node.as_static_property_fetch_expression()
+                .map(|fetch| fetch.target.as_ref())
+        })
+        .or_else(|| {
+            node.as_constant_fetch_expression()
+                .map(|fetch| fetch.target.as_ref())
+        })
+}
+
+fn instance_members(items: &mut Items, class: ReflectionClass, scope: Option<ReflectionClass>) {
+    for method in class.get_methods() {
+        if !method.is_static() && is_visible(method.get_visibility(), method.get_class(), scope) {
+            items.push(
+                method.get_name(),
+                CompletionItemKind::METHOD,
+                method.get_return_type().map(detail),
+            );
+        }
+    }
+
+    for property in class.get_properties() {
+        if !property.is_static()
+            && is_visible(property.get_visibility(), property.get_class(), scope)
+        {
+            items.push(
+                property.get_name(),
+                CompletionItemKind::PROPERTY,
+                property.get_type().map(detail),
+            );
+        }
+    }
+}
+
+fn static_members(
+    items: &mut Items,
+    class: ReflectionClass,
+    scope: Option<ReflectionClass>,
+    is_special: bool,
+) {
+    for case in class.get_cases() {
+        items.push(case.get_name(), CompletionItemKind::ENUM_MEMBER, None);
+    }
+
+    for constant in class.get_constants() {
+        let visibility = if constant.is_private() {
+            Visibility::Private
+        } else if constant.is_protected() {
+            Visibility::Protected
+        } else {
+            Visibility::Public
+        };
+
+        if is_visible(visibility, constant.get_class(), scope) {
+            items.push(
+                constant.get_name(),
+                CompletionItemKind::CONSTANT,
+                Some(detail(constant.get_value_type())),
+            );
+        }
+    }
+
+    for method in class.get_methods() {
+        if (method.is_static() || is_special)
+            && is_visible(method.get_visibility(), method.get_class(), scope)
+        {
+            items.push(
+                method.get_name(),
+                CompletionItemKind::METHOD,
+                method.get_return_type().map(detail),
+            );
+        }
+    }
+
+    for property in class.get_static_properties() {
+        if is_visible(property.get_visibility(), property.get_class(), scope) {
+            let mut label = b"$".to_vec();
+            label.extend_from_slice(property.get_name());
+
+            items.push(
+                &label,
+                CompletionItemKind::PROPERTY,
+                property.get_type().map(detail),
+            );
+        }
+    }
+}
+
+/// Whether a member declared on `owner` can be accessed from inside of `scope`.
+fn is_visible(
+    visibility: Visibility,
+    owner: ReflectionClass,
+    scope: Option<ReflectionClass>,
+) -> bool {
+    let Some(scope) = scope else {
+        return visibility == Visibility::Public;
+    };
+
+    let is_same = scope.name().eq_ignore_ascii_case(owner.name());
+
+    match visibility {
+        Visibility::Public => true,
+        Visibility::Protected => {
+            is_same || scope.is_subclass_of(owner.name()) || owner.is_subclass_of(scope.name())
+        }
+        Visibility::Private => is_same,
+    }
+}
+
+fn detail(ty: ReflectionType) -> String {
+    ty.to_type().to_string()
+}
+
+fn names(items: &mut Items, index: &Index, scope: &Scope, name: &[u8], keyword: Keyword) {
+    // Everything up to the last segment, which is what the items are filtered by.
+    let qualifier = &name[..name
+        .iter()
+        .rposition(|&byte| byte == b'\\')
+        .map_or(0, |position| position + 1)];
+
+    let only_classes = keyword == Keyword::Class;
+
+    if qualifier.starts_with(b"\\") || keyword == Keyword::Use {
+        return children(
+            items,
+            index,
+            strip_leading_backslash(qualifier),
+            only_classes,
+        );
+    }
+
+    if !qualifier.is_empty() {
+        // The first segment of a qualified name can refer to an imported namespace.
+        let (first, rest) = qualifier.split_at(qualifier.iter().position(|&b| b == b'\\').unwrap());
+        let import = scope.imports.iter().find(|import| {
+            import.kind == UseKind::Normal && alias(import).eq_ignore_ascii_case(first)
+        });
+
+        let namespace = match import {
+            Some(import) => [strip_leading_backslash(import.name.symbol()), rest].concat(),
+            None => qualify(scope.namespace, qualifier),
+        };
+
+        return children(items, index, &namespace, only_classes);
+    }
+
+    for import in &scope.imports {
+        let name = strip_leading_backslash(import.name.symbol());
+        let kind = match import.kind {
+            UseKind::Function if !only_classes => CompletionItemKind::FUNCTION,
+            UseKind::Const if !only_classes => CompletionItemKind::CONSTANT,
+            UseKind::Normal => match index.get_class(name) {
+                Some(class) => class_kind(class),
+                None => CompletionItemKind::MODULE,
+            },
+            _ => continue,
+        };
+
+        items.push(
+            alias(import),
+            kind,
+            Some(String::from_utf8_lossy(name).into()),
+        );
+    }
+
+    let namespace = qualify(scope.namespace, b"");
+    children(items, index, &namespace, only_classes);
+
+    if scope.namespace.is_empty() {
+        return;
+    }
+
+    // Classes in the global namespace need a leading backslash from inside of another namespace.
+    for class in index.get_classes() {
+        if !class.name().contains(b"\\") {
+            items.push_with_text(
+                class.name(),
+                class_kind(class),
+                None,
+                Some(format!("\\{}", class.name())),
+            );
+        }
+    }
+
+    if only_classes {
+        return;
+    }
+
+    // Functions and constants fall back to the global namespace on their own.
+    for function in index.get_functions() {
+        if !function.in_namespace() {
+            items.push(
+                function.get_name(),
+                CompletionItemKind::FUNCTION,
+                function.get_return_type().map(detail),
+            );
+        }
+    }
+
+    for constant in index.get_constants() {
+        if !constant.in_namespace() {
+            items.push(
+                constant.get_name(),
+                CompletionItemKind::CONSTANT,
+                Some(detail(constant.get_value_type())),
+            );
+        }
+    }
+}
+
+/// Join a namespace and a relative name, e.g. `App` and `Models\` become `App\Models\`.
+fn qualify(namespace: &[u8], name: &[u8]) -> Vec<u8> {
+    if namespace.is_empty() {
+        return name.to_vec();
+    }
+
+    [namespace, b"\\", name].concat()
+}
+
+/// The classes, functions, constants and namespaces directly inside of a namespace, which is
+/// either empty or ends with a backslash.
+fn children(items: &mut Items, index: &Index, namespace: &[u8], only_classes: bool) {
+    for class in index.get_classes() {
+        child(items, namespace, class.name(), class_kind(class), None);
+    }
+
+    if only_classes {
+        return;
+    }
+
+    for function in index.get_functions() {
+        child(
+            items,
+            namespace,
+            function.get_name(),
+            CompletionItemKind::FUNCTION,
+            function.get_return_type().map(detail),
+        );
+    }
+
+    for constant in index.get_constants() {
+        child(
+            items,
+            namespace,
+            constant.get_name(),
+            CompletionItemKind::CONSTANT,
+            Some(detail(constant.get_value_type())),
+        );
+    }
+}
+
+fn child(
+    items: &mut Items,
+    namespace: &[u8],
+    name: &[u8],
+    kind: CompletionItemKind,
+    detail: Option<String>,
+) {
+    let name = strip_leading_backslash(name);
+
+    if name.len() <= namespace.len() || !name[..namespace.len()].eq_ignore_ascii_case(namespace) {
+        return;
+    }
+
+    let rest = &name[namespace.len()..];
+
+    match rest.iter().position(|&byte| byte == b'\\') {
+        Some(position) => items.push(&rest[..position], CompletionItemKind::MODULE, None),
+        None => items.push(rest, kind, detail),
+    }
+}
+
+fn class_kind(class: ReflectionClass) -> CompletionItemKind {
+    if class.is_interface() {
+        CompletionItemKind::INTERFACE
+    } else if class.is_enum() {
+        CompletionItemKind::ENUM
+    } else {
+        CompletionItemKind::CLASS
+    }
+}
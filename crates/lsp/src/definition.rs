@@ -0,0 +1,9 @@
+use pxp_index::{Index, Location};
+use pxp_span::ByteOffset;
+
+use crate::{document::Document, symbol::Symbol};
+
+/// Find where the function, class, constant or class member at the given offset is declared.
+pub fn definition(document: &Document, index: &Index, offset: ByteOffset) -> Option<Location> {
+    Symbol::at(&document.result().ast, index, offset)?.location(index)
+}
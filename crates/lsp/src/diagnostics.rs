@@ -0,0 +1,33 @@
+use lsp_types::{DiagnosticSeverity, NumberOrString};
+use pxp_diagnostics::{DiagnosticKind, Severity};
+
+use crate::document::Document;
+
+/// The diagnostics for a document, converted into the protocol's representation.
+pub fn diagnostics(document: &Document) -> Vec<lsp_types::Diagnostic> {
+    document
+        .result()
+        .diagnostics
+        .iter()
+        .map(|diagnostic| lsp_types::Diagnostic {
+            range: document.lines().range(document.contents(), diagnostic.span),
+            severity: Some(severity(diagnostic.severity)),
+            code: Some(NumberOrString::String(diagnostic.kind.get_code())),
+            source: Some(String::from("pxp")),
+            message: match diagnostic.kind.get_help() {
+                Some(help) => format!("{}\n{}", diagnostic.kind.get_message(), help),
+                None => diagnostic.kind.get_message(),
+            },
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn severity(severity: Severity) -> DiagnosticSeverity {
+    match severity {
+        Severity::Error => DiagnosticSeverity::ERROR,
+        Severity::Warning => DiagnosticSeverity::WARNING,
+        Severity::Information => DiagnosticSeverity::INFORMATION,
+        Severity::Hint => DiagnosticSeverity::HINT,
+    }
+}
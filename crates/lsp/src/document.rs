@@ -0,0 +1,67 @@
+use lsp_types::TextDocumentContentChangeEvent;
+use pxp_lexer::Lexer;
+use pxp_parser::{ParseResult, Parser};
+
+use crate::line_index::{Encoding, LineIndex};
+
+/// A file that's open in the editor. The editor owns the contents, which can differ from what's
+/// on disk.
+pub struct Document {
+    contents: String,
+    version: i32,
+    lines: LineIndex,
+    result: ParseResult,
+}
+
+impl Document {
+    pub fn new(contents: String, version: i32, encoding: Encoding) -> Self {
+        let lines = LineIndex::new(&contents, encoding);
+        let result = Parser::parse(Lexer::new(contents.as_bytes()));
+
+        Self {
+            contents,
+            version,
+            lines,
+            result,
+        }
+    }
+
+    pub fn contents(&self) -> &str {
+        &self.contents
+    }
+
+    pub fn version(&self) -> i32 {
+        self.version
+    }
+
+    pub fn lines(&self) -> &LineIndex {
+        &self.lines
+    }
+
+    pub fn result(&self) -> &ParseResult {
+        &self.result
+    }
+
+    /// Apply a batch of edits from the editor and reparse the document.
+    ///
+    /// Each edit's range refers to the contents after the previous edits have been applied, so the
+    /// line index has to be rebuilt as we go. The document is only reparsed once at the end.
+    pub fn apply_changes(&mut self, changes: Vec<TextDocumentContentChangeEvent>, version: i32) {
+        for change in changes {
+            match change.range {
+                Some(range) => {
+                    let start = self.lines.offset(&self.contents, range.start);
+                    let end = self.lines.offset(&self.contents, range.end).max(start);
+
+                    self.contents.replace_range(start..end, &change.text);
+                }
+                None => self.contents = change.text,
+            }
+
+            self.lines = LineIndex::new(&self.contents, self.lines.encoding());
+        }
+
+        self.version = version;
+        self.result = Parser::parse(Lexer::new(self.contents.as_bytes()));
+    }
+}
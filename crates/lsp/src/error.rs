@@ -0,0 +1,49 @@
+use std::fmt::Display;
+
+use lsp_server::ProtocolError;
+
+#[derive(Debug)]
+pub enum LspError {
+    /// The client didn't follow the protocol, e.g. it sent a request before initializing the server.
+    Protocol(ProtocolError),
+    /// A message couldn't be serialized or deserialized.
+    Json(serde_json::Error),
+    /// The connection to the client was closed while the server was still running.
+    Disconnected,
+    /// Reading from or writing to the client failed.
+    Io(std::io::Error),
+}
+
+impl Display for LspError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Protocol(error) => write!(f, "Language server protocol error: {}", error),
+            Self::Json(error) => write!(f, "Invalid language server message: {}", error),
+            Self::Disconnected => write!(f, "The connection to the client was closed."),
+            Self::Io(error) => write!(f, "Failed to communicate with the client: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for LspError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Protocol(error) => Some(error),
+            Self::Json(error) => Some(error),
+            Self::Io(error) => Some(error),
+            Self::Disconnected => None,
+        }
+    }
+}
+
+impl From<ProtocolError> for LspError {
+    fn from(error: ProtocolError) -> Self {
+        Self::Protocol(error)
+    }
+}
+
+impl From<serde_json::Error> for LspError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
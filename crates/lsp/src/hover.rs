@@ -0,0 +1,30 @@
+use lsp_types::{Hover, HoverContents, MarkupContent, MarkupKind};
+use pxp_index::Index;
+use pxp_inference::TypeEngine;
+use pxp_node_finder::NodeFinder;
+use pxp_span::ByteOffset;
+
+use crate::document::Document;
+
+/// Show the inferred type of the innermost expression at the given offset.
+pub fn hover(document: &Document, index: &Index, offset: ByteOffset) -> Option<Hover> {
+    let ast = &document.result().ast;
+    let (node, ancestors) = NodeFinder::find_at_byte_offset(ast, offset)?;
+
+    let expression = match node.as_expression() {
+        Some(expression) => expression,
+        None => ancestors
+            .find(|node| node.is_expression())
+            .and_then(|node| node.as_expression())?,
+    };
+
+    let map = TypeEngine::new(index).infer(ast);
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: format!("```php\n{}\n```", map.resolve(expression.id)),
+        }),
+        range: Some(document.lines().range(document.contents(), expression.span)),
+    })
+}
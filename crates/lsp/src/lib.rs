@@ -0,0 +1,26 @@
+mod completion;
+mod definition;
+mod diagnostics;
+mod document;
+mod error;
+mod hover;
+mod line_index;
+mod references;
+mod resolver;
+mod server;
+mod symbol;
+mod workspace;
+
+use lsp_server::Connection;
+
+pub use error::LspError;
+pub use server::Server;
+
+/// Run the language server over stdin and stdout until the client shuts it down.
+pub fn run_stdio() -> Result<(), LspError> {
+    let (connection, io_threads) = Connection::stdio();
+
+    Server::run(connection)?;
+
+    io_threads.join().map_err(LspError::Io)
+}
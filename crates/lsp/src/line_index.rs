@@ -0,0 +1,110 @@
+use lsp_types::{Position, PositionEncodingKind, Range};
+use pxp_span::{ByteOffset, Span};
+
+/// How the client counts the characters in a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    /// Columns are byte offsets.
+    Utf8,
+    /// Columns are UTF-16 code units. Every client has to support this, so it's the default.
+    #[default]
+    Utf16,
+}
+
+impl Encoding {
+    /// Pick the encoding to use, preferring UTF-8 if the client supports it since that's what
+    /// spans already use.
+    pub fn negotiate(supported: Option<&[PositionEncodingKind]>) -> Self {
+        match supported {
+            Some(supported) if supported.contains(&PositionEncodingKind::UTF8) => Self::Utf8,
+            _ => Self::Utf16,
+        }
+    }
+
+    pub fn kind(&self) -> PositionEncodingKind {
+        match self {
+            Self::Utf8 => PositionEncodingKind::UTF8,
+            Self::Utf16 => PositionEncodingKind::UTF16,
+        }
+    }
+}
+
+/// Converts between byte offsets and the line and column positions used by the protocol.
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    /// The byte offset that each line starts at.
+    lines: Vec<ByteOffset>,
+    encoding: Encoding,
+}
+
+impl LineIndex {
+    pub fn new(contents: &str, encoding: Encoding) -> Self {
+        let lines = std::iter::once(0)
+            .chain(contents.match_indices('\n').map(|(offset, _)| offset + 1))
+            .collect();
+
+        Self { lines, encoding }
+    }
+
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    pub fn position(&self, contents: &str, offset: ByteOffset) -> Position {
+        let offset = floor_char_boundary(contents, offset.min(contents.len()));
+        let line = self.lines.partition_point(|start| *start <= offset) - 1;
+        let before = &contents[self.lines[line]..offset];
+
+        let character = match self.encoding {
+            Encoding::Utf8 => before.len(),
+            Encoding::Utf16 => before.encode_utf16().count(),
+        };
+
+        Position::new(line as u32, character as u32)
+    }
+
+    /// Convert a position into a byte offset. Positions past the end of a line are clamped to it.
+    pub fn offset(&self, contents: &str, position: Position) -> ByteOffset {
+        let Some(start) = self.lines.get(position.line as usize).copied() else {
+            return contents.len();
+        };
+
+        let end = self
+            .lines
+            .get(position.line as usize + 1)
+            .map_or(contents.len(), |next| next - 1);
+        let line = contents[start..end].trim_end_matches('\r');
+        let character = position.character as usize;
+
+        let column = match self.encoding {
+            Encoding::Utf8 => floor_char_boundary(line, character.min(line.len())),
+            Encoding::Utf16 => {
+                let mut units = 0;
+
+                line.char_indices()
+                    .find(|(_, c)| {
+                        units += c.len_utf16();
+                        units > character
+                    })
+                    .map_or(line.len(), |(index, _)| index)
+            }
+        };
+
+        start + column
+    }
+
+    pub fn range(&self, contents: &str, span: Span) -> Range {
+        Range::new(
+            self.position(contents, span.start),
+            self.position(contents, span.end),
+        )
+    }
+}
+
+fn floor_char_boundary(contents: &str, mut offset: ByteOffset) -> ByteOffset {
+    while !contents.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    offset
+}
@@ -0,0 +1,338 @@
+use std::collections::HashSet;
+
+use pxp_ast::{
+    visitor::{Ancestors, NodeVisitor, NodeVisitorEscapeHatch},
+    CommentGroup, CommentKind, DataType, DocBlock, DocBlockNode, DocBlockTag, Node, NodeId,
+    ResolvedName, Statement,
+};
+use pxp_index::Index;
+use pxp_span::Span;
+use pxp_type::Type;
+
+use crate::symbol::{is_declaration, Lookup, Symbol};
+
+/// A place where a symbol is declared or referred to.
+#[derive(Debug, Clone, Copy)]
+pub struct Reference {
+    /// The name as it's written, without the `$` in front of properties. Class, function and constant
+    /// names can be qualified or an alias.
+    pub span: Span,
+    pub is_declaration: bool,
+}
+
+/// Find every reference to the symbol in a file, including in type hints and docblocks.
+pub fn references(
+    ast: &[Statement],
+    contents: &[u8],
+    index: &Index,
+    symbol: &Symbol,
+) -> Vec<Reference> {
+    let mut finder = ReferenceFinder {
+        lookup: Lookup::new(ast, index),
+        contents,
+        symbol,
+        docblocks: HashSet::new(),
+        references: Vec::new(),
+    };
+
+    finder.traverse(ast);
+    finder.references
+}
+
+/// Check if a file could refer to the symbol at all, so that most files don't need to be parsed.
+pub fn could_refer_to(contents: &[u8], symbol: &Symbol) -> bool {
+    let name = symbol.short_name();
+
+    !name.is_empty()
+        && contents
+            .windows(name.len())
+            .any(|window| window.eq_ignore_ascii_case(name))
+}
+
+/// The edits that rename every reference in a file, as spans to replace with the new name.
+///
+/// Only the last segment of a qualified name is replaced. Names that are written using an alias are
+/// left alone, since the `use` statement that they refer to is renamed instead.
+pub fn rename(references: &[Reference], contents: &[u8], symbol: &Symbol) -> Vec<Span> {
+    references
+        .iter()
+        .filter_map(|reference| match symbol {
+            Symbol::Class(_) | Symbol::Function(_) | Symbol::Constant(_) => {
+                let written = &contents[reference.span.start..reference.span.end];
+                let start = written
+                    .iter()
+                    .rposition(|b| *b == b'\\')
+                    .map_or(0, |position| position + 1);
+
+                symbol
+                    .could_be(&written[start..])
+                    .then(|| Span::new(reference.span.start + start, reference.span.end))
+            }
+            _ => Some(reference.span),
+        })
+        .collect()
+}
+
+/// Check if a new name for a symbol is a valid PHP identifier.
+pub fn is_valid_name(name: &str) -> bool {
+    let mut bytes = name.bytes();
+
+    bytes
+        .next()
+        .is_some_and(|b| b == b'_' || b.is_ascii_alphabetic() || b >= 0x80)
+        && bytes.all(|b| b == b'_' || b.is_ascii_alphanumeric() || b >= 0x80)
+}
+
+struct ReferenceFinder<'a, 'b> {
+    lookup: Lookup<'a>,
+    contents: &'b [u8],
+    symbol: &'b Symbol,
+    docblocks: HashSet<NodeId>,
+    references: Vec<Reference>,
+}
+
+impl<'a, 'b> ReferenceFinder<'a, 'b> {
+    fn comments(&mut self, comments: &CommentGroup) {
+        for comment in comments.comments.iter() {
+            let CommentKind::DocBlock(docblock) = &comment.kind else {
+                continue;
+            };
+
+            // The same docblock can be attached to more than one node.
+            if !self.docblocks.insert(comment.id) {
+                continue;
+            }
+
+            for data_type in data_types(&docblock.doc) {
+                self.data_type(data_type);
+            }
+        }
+    }
+
+    /// Types aren't made up of nodes, so the names inside of them are found in the source instead.
+    fn data_type(&mut self, data_type: &DataType) {
+        let Symbol::Class(class) = self.symbol else {
+            return;
+        };
+
+        let mut names = Vec::new();
+        named_types(&data_type.kind, &mut names);
+
+        let mut written = names
+            .into_iter()
+            .filter(|name| {
+                name.resolved
+                    .as_bytestr()
+                    .strip_prefix(b'\\')
+                    .eq_ignore_ascii_case(class.as_bytestr())
+            })
+            .map(|name| &name.original[..])
+            .collect::<Vec<&[u8]>>();
+
+        written.sort();
+        written.dedup();
+
+        let span = data_type.span;
+        let source = &self.contents[span.start..span.end];
+
+        for name in written {
+            for start in occurrences(source, name) {
+                self.references.push(Reference {
+                    span: Span::new(span.start + start, span.start + start + name.len()),
+                    is_declaration: false,
+                });
+            }
+        }
+    }
+}
+
+impl<'a, 'b> NodeVisitor<'a> for ReferenceFinder<'a, 'b> {
+    fn enter(&mut self, node: Node<'a>, ancestors: &mut Ancestors<'a>) -> NodeVisitorEscapeHatch {
+        if let Some(comments) = comments(node) {
+            self.comments(comments);
+        }
+
+        if let Some(data_type) = node.as_data_type() {
+            self.data_type(data_type);
+
+            return NodeVisitorEscapeHatch::Continue;
+        }
+
+        let (text, span): (&[u8], Span) = if let Some(name) = node.as_name() {
+            let name = name.symbol().as_bytestr();
+            let start = name
+                .iter()
+                .rposition(|b| *b == b'\\')
+                .map_or(0, |position| position + 1);
+
+            (&name[start..], node.span)
+        } else if let Some(identifier) = node.as_simple_identifier() {
+            (identifier.symbol.as_ref(), node.span)
+        } else if let Some(variable) = node.as_simple_variable() {
+            (
+                variable.stripped.as_ref(),
+                Span::new(node.span.start + 1, node.span.end),
+            )
+        } else {
+            return NodeVisitorEscapeHatch::Continue;
+        };
+
+        if !self.symbol.could_be(text) {
+            return NodeVisitorEscapeHatch::Continue;
+        }
+
+        if self
+            .lookup
+            .symbol(node, ancestors)
+            .is_some_and(|symbol| symbol.is(self.symbol))
+        {
+            self.references.push(Reference {
+                span,
+                is_declaration: is_declaration(ancestors),
+            });
+        }
+
+        NodeVisitorEscapeHatch::Continue
+    }
+}
+
+/// The comments attached to a node, which can contain docblocks.
+fn comments<'a>(node: Node<'a>) -> Option<&'a CommentGroup> {
+    node.as_statement()
+        .map(|statement| &statement.comments)
+        .or_else(|| node.as_expression().map(|expression| &expression.comments))
+        .or_else(|| node.as_method().map(|method| &method.comments))
+        .or_else(|| {
+            node.as_classish_constant()
+                .map(|constant| &constant.comments)
+        })
+        .or_else(|| {
+            node.as_constant_statement()
+                .map(|constant| &constant.comments)
+        })
+        .or_else(|| {
+            node.as_function_statement()
+                .map(|function| &function.comments)
+        })
+        .or_else(|| {
+            node.as_function_parameter()
+                .map(|parameter| &parameter.comments)
+        })
+}
+
+fn data_types(docblock: &DocBlock) -> impl Iterator<Item = &DataType> {
+    docblock.nodes.iter().filter_map(|node| {
+        let DocBlockNode::Tag(tag) = node else {
+            return None;
+        };
+
+        match &tag.tag {
+            DocBlockTag::ParamClosureThis(tag) => Some(&tag.r#type),
+            DocBlockTag::Param(tag) => tag.data_type.as_ref(),
+            DocBlockTag::Return(tag) => tag.data_type.as_ref(),
+            DocBlockTag::Throws(tag) => tag.data_type.as_ref(),
+            DocBlockTag::Var(tag) => tag.data_type.as_ref(),
+            DocBlockTag::Property(tag) => tag.data_type.as_ref(),
+            DocBlockTag::Method(tag) => tag.return_type.as_ref(),
+            DocBlockTag::Extends(tag) => Some(&tag.data_type),
+            DocBlockTag::Implements(tag) => Some(&tag.data_type),
+            DocBlockTag::Uses(tag) => Some(&tag.data_type),
+            _ => None,
+        }
+    })
+}
+
+fn named_types<'t>(ty: &'t Type<ResolvedName>, names: &mut Vec<&'t ResolvedName>) {
+    match ty {
+        Type::Named(name) => names.push(name),
+        Type::Generic(base, arguments) => {
+            named_types(base, names);
+
+            for argument in arguments {
+                named_types(&argument.r#type, names);
+            }
+        }
+        Type::Nullable(inner) => named_types(inner, names),
+        Type::Union(types) | Type::Intersection(types) => {
+            for ty in types {
+                named_types(ty, names);
+            }
+        }
+        Type::CallableSignature(base, parameters, return_type) => {
+            named_types(base, names);
+
+            for parameter in parameters {
+                named_types(&parameter.r#type, names);
+            }
+
+            named_types(return_type, names);
+        }
+        Type::TypedArray(key, value) => {
+            named_types(key, names);
+            named_types(value, names);
+        }
+        Type::Shaped {
+            base,
+            items,
+            unsealed_type,
+            ..
+        } => {
+            named_types(base, names);
+
+            for item in items {
+                named_types(&item.value_type, names);
+            }
+
+            if let Some(unsealed) = unsealed_type {
+                if let Some(key) = &unsealed.key_type {
+                    named_types(key, names);
+                }
+
+                named_types(&unsealed.value_type, names);
+            }
+        }
+        Type::ConditionalForParameter {
+            target,
+            then,
+            otherwise,
+            ..
+        } => {
+            named_types(target, names);
+            named_types(then, names);
+            named_types(otherwise, names);
+        }
+        Type::Conditional {
+            subject,
+            target,
+            then,
+            otherwise,
+            ..
+        } => {
+            named_types(subject, names);
+            named_types(target, names);
+            named_types(then, names);
+            named_types(otherwise, names);
+        }
+        _ => {}
+    }
+}
+
+/// The offsets where a name appears in the source on its own, rather than as part of a longer name.
+fn occurrences<'s>(source: &'s [u8], name: &'s [u8]) -> impl Iterator<Item = usize> + 's {
+    let is_name = |b: u8| b == b'_' || b == b'\\' || b.is_ascii_alphanumeric() || b >= 0x80;
+
+    let last = source
+        .len()
+        .checked_sub(name.len())
+        .filter(|_| !name.is_empty());
+
+    last.into_iter()
+        .flat_map(|last| 0..=last)
+        .filter(move |&start| {
+            let end = start + name.len();
+
+            source[start..end].eq_ignore_ascii_case(name)
+                && (start == 0 || !is_name(source[start - 1]))
+                && source.get(end).map_or(true, |b| !is_name(*b))
+        })
+}
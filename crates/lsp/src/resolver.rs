@@ -0,0 +1,107 @@
+use pxp_ast::{
+    visitor::Ancestors, Expression, ExpressionKind, Name, ResolvedName, SpecialNameKind,
+};
+use pxp_bytestring::ByteString;
+use pxp_index::{Index, ReflectionClass};
+use pxp_inference::TypeMap;
+use pxp_type::Type;
+
+/// The class, interface, trait or enum that the node with the given ancestors is declared inside of.
+pub fn enclosing_class<'a>(ancestors: &Ancestors, index: &'a Index) -> Option<ReflectionClass<'a>> {
+    let classish = ancestors.find(|node| {
+        node.is_class_statement()
+            || node.is_interface_statement()
+            || node.is_trait_statement()
+            || node.is_unit_enum_statement()
+            || node.is_backed_enum_statement()
+    })?;
+
+    let name = classish
+        .as_class_statement()
+        .map(|class| &class.name)
+        .or_else(|| classish.as_interface_statement().map(|class| &class.name))
+        .or_else(|| classish.as_trait_statement().map(|class| &class.name))
+        .or_else(|| classish.as_unit_enum_statement().map(|class| &class.name))
+        .or_else(|| classish.as_backed_enum_statement().map(|class| &class.name))?;
+
+    index.get_class(name.symbol().as_bytestr())
+}
+
+/// The class that `self`, `static` or `parent` refers to.
+pub fn class_of_special<'a>(
+    kind: SpecialNameKind,
+    ancestors: &Ancestors,
+    index: &'a Index,
+) -> Option<ReflectionClass<'a>> {
+    let class = enclosing_class(ancestors, index)?;
+
+    match kind {
+        SpecialNameKind::Parent => class.get_parent(),
+        SpecialNameKind::Self_ | SpecialNameKind::Static => Some(class),
+    }
+}
+
+pub fn class_of_name<'a>(
+    name: &Name,
+    ancestors: &Ancestors,
+    index: &'a Index,
+) -> Option<ReflectionClass<'a>> {
+    class_of_special(name.as_special()?.kind, ancestors, index)
+}
+
+/// Resolves the classes that a member is being accessed on.
+pub struct Resolver<'a, 'b> {
+    pub index: &'a Index,
+    pub map: &'b TypeMap,
+    pub ancestors: &'b Ancestors<'b>,
+}
+
+impl<'a, 'b> Resolver<'a, 'b> {
+    pub fn classes(&self, target: &Expression) -> Vec<ReflectionClass<'a>> {
+        let special = match &target.kind {
+            ExpressionKind::Name(name) if name.is_special() => {
+                return class_of_name(name, self.ancestors, self.index)
+                    .into_iter()
+                    .collect()
+            }
+            ExpressionKind::Name(name) => {
+                return self
+                    .index
+                    .get_class(name.symbol().as_bytestr())
+                    .into_iter()
+                    .collect()
+            }
+            ExpressionKind::Self_(_) => Some(SpecialNameKind::Self_),
+            ExpressionKind::Static(_) => Some(SpecialNameKind::Static),
+            ExpressionKind::Parent(_) => Some(SpecialNameKind::Parent),
+            _ => None,
+        };
+
+        if let Some(kind) = special {
+            return class_of_special(kind, self.ancestors, self.index)
+                .into_iter()
+                .collect();
+        }
+
+        let mut names = Vec::new();
+        class_names(self.map.resolve(target.id), &mut names);
+
+        names
+            .into_iter()
+            .filter_map(|name| self.index.get_class(name.as_bytestr()))
+            .collect()
+    }
+}
+
+fn class_names<'t>(ty: &'t Type<ResolvedName>, names: &mut Vec<&'t ByteString>) {
+    match ty {
+        Type::Named(name) => names.push(&name.resolved),
+        Type::Generic(base, _) | Type::Nullable(base) => class_names(base, names),
+        Type::Union(types) | Type::Intersection(types) => {
+            for ty in types {
+                class_names(ty, names);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,498 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    panic::AssertUnwindSafe,
+    path::{Path, PathBuf},
+};
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, LogMessage,
+        Notification as _, PublishDiagnostics,
+    },
+    request::{Completion, GotoDefinition, HoverRequest, References, Rename, Request as _},
+    CompletionOptions, CompletionParams, CompletionResponse, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, GotoDefinitionParams,
+    GotoDefinitionResponse, Hover, HoverParams, HoverProviderCapability, InitializeParams,
+    InitializeResult, LogMessageParams, MessageType, OneOf, PublishDiagnosticsParams,
+    ReferenceParams, RenameParams, ServerCapabilities, ServerInfo, TextDocumentPositionParams,
+    TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions, TextEdit, Url,
+    WorkspaceEdit,
+};
+use pxp_index::{is_stub, Extension, HasFileId, Index};
+use pxp_lexer::Lexer;
+use pxp_parser::Parser;
+use pxp_span::IsSpanned;
+
+use crate::{
+    completion::completion,
+    definition::definition,
+    diagnostics::diagnostics,
+    document::Document,
+    hover::hover,
+    line_index::{Encoding, LineIndex},
+    references::{could_refer_to, is_valid_name, references, rename, Reference},
+    symbol::Symbol,
+    workspace::find_files,
+    LspError,
+};
+
+/// A language server that talks to a single client over the given connection.
+pub struct Server {
+    connection: Connection,
+    documents: HashMap<Url, Document>,
+    index: Index,
+    encoding: Encoding,
+}
+
+impl Server {
+    /// Initialize the server, index the client's workspace and then handle messages until the
+    /// client shuts the server down.
+    pub fn run(connection: Connection) -> Result<(), LspError> {
+        let (id, params) = connection.initialize_start()?;
+        let params: InitializeParams = serde_json::from_value(params)?;
+
+        let encoding = Encoding::negotiate(
+            params
+                .capabilities
+                .general
+                .as_ref()
+                .and_then(|general| general.position_encodings.as_deref()),
+        );
+
+        let result = InitializeResult {
+            capabilities: capabilities(encoding),
+            server_info: Some(ServerInfo {
+                name: String::from("pxp"),
+                version: Some(String::from(env!("CARGO_PKG_VERSION"))),
+            }),
+        };
+
+        connection.initialize_finish(id, serde_json::to_value(result)?)?;
+
+        let mut server = Server {
+            connection,
+            documents: HashMap::new(),
+            index: Index::new(),
+            encoding,
+        };
+
+        server.index_workspace(&workspace_roots(&params))?;
+        server.main_loop()
+    }
+
+    fn main_loop(&mut self) -> Result<(), LspError> {
+        while let Ok(message) = self.connection.receiver.recv() {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+
+                    let response = self.handle_request(request);
+                    self.send(response.into())?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn index_workspace(&mut self, roots: &[PathBuf]) -> Result<(), LspError> {
+        self.index.load_stubs(&Extension::REQUIRED);
+
+        let files = roots
+            .iter()
+            .flat_map(|root| find_files(root))
+            .collect::<Vec<_>>();
+
+        for error in self.index.index_paths(&files) {
+            self.log(MessageType::WARNING, error.to_string())?;
+        }
+
+        self.log(MessageType::INFO, format!("Indexed {} files.", files.len()))
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        match request.method.as_str() {
+            HoverRequest::METHOD => self.respond::<HoverRequest>(request, Self::hover),
+            GotoDefinition::METHOD => self.respond::<GotoDefinition>(request, Self::definition),
+            Completion::METHOD => self.respond::<Completion>(request, Self::completion),
+            References::METHOD => self.respond::<References>(request, Self::references),
+            Rename::METHOD => self.try_respond::<Rename>(request, Self::rename),
+            method => Response::new_err(
+                request.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("Unsupported request `{}`.", method),
+            ),
+        }
+    }
+
+    fn respond<R: lsp_types::request::Request>(
+        &self,
+        request: Request,
+        handler: impl FnOnce(&Self, R::Params) -> R::Result,
+    ) -> Response {
+        self.try_respond::<R>(request, |server, params| Ok(handler(server, params)))
+    }
+
+    /// Respond to a request whose handler can fail with a message for the user.
+    ///
+    /// A bug in one of the handlers shouldn't take down the whole server, so a panic is reported
+    /// to the client as an internal error instead.
+    fn try_respond<R: lsp_types::request::Request>(
+        &self,
+        request: Request,
+        handler: impl FnOnce(&Self, R::Params) -> Result<R::Result, String>,
+    ) -> Response {
+        match serde_json::from_value::<R::Params>(request.params) {
+            Ok(params) => {
+                match std::panic::catch_unwind(AssertUnwindSafe(|| handler(self, params))) {
+                    Ok(Ok(result)) => Response::new_ok(request.id, result),
+                    Ok(Err(message)) => {
+                        Response::new_err(request.id, ErrorCode::RequestFailed as i32, message)
+                    }
+                    Err(panic) => Response::new_err(
+                        request.id,
+                        ErrorCode::InternalError as i32,
+                        format!("`{}` failed: {}", R::METHOD, panic_message(panic.as_ref())),
+                    ),
+                }
+            }
+            Err(error) => Response::new_err(
+                request.id,
+                ErrorCode::InvalidParams as i32,
+                error.to_string(),
+            ),
+        }
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Result<(), LspError> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let document = params.text_document;
+
+                self.open(
+                    document.uri,
+                    Document::new(document.text, document.version, self.encoding),
+                )
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+
+                let Some(mut document) = self.documents.remove(&uri) else {
+                    return Ok(());
+                };
+
+                document.apply_changes(params.content_changes, params.text_document.version);
+
+                self.open(uri, document)
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+
+                self.close(params.text_document.uri)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Start tracking a document, or replace the one that's already open, and report its diagnostics.
+    ///
+    /// Open documents are indexed using the editor's contents, so definitions that haven't been saved
+    /// yet can still be found.
+    fn open(&mut self, uri: Url, document: Document) -> Result<(), LspError> {
+        if let Ok(path) = uri.to_file_path() {
+            self.index.update_parsed_file(
+                &path,
+                document.contents().as_bytes(),
+                &document.result().ast,
+            );
+        }
+
+        let params = PublishDiagnosticsParams::new(
+            uri.clone(),
+            diagnostics(&document),
+            Some(document.version()),
+        );
+
+        self.documents.insert(uri, document);
+        self.notify::<PublishDiagnostics>(params)
+    }
+
+    /// Stop tracking a document, going back to whatever is on disk.
+    fn close(&mut self, uri: Url) -> Result<(), LspError> {
+        self.documents.remove(&uri);
+
+        if let Ok(path) = uri.to_file_path() {
+            match std::fs::read(&path) {
+                Ok(contents) => {
+                    self.index.update_file(&path, &contents);
+                }
+                Err(_) => {
+                    if let Some(file_id) = self.index.get_file_id(&path) {
+                        self.index.remove_file(file_id);
+                    }
+                }
+            }
+        }
+
+        self.notify::<PublishDiagnostics>(PublishDiagnosticsParams::new(uri, Vec::new(), None))
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let position = params.text_document_position_params;
+        let document = self.documents.get(&position.text_document.uri)?;
+        let offset = document
+            .lines()
+            .offset(document.contents(), position.position);
+
+        hover(document, &self.index, offset)
+    }
+
+    fn definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let position = params.text_document_position_params;
+        let document = self.documents.get(&position.text_document.uri)?;
+        let offset = document
+            .lines()
+            .offset(document.contents(), position.position);
+
+        let location = definition(document, &self.index, offset)?;
+        let path = self.index.get_file_path(location)?;
+
+        // Stubs don't exist on disk, so there's nowhere to go.
+        if is_stub(path) {
+            return None;
+        }
+
+        let uri = Url::from_file_path(path).ok()?;
+        let range = match self.documents.get(&uri) {
+            Some(document) => document.lines().range(document.contents(), location.span()),
+            None => {
+                let contents = String::from_utf8_lossy(&std::fs::read(path).ok()?).into_owned();
+
+                LineIndex::new(&contents, self.encoding).range(&contents, location.span())
+            }
+        };
+
+        Some(GotoDefinitionResponse::Scalar(lsp_types::Location::new(
+            uri, range,
+        )))
+    }
+
+    fn completion(&self, params: CompletionParams) -> Option<CompletionResponse> {
+        let position = params.text_document_position;
+        let document = self.documents.get(&position.text_document.uri)?;
+        let offset = document
+            .lines()
+            .offset(document.contents(), position.position);
+
+        Some(CompletionResponse::Array(completion(
+            document,
+            &self.index,
+            offset,
+        )))
+    }
+
+    fn references(&self, params: ReferenceParams) -> Option<Vec<lsp_types::Location>> {
+        let symbol = self.symbol(&params.text_document_position)?;
+        let include_declaration = params.context.include_declaration;
+        let mut locations = Vec::new();
+
+        for (uri, contents, references) in self.find_references(&symbol) {
+            let lines = LineIndex::new(&contents, self.encoding);
+
+            locations.extend(
+                references
+                    .into_iter()
+                    .filter(|reference| include_declaration || !reference.is_declaration)
+                    .map(|reference| {
+                        lsp_types::Location::new(
+                            uri.clone(),
+                            lines.range(&contents, reference.span),
+                        )
+                    }),
+            );
+        }
+
+        Some(locations)
+    }
+
+    fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>, String> {
+        let Some(symbol) = self.symbol(&params.text_document_position) else {
+            return Ok(None);
+        };
+
+        let new_name = match symbol {
+            Symbol::Property { .. } => params
+                .new_name
+                .strip_prefix('$')
+                .unwrap_or(&params.new_name),
+            _ => &params.new_name,
+        };
+
+        if !is_valid_name(new_name) {
+            return Err(format!("`{}` isn't a valid name.", new_name));
+        }
+
+        // Built-in symbols are declared in the stubs, which can't be changed.
+        let declared_in_stub = symbol
+            .location(&self.index)
+            .and_then(|location| self.index.get_file_path(location.file_id()))
+            .is_some_and(is_stub);
+
+        if declared_in_stub {
+            return Err(String::from("Built-in symbols can't be renamed."));
+        }
+
+        let mut changes = HashMap::new();
+
+        for (uri, contents, references) in self.find_references(&symbol) {
+            let lines = LineIndex::new(&contents, self.encoding);
+            let edits = rename(&references, contents.as_bytes(), &symbol)
+                .into_iter()
+                .map(|span| TextEdit::new(lines.range(&contents, span), new_name.to_string()))
+                .collect::<Vec<_>>();
+
+            if !edits.is_empty() {
+                changes.insert(uri, edits);
+            }
+        }
+
+        Ok(Some(WorkspaceEdit::new(changes)))
+    }
+
+    /// The symbol at a position in one of the open documents.
+    fn symbol(&self, position: &TextDocumentPositionParams) -> Option<Symbol> {
+        let document = self.documents.get(&position.text_document.uri)?;
+        let offset = document
+            .lines()
+            .offset(document.contents(), position.position);
+
+        Symbol::at(&document.result().ast, &self.index, offset)
+    }
+
+    /// Find the references to a symbol in every file in the index, apart from the stubs. Open
+    /// documents are searched using the editor's contents.
+    fn find_references(&self, symbol: &Symbol) -> Vec<(Url, String, Vec<Reference>)> {
+        let mut found = Vec::new();
+
+        for path in self.index.get_file_paths() {
+            if is_stub(path) {
+                continue;
+            }
+
+            let Ok(uri) = Url::from_file_path(path) else {
+                continue;
+            };
+
+            let contents = match self.documents.get(&uri) {
+                Some(document) => document.contents().to_string(),
+                None => match std::fs::read(path) {
+                    Ok(contents) => String::from_utf8_lossy(&contents).into_owned(),
+                    Err(_) => continue,
+                },
+            };
+
+            if !could_refer_to(contents.as_bytes(), symbol) {
+                continue;
+            }
+
+            let in_file = match self.documents.get(&uri) {
+                Some(document) => references(
+                    &document.result().ast,
+                    contents.as_bytes(),
+                    &self.index,
+                    symbol,
+                ),
+                None => {
+                    let result = Parser::parse(Lexer::new(contents.as_bytes()));
+
+                    references(&result.ast, contents.as_bytes(), &self.index, symbol)
+                }
+            };
+
+            if !in_file.is_empty() {
+                found.push((uri, contents, in_file));
+            }
+        }
+
+        found
+    }
+
+    fn log(&self, typ: MessageType, message: String) -> Result<(), LspError> {
+        self.notify::<LogMessage>(LogMessageParams { typ, message })
+    }
+
+    fn notify<N: lsp_types::notification::Notification>(
+        &self,
+        params: N::Params,
+    ) -> Result<(), LspError> {
+        self.send(Notification::new(N::METHOD.to_string(), params).into())
+    }
+
+    fn send(&self, message: Message) -> Result<(), LspError> {
+        self.connection
+            .sender
+            .send(message)
+            .map_err(|_| LspError::Disconnected)
+    }
+}
+
+fn capabilities(encoding: Encoding) -> ServerCapabilities {
+    ServerCapabilities {
+        position_encoding: Some(encoding.kind()),
+        text_document_sync: Some(TextDocumentSyncCapability::Options(
+            TextDocumentSyncOptions {
+                open_close: Some(true),
+                change: Some(TextDocumentSyncKind::INCREMENTAL),
+                ..Default::default()
+            },
+        )),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        rename_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![
+                String::from(">"),
+                String::from(":"),
+                String::from("\\"),
+            ]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// The directories that the client has open. Older clients only send a single root.
+#[allow(deprecated)]
+fn workspace_roots(params: &InitializeParams) -> Vec<PathBuf> {
+    let uris = match &params.workspace_folders {
+        Some(folders) if !folders.is_empty() => {
+            folders.iter().map(|folder| folder.uri.clone()).collect()
+        }
+        _ => params.root_uri.iter().cloned().collect::<Vec<_>>(),
+    };
+
+    uris.iter()
+        .filter_map(|uri| uri.to_file_path().ok())
+        .filter(|path| Path::is_dir(path))
+        .collect()
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    panic
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown error".to_string())
+}
@@ -0,0 +1,516 @@
+use std::cell::OnceCell;
+
+use pxp_ast::{
+    visitor::Ancestors, Expression, ExpressionKind, Identifier, Name, NameKind, Node, ResolvedName,
+    SimpleIdentifier, SimpleVariable, Statement, UseKind, Variable,
+};
+use pxp_bytestring::{ByteStr, ByteString};
+use pxp_index::{HasLocation, Index, Location, ReflectionClass};
+use pxp_inference::{TypeEngine, TypeMap};
+use pxp_node_finder::NodeFinder;
+use pxp_span::ByteOffset;
+
+use crate::resolver::{class_of_name, enclosing_class, Resolver};
+
+/// Something that's declared in one place and can be referred to from others.
+///
+/// Members belong to the class that declares them, so a method that's called through a subclass is
+/// the same symbol as the method in its parent.
+#[derive(Debug, Clone)]
+pub enum Symbol {
+    Class(ByteString),
+    Function(ByteString),
+    Constant(ByteString),
+    Method {
+        class: ByteString,
+        name: ByteString,
+    },
+    Property {
+        class: ByteString,
+        name: ByteString,
+    },
+    /// Class constants and enum cases, which share the same namespace.
+    ClassConstant {
+        class: ByteString,
+        name: ByteString,
+    },
+}
+
+impl Symbol {
+    /// The symbol that's declared or referred to at the given offset.
+    pub fn at(ast: &[Statement], index: &Index, offset: ByteOffset) -> Option<Symbol> {
+        let (node, ancestors) = NodeFinder::find_at_byte_offset(ast, offset)?;
+
+        Lookup::new(ast, index).symbol(node, &ancestors)
+    }
+
+    /// The name that the symbol is written as, without a namespace or `$` prefix.
+    pub fn short_name(&self) -> &ByteStr {
+        match self {
+            Symbol::Class(name) | Symbol::Function(name) | Symbol::Constant(name) => {
+                let name = name.as_bytestr();
+
+                match name.iter().rposition(|b| *b == b'\\') {
+                    Some(position) => name[position + 1..].into(),
+                    None => name,
+                }
+            }
+            Symbol::Method { name, .. }
+            | Symbol::Property { name, .. }
+            | Symbol::ClassConstant { name, .. } => name.as_bytestr(),
+        }
+    }
+
+    /// Check if two symbols are the same, following PHP's rules for which names are case-sensitive.
+    pub fn is(&self, other: &Symbol) -> bool {
+        match (self, other) {
+            (Symbol::Class(a), Symbol::Class(b)) | (Symbol::Function(a), Symbol::Function(b)) => {
+                a.eq_ignore_ascii_case(b)
+            }
+            (Symbol::Constant(a), Symbol::Constant(b)) => a == b,
+            (
+                Symbol::Method { class, name },
+                Symbol::Method {
+                    class: other_class,
+                    name: other_name,
+                },
+            ) => class.eq_ignore_ascii_case(other_class) && name.eq_ignore_ascii_case(other_name),
+            (
+                Symbol::Property { class, name },
+                Symbol::Property {
+                    class: other_class,
+                    name: other_name,
+                },
+            )
+            | (
+                Symbol::ClassConstant { class, name },
+                Symbol::ClassConstant {
+                    class: other_class,
+                    name: other_name,
+                },
+            ) => class.eq_ignore_ascii_case(other_class) && name == other_name,
+            _ => false,
+        }
+    }
+
+    /// Check if the given text could be a reference to this symbol, before doing the work to resolve it.
+    pub fn could_be(&self, name: &[u8]) -> bool {
+        match self {
+            Symbol::Constant(_) | Symbol::Property { .. } | Symbol::ClassConstant { .. } => {
+                name == self.short_name().as_ref()
+            }
+            _ => name.eq_ignore_ascii_case(self.short_name()),
+        }
+    }
+
+    /// Where the symbol is declared.
+    pub fn location(&self, index: &Index) -> Option<Location> {
+        match self {
+            Symbol::Class(name) => index
+                .get_class(name.as_bytestr())
+                .map(|class| class.location()),
+            Symbol::Function(name) => index
+                .get_function(name.as_bytestr())
+                .map(|function| function.location()),
+            Symbol::Constant(name) => index
+                .get_constant(name.as_bytestr())
+                .map(|constant| constant.location()),
+            Symbol::Method { class, name } => index
+                .get_class(class.as_bytestr())?
+                .get_method(name.as_bytestr())
+                .map(|method| method.location()),
+            Symbol::Property { class, name } => {
+                let class = index.get_class(class.as_bytestr())?;
+
+                class
+                    .get_property(name.as_bytestr())
+                    .or_else(|| class.get_static_property(name.as_bytestr()))
+                    .map(|property| property.location())
+            }
+            Symbol::ClassConstant { class, name } => {
+                let class = index.get_class(class.as_bytestr())?;
+
+                class
+                    .get_constant(name.as_bytestr())
+                    .map(|constant| constant.location())
+                    .or_else(|| {
+                        class
+                            .get_case(name.as_bytestr())
+                            .map(|case| case.location())
+                    })
+            }
+        }
+    }
+}
+
+/// Works out which symbols the nodes in a file declare or refer to.
+///
+/// Member accesses need the inferred types for the whole file, which are only worked out the first
+/// time that they're needed.
+pub struct Lookup<'a> {
+    ast: &'a [Statement],
+    index: &'a Index,
+    map: OnceCell<TypeMap>,
+}
+
+impl<'a> Lookup<'a> {
+    pub fn new(ast: &'a [Statement], index: &'a Index) -> Self {
+        Self {
+            ast,
+            index,
+            map: OnceCell::new(),
+        }
+    }
+
+    /// The symbol that the given node declares or refers to, if any.
+    pub fn symbol(&self, node: Node, ancestors: &Ancestors) -> Option<Symbol> {
+        if let Some(name) = node.as_name() {
+            return self.name(name, ancestors);
+        }
+
+        if let Some(identifier) = node.as_simple_identifier() {
+            return self.identifier(identifier, ancestors);
+        }
+
+        if let Some(variable) = node.as_simple_variable() {
+            return self.variable(variable, ancestors);
+        }
+
+        None
+    }
+
+    fn name(&self, name: &Name, ancestors: &Ancestors) -> Option<Symbol> {
+        let resolved = match &name.kind {
+            NameKind::Resolved(resolved) => resolved,
+            NameKind::Special(_) => {
+                return class_of_name(name, ancestors, self.index)
+                    .map(|class| Symbol::Class(class.name().into()))
+            }
+            NameKind::Unresolved(_) => return None,
+        };
+
+        match role(ancestors) {
+            Role::Class => Some(Symbol::Class(qualified(resolved.resolved.as_bytestr()))),
+            Role::Function => Some(Symbol::Function(
+                self.index
+                    .get_function(resolved.resolved.as_bytestr())
+                    .or_else(|| fallback(resolved).and_then(|name| self.index.get_function(name)))
+                    .map(|function| function.get_name().into())
+                    .unwrap_or_else(|| qualified(resolved.resolved.as_bytestr())),
+            )),
+            Role::Constant => Some(Symbol::Constant(
+                self.index
+                    .get_constant(resolved.resolved.as_bytestr())
+                    .or_else(|| fallback(resolved).and_then(|name| self.index.get_constant(name)))
+                    .map(|constant| constant.get_name().into())
+                    .unwrap_or_else(|| qualified(resolved.resolved.as_bytestr())),
+            )),
+        }
+    }
+
+    fn identifier(&self, identifier: &SimpleIdentifier, ancestors: &Ancestors) -> Option<Symbol> {
+        let name = identifier.symbol.as_bytestr();
+        let parent = ancestors.nth(0)?;
+
+        if parent.is_method() {
+            return enclosing_class(ancestors, self.index).map(|class| Symbol::Method {
+                class: class.name().into(),
+                name: name.into(),
+            });
+        }
+
+        if parent.is_classish_constant_entry()
+            || parent.is_unit_enum_case()
+            || parent.is_backed_enum_case()
+        {
+            return enclosing_class(ancestors, self.index).map(|class| Symbol::ClassConstant {
+                class: class.name().into(),
+                name: name.into(),
+            });
+        }
+
+        let access = ancestors.find(|node| is_member_access(node))?;
+        let (target, member, kind) = member(access)?;
+
+        // The identifier could be somewhere inside of the target, like a named argument.
+        if member.id != identifier.id {
+            return None;
+        }
+
+        let classes = self.classes(target, ancestors);
+
+        match kind {
+            Member::Method => classes.into_iter().find_map(|class| {
+                class.get_method(name).map(|method| Symbol::Method {
+                    class: method.get_class().name().into(),
+                    name: method.get_name().into(),
+                })
+            }),
+            Member::Property => classes.into_iter().find_map(|class| {
+                class.get_property(name).map(|property| Symbol::Property {
+                    class: property.get_class().name().into(),
+                    name: name.into(),
+                })
+            }),
+            Member::Constant => classes.into_iter().find_map(|class| {
+                class
+                    .get_constant(name)
+                    .map(|constant| constant.get_class())
+                    .or_else(|| class.get_case(name).map(|case| case.get_enum()))
+                    .map(|class| Symbol::ClassConstant {
+                        class: class.name().into(),
+                        name: name.into(),
+                    })
+            }),
+        }
+    }
+
+    fn variable(&self, variable: &SimpleVariable, ancestors: &Ancestors) -> Option<Symbol> {
+        let name = variable.stripped.as_bytestr();
+        let parent = ancestors.nth(0)?;
+
+        let is_property = parent.is_uninitialized_property_entry()
+            || parent.is_initialized_property_entry()
+            || parent
+                .as_method_parameter()
+                .is_some_and(|parameter| parameter.modifiers.is_some());
+
+        if is_property {
+            return enclosing_class(ancestors, self.index).map(|class| Symbol::Property {
+                class: class.name().into(),
+                name: name.into(),
+            });
+        }
+
+        let fetch = ancestors.nth(1)?.as_static_property_fetch_expression()?;
+
+        if !matches!(&fetch.property, Variable::SimpleVariable(property) if property.id == variable.id)
+        {
+            return None;
+        }
+
+        self.classes(&fetch.target, ancestors)
+            .into_iter()
+            .find_map(|class| {
+                class
+                    .get_static_property(name)
+                    .map(|property| Symbol::Property {
+                        class: property.get_class().name().into(),
+                        name: name.into(),
+                    })
+            })
+    }
+
+    fn classes(&self, target: &Expression, ancestors: &Ancestors) -> Vec<ReflectionClass<'a>> {
+        let map = self
+            .map
+            .get_or_init(|| TypeEngine::new(self.index).infer(self.ast));
+
+        Resolver {
+            index: self.index,
+            map,
+            ancestors,
+        }
+        .classes(target)
+    }
+}
+
+/// Check if the node with the given ancestors is where a symbol is declared, rather than a reference
+/// to it.
+pub fn is_declaration(ancestors: &Ancestors) -> bool {
+    ancestors.nth(0).is_some_and(|parent| {
+        parent.is_class_statement()
+            || parent.is_interface_statement()
+            || parent.is_trait_statement()
+            || parent.is_unit_enum_statement()
+            || parent.is_backed_enum_statement()
+            || parent.is_function_statement()
+            || parent.is_constant_entry()
+            || parent.is_method()
+            || parent.is_classish_constant_entry()
+            || parent.is_unit_enum_case()
+            || parent.is_backed_enum_case()
+            || parent.is_uninitialized_property_entry()
+            || parent.is_initialized_property_entry()
+            || parent.is_method_parameter()
+    })
+}
+
+/// What a name refers to, which depends on where it's used.
+enum Role {
+    Class,
+    Function,
+    Constant,
+}
+
+fn role(ancestors: &Ancestors) -> Role {
+    let Some(parent) = ancestors.nth(0) else {
+        return Role::Class;
+    };
+
+    if let Some(r#use) = parent.as_use() {
+        return match r#use.kind {
+            UseKind::Function => Role::Function,
+            UseKind::Const => Role::Constant,
+            UseKind::Normal => Role::Class,
+        };
+    }
+
+    if parent.is_function_statement() {
+        return Role::Function;
+    }
+
+    if parent.is_constant_entry() {
+        return Role::Constant;
+    }
+
+    // Everything else that isn't an expression is a class, e.g. `extends`, `implements` and attributes.
+    if !parent.is_expression_kind() {
+        return Role::Class;
+    }
+
+    let (Some(expression), Some(container)) = (
+        ancestors.nth(1).and_then(|node| node.as_expression()),
+        ancestors.nth(2),
+    ) else {
+        return Role::Constant;
+    };
+
+    let is_target = |target: &Expression| target.id == expression.id;
+
+    if container
+        .as_function_call_expression()
+        .is_some_and(|call| is_target(&call.target))
+        || container
+            .as_function_closure_creation_expression()
+            .is_some_and(|closure| is_target(&closure.target))
+    {
+        return Role::Function;
+    }
+
+    let is_class = container
+        .as_new_expression()
+        .is_some_and(|new| is_target(&new.target))
+        || container
+            .as_instanceof_expression()
+            .is_some_and(|instanceof| is_target(&instanceof.right))
+        || container
+            .as_static_method_call_expression()
+            .is_some_and(|call| is_target(&call.target))
+        || container
+            .as_static_variable_method_call_expression()
+            .is_some_and(|call| is_target(&call.target))
+        || container
+            .as_static_method_closure_creation_expression()
+            .is_some_and(|closure| is_target(&closure.target))
+        || container
+            .as_static_variable_method_closure_creation_expression()
+            .is_some_and(|closure| is_target(&closure.target))
+        || container
+            .as_static_property_fetch_expression()
+            .is_some_and(|fetch| is_target(&fetch.target))
+        || container
+            .as_constant_fetch_expression()
+            .is_some_and(|fetch| is_target(&fetch.target));
+
+    if is_class {
+        Role::Class
+    } else {
+        Role::Constant
+    }
+}
+
+enum Member {
+    Method,
+    Property,
+    Constant,
+}
+
+fn is_member_access(node: &Node) -> bool {
+    node.is_method_call_expression()
+        || node.is_nullsafe_method_call_expression()
+        || node.is_static_method_call_expression()
+        || node.is_property_fetch_expression()
+        || node.is_nullsafe_property_fetch_expression()
+        || node.is_constant_fetch_expression()
+}
+
+/// The target of a member access, along with the name of the member. Members with dynamic names
+/// can't be resolved.
+fn member<'a>(access: Node<'a>) -> Option<(&'a Expression, &'a SimpleIdentifier, Member)> {
+    if let Some(call) = access.as_method_call_expression() {
+        return Some((
+            &call.target,
+            expression_identifier(&call.method)?,
+            Member::Method,
+        ));
+    }
+
+    if let Some(call) = access.as_nullsafe_method_call_expression() {
+        return Some((
+            &call.target,
+            expression_identifier(&call.method)?,
+            Member::Method,
+        ));
+    }
+
+    if let Some(call) = access.as_static_method_call_expression() {
+        return Some((
+            &call.target,
+            simple_identifier(&call.method)?,
+            Member::Method,
+        ));
+    }
+
+    if let Some(fetch) = access.as_property_fetch_expression() {
+        return Some((
+            &fetch.target,
+            expression_identifier(&fetch.property)?,
+            Member::Property,
+        ));
+    }
+
+    if let Some(fetch) = access.as_nullsafe_property_fetch_expression() {
+        return Some((
+            &fetch.target,
+            expression_identifier(&fetch.property)?,
+            Member::Property,
+        ));
+    }
+
+    if let Some(fetch) = access.as_constant_fetch_expression() {
+        return Some((
+            &fetch.target,
+            simple_identifier(&fetch.constant)?,
+            Member::Constant,
+        ));
+    }
+
+    None
+}
+
+fn expression_identifier(expression: &Expression) -> Option<&SimpleIdentifier> {
+    match &expression.kind {
+        ExpressionKind::Identifier(identifier) => simple_identifier(identifier),
+        _ => None,
+    }
+}
+
+fn simple_identifier(identifier: &Identifier) -> Option<&SimpleIdentifier> {
+    match identifier {
+        Identifier::SimpleIdentifier(identifier) => Some(identifier),
+        _ => None,
+    }
+}
+
+/// Resolved names can start with a `\` if they were written that way, but the index doesn't care.
+fn qualified(name: &ByteStr) -> ByteString {
+    name.strip_prefix(b'\\').into()
+}
+
+/// Unqualified functions and constants fall back to the global namespace if they don't exist in
+/// the current one.
+fn fallback(name: &ResolvedName) -> Option<&ByteStr> {
+    (!name.original.contains(&b'\\') && name.original != name.resolved)
+        .then(|| name.original.as_bytestr())
+}
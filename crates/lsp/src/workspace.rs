@@ -0,0 +1,17 @@
+use std::path::{Path, PathBuf};
+
+use pxp_composer::{find_php_files, Project};
+
+/// Find the PHP files in a workspace.
+///
+/// Composer projects only need the files that can be autoloaded. Anything else is searched for
+/// PHP files instead.
+pub fn find_files(root: &Path) -> Vec<PathBuf> {
+    if Project::exists(root) {
+        if let Ok(project) = Project::load(root) {
+            return project.files();
+        }
+    }
+
+    find_php_files(root).unwrap_or_default()
+}
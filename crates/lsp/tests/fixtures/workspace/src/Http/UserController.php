@@ -0,0 +1,21 @@
+<?php
+
+namespace App\Http;
+
+use App\User;
+use App\User as Person;
+
+class UserController
+{
+    /**
+     * @param User $user
+     * @return Person|null
+     */
+    public function show(User $user): ?User
+    {
+        $copy = User::make();
+        $copy->name = $user->getName();
+
+        return new Person();
+    }
+}
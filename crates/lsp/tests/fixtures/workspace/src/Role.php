@@ -0,0 +1,9 @@
+<?php
+
+namespace App\Enums;
+
+enum Role: string
+{
+    case Admin = 'admin';
+    case Member = 'member';
+}
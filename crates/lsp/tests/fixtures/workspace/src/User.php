@@ -0,0 +1,27 @@
+<?php
+
+namespace App;
+
+class User
+{
+    public const ADMIN = 'admin';
+
+    public string $name;
+
+    public static int $count = 0;
+
+    public function getName(): string
+    {
+        return $this->name;
+    }
+
+    public static function make(): User
+    {
+        return new User();
+    }
+
+    private function secret(): string
+    {
+        return 'secret';
+    }
+}
@@ -0,0 +1,891 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use lsp_server::{Connection, Message, Notification, Request, RequestId};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Exit, Initialized,
+        Notification as _, PublishDiagnostics,
+    },
+    request::{
+        Completion, GotoDefinition, HoverRequest, Initialize, References, Rename, Request as _,
+        Shutdown,
+    },
+    ClientCapabilities, CompletionParams, CompletionResponse, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, GeneralClientCapabilities,
+    GotoDefinitionParams, GotoDefinitionResponse, HoverContents, HoverParams, InitializeParams,
+    InitializeResult, InitializedParams, Position, PositionEncodingKind, PublishDiagnosticsParams,
+    Range, ReferenceContext, ReferenceParams, RenameParams, TextDocumentContentChangeEvent,
+    TextDocumentIdentifier, TextDocumentItem, TextDocumentPositionParams,
+    TextDocumentSyncCapability, TextDocumentSyncKind, Url, VersionedTextDocumentIdentifier,
+    WorkspaceEdit, WorkspaceFolder,
+};
+use pxp_lsp::Server;
+
+/// The marker used to show where the cursor is in a document.
+const CURSOR: char = '§';
+
+#[test]
+fn it_negotiates_capabilities() {
+    let (client, result) = Client::start(None);
+
+    assert_eq!(
+        result.capabilities.text_document_sync,
+        Some(TextDocumentSyncCapability::Options(
+            lsp_types::TextDocumentSyncOptions {
+                open_close: Some(true),
+                change: Some(TextDocumentSyncKind::INCREMENTAL),
+                ..Default::default()
+            }
+        ))
+    );
+    assert_eq!(
+        result.capabilities.position_encoding,
+        Some(PositionEncodingKind::UTF16)
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn it_prefers_utf8_positions() {
+    let (client, result) = Client::start(Some(vec![
+        PositionEncodingKind::UTF8,
+        PositionEncodingKind::UTF16,
+    ]));
+
+    assert_eq!(
+        result.capabilities.position_encoding,
+        Some(PositionEncodingKind::UTF8)
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn it_publishes_diagnostics_when_a_document_is_opened() {
+    let (mut client, _) = Client::start(None);
+    let uri = client.open("test.php", "<?php\n\nfunction f(int $a,, $b) {}\n");
+    let diagnostics = client.diagnostics(&uri);
+
+    assert_eq!(diagnostics.version, Some(1));
+    assert_eq!(diagnostics.diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics.diagnostics[0].range,
+        Range::new(Position::new(2, 18), Position::new(2, 19))
+    );
+    assert_eq!(
+        diagnostics.diagnostics[0].code,
+        Some(lsp_types::NumberOrString::String("P002".to_string()))
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn it_reparses_documents_after_incremental_changes() {
+    let (mut client, _) = Client::start(None);
+    let uri = client.open("test.php", "<?php\n\nfunction f(int $a,, $b) {}\n");
+
+    assert_eq!(client.diagnostics(&uri).diagnostics.len(), 1);
+
+    client.change(
+        &uri,
+        2,
+        vec![TextDocumentContentChangeEvent {
+            range: Some(Range::new(Position::new(2, 18), Position::new(2, 19))),
+            range_length: None,
+            text: "".to_string(),
+        }],
+    );
+
+    let diagnostics = client.diagnostics(&uri);
+
+    assert_eq!(diagnostics.version, Some(2));
+    assert!(diagnostics.diagnostics.is_empty());
+
+    client.shutdown();
+}
+
+#[test]
+fn it_applies_changes_in_order() {
+    let (mut client, _) = Client::start(None);
+    let uri = client.open("test.php", "<?php\n\n$a = 1;\n");
+    client.diagnostics(&uri);
+
+    // The second change's range only makes sense once the first one has been applied.
+    client.change(
+        &uri,
+        2,
+        vec![
+            TextDocumentContentChangeEvent {
+                range: Some(Range::new(Position::new(2, 6), Position::new(2, 7))),
+                range_length: None,
+                text: "\n$b = 'é';".to_string(),
+            },
+            TextDocumentContentChangeEvent {
+                range: Some(Range::new(Position::new(3, 9), Position::new(3, 9))),
+                range_length: None,
+                text: "\n$b".to_string(),
+            },
+        ],
+    );
+    client.diagnostics(&uri);
+
+    assert_eq!(
+        client.hover_at(&uri, Position::new(4, 1)),
+        Some("literal-string".to_string())
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn it_clears_diagnostics_when_a_document_is_closed() {
+    let (mut client, _) = Client::start(None);
+    let uri = client.open("test.php", "<?php\n\nfunction f(int $a,, $b) {}\n");
+    client.diagnostics(&uri);
+
+    client.notify::<DidCloseTextDocument>(DidCloseTextDocumentParams {
+        text_document: TextDocumentIdentifier::new(uri.clone()),
+    });
+
+    assert!(client.diagnostics(&uri).diagnostics.is_empty());
+
+    client.shutdown();
+}
+
+#[test]
+fn it_shows_the_inferred_type_on_hover() {
+    let (mut client, _) = Client::start(None);
+    let (uri, position) = client.open_with_cursor("test.php", "<?php\n\n$a = 42;\n$§a;\n");
+
+    assert_eq!(client.hover_at(&uri, position), Some("int".to_string()));
+
+    client.shutdown();
+}
+
+#[test]
+fn it_uses_utf16_columns_by_default() {
+    let (mut client, _) = Client::start(None);
+    let (uri, position) = client.open_with_cursor("test.php", "<?php\n\n$a = 42;\n/* 😀 */ $§a;\n");
+
+    assert_eq!(position, Position::new(3, 10));
+    assert_eq!(client.hover_at(&uri, position), Some("int".to_string()));
+
+    client.shutdown();
+}
+
+#[test]
+fn it_uses_the_workspace_index_on_hover() {
+    let (mut client, _) = Client::start(None);
+    let (uri, position) = client.open_with_cursor("test.php", "<?php\n\ngreet§('Ryan');\n");
+
+    assert_eq!(client.hover_at(&uri, position), Some("string".to_string()));
+
+    client.shutdown();
+}
+
+#[test]
+fn it_goes_to_the_definition_of_a_function() {
+    let (mut client, _) = Client::start(None);
+    let (uri, position) = client.open_with_cursor("test.php", "<?php\n\ngr§eet('Ryan');\n");
+
+    assert_eq!(
+        client.definition(&uri, position),
+        Some((fixture("src/helpers.php"), 2))
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn it_goes_to_the_definition_of_a_class() {
+    let (mut client, _) = Client::start(None);
+    let (uri, position) =
+        client.open_with_cursor("test.php", "<?php\n\nuse App\\User;\n\nnew Us§er();\n");
+
+    assert_eq!(
+        client.definition(&uri, position),
+        Some((fixture("src/User.php"), 4))
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn it_goes_to_the_definition_of_a_method() {
+    let (mut client, _) = Client::start(None);
+    let (uri, position) = client.open_with_cursor(
+        "test.php",
+        "<?php\n\nuse App\\User;\n\n$user = new User();\n$user->get§Name();\n",
+    );
+
+    assert_eq!(
+        client.definition(&uri, position),
+        Some((fixture("src/User.php"), 12))
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn it_goes_to_the_definition_of_static_members() {
+    let (mut client, _) = Client::start(None);
+    let (uri, method) =
+        client.open_with_cursor("test.php", "<?php\n\nuse App\\User;\n\nUser::ma§ke();\n");
+
+    assert_eq!(
+        client.definition(&uri, method),
+        Some((fixture("src/User.php"), 17))
+    );
+
+    let (uri, constant) =
+        client.open_with_cursor("constant.php", "<?php\n\nuse App\\User;\n\nUser::AD§MIN;\n");
+
+    assert_eq!(
+        client.definition(&uri, constant),
+        Some((fixture("src/User.php"), 6))
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn it_goes_to_definitions_in_open_documents() {
+    let (mut client, _) = Client::start(None);
+    let (uri, position) = client.open_with_cursor(
+        "test.php",
+        "<?php\n\nfunction unsaved(): int {}\n\nun§saved();\n",
+    );
+
+    assert_eq!(
+        client.definition(&uri, position),
+        Some((fixture("test.php"), 2))
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn it_completes_instance_members() {
+    let (mut client, _) = Client::start(None);
+    let (uri, position) = client.open_with_cursor(
+        "test.php",
+        "<?php\n\nuse App\\User;\n\n$user = new User();\n$user->§\n",
+    );
+
+    assert_eq!(client.completions(&uri, position), vec!["getName", "name"]);
+
+    let (uri, position) = client.open_with_cursor(
+        "nullsafe.php",
+        "<?php\n\nuse App\\User;\n\n$user = new User();\n$user?->get§Name();\n",
+    );
+
+    assert_eq!(client.completions(&uri, position), vec!["getName"]);
+
+    client.shutdown();
+}
+
+#[test]
+fn it_completes_static_members() {
+    let (mut client, _) = Client::start(None);
+    let (uri, position) =
+        client.open_with_cursor("test.php", "<?php\n\nuse App\\User;\n\nUser::§\n");
+
+    assert_eq!(
+        client.completions(&uri, position),
+        vec!["$count", "ADMIN", "class", "make"]
+    );
+
+    let (uri, position) =
+        client.open_with_cursor("enum.php", "<?php\n\nuse App\\Enums\\Role;\n\nRole::Me§\n");
+
+    assert_eq!(client.completions(&uri, position), vec!["Member"]);
+
+    client.shutdown();
+}
+
+#[test]
+fn it_completes_private_members_inside_of_the_class() {
+    let (mut client, _) = Client::start(None);
+    let (uri, position) = client.open_with_cursor(
+        "src/User.php",
+        "<?php\n\nnamespace App;\n\nclass User\n{\n    private function secret(): string {}\n\n    public function test()\n    {\n        $this->§\n    }\n}\n",
+    );
+
+    assert_eq!(client.completions(&uri, position), vec!["secret", "test"]);
+
+    let (uri, position) = client.open_with_cursor("test.php", "<?php\n\n(new App\\User)->sec§\n");
+
+    assert!(client.completions(&uri, position).is_empty());
+
+    client.shutdown();
+}
+
+#[test]
+fn it_completes_names_using_imports() {
+    let (mut client, _) = Client::start(None);
+    let (uri, position) = client.open_with_cursor(
+        "test.php",
+        "<?php\n\nnamespace Test;\n\nuse App\\User as Person;\nuse App\\Enums;\n\nnew Per§\n",
+    );
+
+    assert_eq!(client.completions(&uri, position), vec!["Person"]);
+
+    let (uri, position) = client.open_with_cursor(
+        "qualified.php",
+        "<?php\n\nnamespace Test;\n\nuse App\\Enums;\n\nEnums\\§\n",
+    );
+
+    assert_eq!(client.completions(&uri, position), vec!["Role"]);
+
+    client.shutdown();
+}
+
+#[test]
+fn it_completes_fully_qualified_names() {
+    let (mut client, _) = Client::start(None);
+    let (uri, position) = client.open_with_cursor("test.php", "<?php\n\n\\App\\§\n");
+
+    assert_eq!(
+        client.completions(&uri, position),
+        vec!["Enums", "Http", "User"]
+    );
+
+    let (uri, position) = client.open_with_cursor("global.php", "<?php\n\ngre§\n");
+
+    assert_eq!(client.completions(&uri, position), vec!["greet"]);
+
+    client.shutdown();
+}
+
+#[test]
+fn it_does_not_complete_variables_or_declarations() {
+    let (mut client, _) = Client::start(None);
+    let (uri, position) = client.open_with_cursor("test.php", "<?php\n\n$gre§\n");
+
+    assert!(client.completions(&uri, position).is_empty());
+
+    let (uri, position) = client.open_with_cursor("declaration.php", "<?php\n\nfunction gre§\n");
+
+    assert!(client.completions(&uri, position).is_empty());
+
+    client.shutdown();
+}
+
+#[test]
+fn it_finds_references_to_a_class() {
+    let (mut client, _) = Client::start(None);
+    let (uri, position) = client.open_with_cursor(
+        "test.php",
+        "<?php\n\nuse App\\User;\n\n$user = new Us§er();\n",
+    );
+
+    assert_eq!(
+        client.references(&uri, position, true),
+        vec![
+            ("src/Http/UserController.php".to_string(), 4),
+            ("src/Http/UserController.php".to_string(), 5),
+            ("src/Http/UserController.php".to_string(), 10),
+            ("src/Http/UserController.php".to_string(), 11),
+            ("src/Http/UserController.php".to_string(), 13),
+            ("src/Http/UserController.php".to_string(), 13),
+            ("src/Http/UserController.php".to_string(), 15),
+            ("src/Http/UserController.php".to_string(), 18),
+            ("src/User.php".to_string(), 4),
+            ("src/User.php".to_string(), 17),
+            ("src/User.php".to_string(), 19),
+            ("test.php".to_string(), 2),
+            ("test.php".to_string(), 4),
+        ]
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn it_finds_references_to_members() {
+    let (mut client, _) = Client::start(None);
+    let (uri, position) = client.open_with_cursor(
+        "test.php",
+        "<?php\n\nuse App\\User;\n\n$user = new User();\n$user->get§Name();\n",
+    );
+
+    assert_eq!(
+        client.references(&uri, position, false),
+        vec![
+            ("src/Http/UserController.php".to_string(), 16),
+            ("test.php".to_string(), 5),
+        ]
+    );
+
+    let (uri, position) = client.open_with_cursor(
+        "test.php",
+        "<?php\n\nuse App\\User;\n\n$user = new User();\n$user->na§me;\n",
+    );
+
+    assert_eq!(
+        client.references(&uri, position, true),
+        vec![
+            ("src/Http/UserController.php".to_string(), 16),
+            ("src/User.php".to_string(), 8),
+            ("src/User.php".to_string(), 14),
+            ("test.php".to_string(), 5),
+        ]
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn it_renames_a_class_and_its_imports() {
+    let (mut client, _) = Client::start(None);
+    let (uri, position) =
+        client.open_with_cursor("test.php", "<?php\n\n$user = new \\App\\Us§er();\n");
+
+    let renamed = client.rename(&uri, position, "Account").unwrap();
+
+    assert_eq!(
+        renamed["src/Http/UserController.php"],
+        std::fs::read_to_string(fixture("src/Http/UserController.php"))
+            .unwrap()
+            .replace("use App\\User", "use App\\Account")
+            .replace("@param User", "@param Account")
+            .replace("User $user): ?User", "Account $user): ?Account")
+            .replace("User::make", "Account::make")
+    );
+    assert_eq!(
+        renamed["src/User.php"],
+        std::fs::read_to_string(fixture("src/User.php"))
+            .unwrap()
+            .replace("class User", "class Account")
+            .replace("(): User", "(): Account")
+            .replace("new User", "new Account")
+    );
+    assert_eq!(
+        renamed["test.php"],
+        "<?php\n\n$user = new \\App\\Account();\n"
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn it_renames_properties() {
+    let (mut client, _) = Client::start(None);
+    let (uri, position) =
+        client.open_with_cursor("test.php", "<?php\n\n(new App\\User())->na§me = 'Ryan';\n");
+
+    let renamed = client.rename(&uri, position, "$title").unwrap();
+
+    assert_eq!(
+        renamed["src/User.php"],
+        std::fs::read_to_string(fixture("src/User.php"))
+            .unwrap()
+            .replace("string $name", "string $title")
+            .replace("$this->name", "$this->title")
+    );
+    assert_eq!(
+        renamed["test.php"],
+        "<?php\n\n(new App\\User())->title = 'Ryan';\n"
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn it_refuses_to_rename_to_an_invalid_name_or_a_built_in() {
+    let (mut client, _) = Client::start(None);
+    let (uri, position) = client.open_with_cursor("test.php", "<?php\n\ngreet(str§len('Ryan'));\n");
+
+    assert_eq!(
+        client.rename(&uri, position, "length"),
+        Err("Built-in symbols can't be renamed.".to_string())
+    );
+
+    let (uri, position) = client.open_with_cursor("test.php", "<?php\n\ngre§et('Ryan');\n");
+
+    assert_eq!(
+        client.rename(&uri, position, "say hello"),
+        Err("`say hello` isn't a valid name.".to_string())
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn it_responds_with_an_error_to_unsupported_requests() {
+    let (mut client, _) = Client::start(None);
+    let id = client.send_request("textDocument/unsupported", serde_json::json!({}));
+    let response = client.response(id);
+
+    assert_eq!(
+        response.error.map(|error| error.code),
+        Some(lsp_server::ErrorCode::MethodNotFound as i32)
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn it_keeps_running_when_a_request_panics() {
+    let (mut client, _) = Client::start(None);
+    // Callable strings that refer to static methods aren't supported by the type engine yet.
+    let (uri, position) =
+        client.open_with_cursor("test.php", "<?php\n\n'Foo::bar'();\n$§a = 42;\n");
+
+    let id = client.send_request(
+        HoverRequest::METHOD,
+        HoverParams {
+            text_document_position_params: TextDocumentPositionParams::new(
+                TextDocumentIdentifier::new(uri.clone()),
+                position,
+            ),
+            work_done_progress_params: Default::default(),
+        },
+    );
+
+    assert_eq!(
+        client.response(id).error.map(|error| error.code),
+        Some(lsp_server::ErrorCode::InternalError as i32)
+    );
+
+    let uri = client.open("other.php", "<?php\n\n$a = 42;\n$a;\n");
+
+    assert_eq!(
+        client.hover_at(&uri, Position::new(3, 1)),
+        Some("int".to_string())
+    );
+
+    client.shutdown();
+}
+
+/// A scripted client that talks to a server running on another thread.
+struct Client {
+    connection: Connection,
+    server: JoinHandle<()>,
+    next_id: i32,
+    notifications: Vec<Notification>,
+    opened: HashMap<Url, String>,
+}
+
+impl Client {
+    /// Start a server for the fixture workspace and initialize it.
+    fn start(position_encodings: Option<Vec<PositionEncodingKind>>) -> (Self, InitializeResult) {
+        let (connection, server) = Connection::memory();
+        let server = std::thread::spawn(move || Server::run(server).unwrap());
+
+        let mut client = Self {
+            connection,
+            server,
+            next_id: 0,
+            notifications: Vec::new(),
+            opened: HashMap::new(),
+        };
+
+        let root = Url::from_file_path(fixture("")).unwrap();
+        let result = client.request::<Initialize>(InitializeParams {
+            capabilities: ClientCapabilities {
+                general: Some(GeneralClientCapabilities {
+                    position_encodings,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            workspace_folders: Some(vec![WorkspaceFolder {
+                uri: root,
+                name: "workspace".to_string(),
+            }]),
+            ..Default::default()
+        });
+
+        client.notify::<Initialized>(InitializedParams {});
+
+        (client, result)
+    }
+
+    fn open(&mut self, name: &str, text: &str) -> Url {
+        let uri = Url::from_file_path(fixture(name)).unwrap();
+
+        self.opened.insert(uri.clone(), text.to_string());
+        self.notify::<DidOpenTextDocument>(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem::new(uri.clone(), "php".to_string(), 1, text.into()),
+        });
+
+        uri
+    }
+
+    /// Open a document, returning the position of the cursor marker.
+    fn open_with_cursor(&mut self, name: &str, text: &str) -> (Url, Position) {
+        let offset = text.find(CURSOR).expect("The text must contain a cursor.");
+        let before = &text[..offset];
+        let line = before.matches('\n').count();
+        let column = before[before.rfind('\n').map_or(0, |newline| newline + 1)..]
+            .encode_utf16()
+            .count();
+
+        let uri = self.open(name, &text.replace(CURSOR, ""));
+
+        (uri, Position::new(line as u32, column as u32))
+    }
+
+    fn change(&mut self, uri: &Url, version: i32, changes: Vec<TextDocumentContentChangeEvent>) {
+        self.notify::<DidChangeTextDocument>(DidChangeTextDocumentParams {
+            text_document: VersionedTextDocumentIdentifier::new(uri.clone(), version),
+            content_changes: changes,
+        });
+    }
+
+    /// Wait for the next set of diagnostics to be published for the given document.
+    fn diagnostics(&mut self, uri: &Url) -> PublishDiagnosticsParams {
+        loop {
+            let position = self.notifications.iter().position(|notification| {
+                notification.method == PublishDiagnostics::METHOD
+                    && notification.params["uri"] == uri.as_str()
+            });
+
+            if let Some(position) = position {
+                let notification = self.notifications.remove(position);
+
+                return serde_json::from_value(notification.params).unwrap();
+            }
+
+            self.receive();
+        }
+    }
+
+    /// The labels of the completion items at the given position, in alphabetical order.
+    fn completions(&mut self, uri: &Url, position: Position) -> Vec<String> {
+        let response = self.request::<Completion>(CompletionParams {
+            text_document_position: TextDocumentPositionParams::new(
+                TextDocumentIdentifier::new(uri.clone()),
+                position,
+            ),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+            context: None,
+        });
+
+        match response {
+            Some(CompletionResponse::Array(items)) => {
+                let mut labels: Vec<String> = items.into_iter().map(|item| item.label).collect();
+                labels.sort();
+                labels
+            }
+            response => panic!("Unexpected completion response: {:?}", response),
+        }
+    }
+
+    /// The files and lines of every reference to the symbol at the given position, relative to the
+    /// workspace and in order.
+    fn references(
+        &mut self,
+        uri: &Url,
+        position: Position,
+        include_declaration: bool,
+    ) -> Vec<(String, u32)> {
+        let locations = self
+            .request::<References>(ReferenceParams {
+                text_document_position: TextDocumentPositionParams::new(
+                    TextDocumentIdentifier::new(uri.clone()),
+                    position,
+                ),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+                context: ReferenceContext {
+                    include_declaration,
+                },
+            })
+            .unwrap_or_default();
+
+        let mut references = locations
+            .into_iter()
+            .map(|location| (relative(&location.uri), location.range.start.line))
+            .collect::<Vec<_>>();
+
+        references.sort();
+        references
+    }
+
+    /// The contents of each file after applying the edits for renaming the symbol at the given
+    /// position, or the error message if it can't be renamed.
+    fn rename(
+        &mut self,
+        uri: &Url,
+        position: Position,
+        new_name: &str,
+    ) -> Result<HashMap<String, String>, String> {
+        let id = self.send_request(
+            Rename::METHOD,
+            RenameParams {
+                text_document_position: TextDocumentPositionParams::new(
+                    TextDocumentIdentifier::new(uri.clone()),
+                    position,
+                ),
+                new_name: new_name.to_string(),
+                work_done_progress_params: Default::default(),
+            },
+        );
+        let response = self.response(id);
+
+        if let Some(error) = response.error {
+            return Err(error.message);
+        }
+
+        let edit: Option<WorkspaceEdit> =
+            serde_json::from_value(response.result.unwrap_or_default()).unwrap();
+        let mut files = HashMap::new();
+
+        for (uri, mut edits) in edit.and_then(|edit| edit.changes).unwrap_or_default() {
+            // The documents opened by the tests only exist in the editor.
+            let mut contents = std::fs::read_to_string(uri.to_file_path().unwrap())
+                .unwrap_or_else(|_| self.opened[&uri].clone());
+            let lines = contents
+                .split_inclusive('\n')
+                .map(str::len)
+                .collect::<Vec<_>>();
+            let offset = |position: Position| {
+                lines[..position.line as usize].iter().sum::<usize>() + position.character as usize
+            };
+
+            // Edits don't overlap, so applying them from the end keeps the earlier offsets valid.
+            edits.sort_by_key(|edit| std::cmp::Reverse(offset(edit.range.start)));
+
+            for edit in edits {
+                contents.replace_range(
+                    offset(edit.range.start)..offset(edit.range.end),
+                    &edit.new_text,
+                );
+            }
+
+            files.insert(relative(&uri), contents);
+        }
+
+        Ok(files)
+    }
+
+    /// The type shown when hovering over the given position.
+    fn hover_at(&mut self, uri: &Url, position: Position) -> Option<String> {
+        let hover = self.request::<HoverRequest>(HoverParams {
+            text_document_position_params: TextDocumentPositionParams::new(
+                TextDocumentIdentifier::new(uri.clone()),
+                position,
+            ),
+            work_done_progress_params: Default::default(),
+        })?;
+
+        match hover.contents {
+            HoverContents::Markup(markup) => Some(
+                markup
+                    .value
+                    .trim_start_matches("```php\n")
+                    .trim_end_matches("\n```")
+                    .to_string(),
+            ),
+            contents => panic!("Unexpected hover contents: {:?}", contents),
+        }
+    }
+
+    /// The file and line that the definition at the given position is on.
+    fn definition(&mut self, uri: &Url, position: Position) -> Option<(PathBuf, u32)> {
+        let response = self.request::<GotoDefinition>(GotoDefinitionParams {
+            text_document_position_params: TextDocumentPositionParams::new(
+                TextDocumentIdentifier::new(uri.clone()),
+                position,
+            ),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })?;
+
+        match response {
+            GotoDefinitionResponse::Scalar(location) => Some((
+                location.uri.to_file_path().unwrap(),
+                location.range.start.line,
+            )),
+            response => panic!("Unexpected definition response: {:?}", response),
+        }
+    }
+
+    fn request<R: lsp_types::request::Request>(&mut self, params: R::Params) -> R::Result {
+        let id = self.send_request(R::METHOD, params);
+        let response = self.response(id);
+
+        if let Some(error) = response.error {
+            panic!("Request `{}` failed: {}", R::METHOD, error.message);
+        }
+
+        serde_json::from_value(response.result.unwrap_or_default()).unwrap()
+    }
+
+    fn send_request(&mut self, method: &str, params: impl serde::Serialize) -> RequestId {
+        self.next_id += 1;
+
+        let id = RequestId::from(self.next_id);
+        self.send(Request::new(id.clone(), method.to_string(), params).into());
+
+        id
+    }
+
+    /// Wait for the response to a request, holding on to any notifications that arrive first.
+    fn response(&mut self, id: RequestId) -> lsp_server::Response {
+        loop {
+            match self.receive() {
+                Some(response) if response.id == id => return response,
+                _ => continue,
+            }
+        }
+    }
+
+    fn receive(&mut self) -> Option<lsp_server::Response> {
+        let message = self
+            .connection
+            .receiver
+            .recv_timeout(Duration::from_secs(10))
+            .expect("The server didn't respond in time.");
+
+        match message {
+            Message::Response(response) => Some(response),
+            Message::Notification(notification) => {
+                self.notifications.push(notification);
+                None
+            }
+            Message::Request(request) => panic!("Unexpected request from server: {:?}", request),
+        }
+    }
+
+    fn notify<N: lsp_types::notification::Notification>(&self, params: N::Params) {
+        self.send(Notification::new(N::METHOD.to_string(), params).into());
+    }
+
+    fn send(&self, message: Message) {
+        self.connection.sender.send(message).unwrap();
+    }
+
+    fn shutdown(mut self) {
+        self.request::<Shutdown>(());
+        self.notify::<Exit>(());
+
+        self.server.join().unwrap();
+    }
+}
+
+/// The path of a file relative to the fixture workspace.
+fn relative(uri: &Url) -> String {
+    uri.to_file_path()
+        .unwrap()
+        .strip_prefix(fixture(""))
+        .unwrap()
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn fixture(path: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/workspace")
+        .join(path)
+}
@@ -12,6 +12,8 @@ use pxp_type::{
 
 impl<'a> Parser<'a> {
     pub fn parse_data_type(&mut self) -> DataType {
+        let start = self.current_span().start;
+
         let kind = if self.is_in_docblock() {
             self.parse_docblock_type()
         } else if self.current_kind() == TokenKind::Question {
@@ -35,13 +37,14 @@ impl<'a> Parser<'a> {
             }
         };
 
-        // FIXME: We need to create spans for types, but we don't have access to the previous token anymore.
-        let span = Span::missing();
+        let span = Span::new(start, self.previous.end.max(start));
 
         DataType::new(self.id(), kind, span)
     }
 
     pub fn parse_optional_data_type(&mut self) -> Option<DataType> {
+        let start = self.current_span().start;
+
         let kind = if self.is_in_docblock() {
             self.parse_docblock_type()
         } else if self.current_kind() == TokenKind::Question {
@@ -70,8 +73,7 @@ impl<'a> Parser<'a> {
             }
         };
 
-        // FIXME: We need to create spans for types, but we don't have access to the previous token anymore.
-        let span = Span::missing();
+        let span = Span::new(start, self.previous.end.max(start));
 
         Some(DataType::new(self.id(), kind, span))
     }
@@ -1,5 +1,6 @@
 use pxp_bytestring::ByteString;
-use pxp_diagnostics::{Diagnostic, DiagnosticKind, Severity};
+use pxp_diagnostics::{Diagnostic, DiagnosticKind, DiagnosticLabel, Severity};
+use pxp_lexer::diagnostics::LexerDiagnostic;
 use pxp_span::Span;
 
 use crate::Parser;
@@ -573,6 +574,78 @@ impl Display for ParserDiagnostic {
     }
 }
 
+/// A diagnostic produced while parsing a file, either by the lexer or by the parser itself.
+#[derive(Clone)]
+pub enum SyntaxDiagnostic {
+    Lexer(LexerDiagnostic),
+    Parser(ParserDiagnostic),
+}
+
+impl SyntaxDiagnostic {
+    pub(crate) fn from_lexer(diagnostic: Diagnostic<LexerDiagnostic>) -> Diagnostic<Self> {
+        Diagnostic::new(
+            Self::Lexer(diagnostic.kind),
+            diagnostic.severity,
+            diagnostic.span,
+        )
+    }
+
+    pub(crate) fn from_parser(diagnostic: Diagnostic<ParserDiagnostic>) -> Diagnostic<Self> {
+        Diagnostic::new(
+            Self::Parser(diagnostic.kind),
+            diagnostic.severity,
+            diagnostic.span,
+        )
+    }
+}
+
+// The wrapper is an implementation detail, so diagnostics are printed the same way as the kind they wrap.
+impl std::fmt::Debug for SyntaxDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Lexer(diagnostic) => std::fmt::Debug::fmt(diagnostic, f),
+            Self::Parser(diagnostic) => std::fmt::Debug::fmt(diagnostic, f),
+        }
+    }
+}
+
+impl DiagnosticKind for SyntaxDiagnostic {
+    fn get_code(&self) -> String {
+        match self {
+            Self::Lexer(diagnostic) => diagnostic.get_code(),
+            Self::Parser(diagnostic) => diagnostic.get_code(),
+        }
+    }
+
+    fn get_identifier(&self) -> String {
+        match self {
+            Self::Lexer(diagnostic) => diagnostic.get_identifier(),
+            Self::Parser(diagnostic) => diagnostic.get_identifier(),
+        }
+    }
+
+    fn get_message(&self) -> String {
+        match self {
+            Self::Lexer(diagnostic) => diagnostic.get_message(),
+            Self::Parser(diagnostic) => diagnostic.get_message(),
+        }
+    }
+
+    fn get_help(&self) -> Option<String> {
+        match self {
+            Self::Lexer(diagnostic) => diagnostic.get_help(),
+            Self::Parser(diagnostic) => diagnostic.get_help(),
+        }
+    }
+
+    fn get_labels(&self) -> Vec<DiagnosticLabel> {
+        match self {
+            Self::Lexer(diagnostic) => diagnostic.get_labels(),
+            Self::Parser(diagnostic) => diagnostic.get_labels(),
+        }
+    }
+}
+
 impl<'a> Parser<'a> {
     pub(crate) fn diagnostic(
         &mut self,
@@ -53,9 +53,10 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_unbraced_namespace(&mut self, start: Span, name: SimpleIdentifier) -> StatementKind {
-        let end = self.skip_semicolon();
-
-        let statements = scoped!(self, Scope::Namespace(name.symbol.clone()), {
+        let (end, statements) = scoped!(self, Scope::Namespace(name.symbol.clone()), {
+            // Any docblock before the first statement is parsed along with the semicolon, so the
+            // namespace needs to be entered first for the names inside of it to be resolved.
+            let end = self.skip_semicolon();
             let mut statements = Block::new();
 
             while !self.is_eof() && self.current_kind() != TokenKind::Namespace {
@@ -70,7 +71,7 @@ impl<'a> Parser<'a> {
                 statements.push(self.parse_top_level_statement());
             }
 
-            statements
+            (end, statements)
         });
 
         StatementKind::Namespace(Box::new(NamespaceStatement::Unbraced(UnbracedNamespace {
@@ -11,12 +11,13 @@ use pxp_lexer::Lexer;
 use pxp_span::Span;
 use pxp_token::{Token, TokenKind};
 
-pub use internal::diagnostics::ParserDiagnostic;
+pub use internal::diagnostics::{ParserDiagnostic, SyntaxDiagnostic};
 
 #[derive(Debug)]
 pub struct ParseResult {
     pub ast: Vec<Statement>,
-    pub diagnostics: Vec<Diagnostic<ParserDiagnostic>>,
+    /// Diagnostics from the lexer, followed by diagnostics from the parser.
+    pub diagnostics: Vec<Diagnostic<SyntaxDiagnostic>>,
 }
 
 #[derive(Debug)]
@@ -30,6 +31,8 @@ pub struct Parser<'a> {
     namespace_type: Option<NamespaceType>,
     imports: HashMap<UseKind, HashMap<ByteString, ByteString>>,
     in_docblock: bool,
+    /// The span of the last token that was consumed, which is where the node being parsed ends.
+    previous: Span,
 
     diagnostics: Vec<Diagnostic<ParserDiagnostic>>,
 }
@@ -43,10 +46,20 @@ impl<'a> Parser<'a> {
             ast.push(parser.parse_top_level_statement());
         }
 
-        ParseResult {
-            ast,
-            diagnostics: parser.diagnostics,
-        }
+        let diagnostics = parser
+            .lexer
+            .take_diagnostics()
+            .into_iter()
+            .map(SyntaxDiagnostic::from_lexer)
+            .chain(
+                parser
+                    .diagnostics
+                    .into_iter()
+                    .map(SyntaxDiagnostic::from_parser),
+            )
+            .collect();
+
+        ParseResult { ast, diagnostics }
     }
 
     fn new(lexer: Lexer<'a>) -> Self {
@@ -65,6 +78,7 @@ impl<'a> Parser<'a> {
             namespace_type: None,
             imports,
             in_docblock: false,
+            previous: Span::missing(),
 
             diagnostics: vec![],
         };
@@ -87,6 +101,9 @@ impl<'a> Parser<'a> {
         self.collect_comments();
         self.skip_horizontal_whitespace();
 
+        // Comments and whitespace are consumed by nested calls, so this has to happen last.
+        self.previous = span;
+
         span
     }
 
@@ -96,6 +113,8 @@ impl<'a> Parser<'a> {
         self.lexer.next();
         self.collect_comments();
 
+        self.previous = span;
+
         span
     }
 
@@ -0,0 +1,5 @@
+<?php
+
+$a = "\400";
+$b = "\377 \0 \1234 \\777";
+$c = `echo $a \777`;
@@ -0,0 +1,9 @@
+<?php
+
+$a = "\u{zz}";
+$b = "\u{110000}";
+$c = "{$a} \u{}";
+$d = "\u{1F418} é \\u{zz}";
+$e = <<<EOT
+    \u{12
+    EOT;
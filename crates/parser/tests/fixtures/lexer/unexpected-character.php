@@ -0,0 +1,5 @@
+<?php
+
+$a = 1;
+
+$b = 2;
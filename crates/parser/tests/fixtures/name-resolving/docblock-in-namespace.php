@@ -0,0 +1,6 @@
+<?php
+
+namespace Foo;
+
+/** @param Bar $bar */
+function baz($bar) {}
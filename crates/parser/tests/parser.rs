@@ -734,6 +734,11 @@ snap!(
     enum_in_namespace,
     process("fixtures/name-resolving/enum-in-namespace.php")
 );
+snap!(
+    snapper,
+    docblock_in_namespace,
+    process("fixtures/name-resolving/docblock-in-namespace.php")
+);
 snap!(
     snapper,
     class_extends,
@@ -1018,6 +1023,28 @@ snap!(
     process("fixtures/asymmetric-visibility/promoted-property.php")
 );
 
+// Lexer
+snap!(
+    snapper,
+    lexer_unexpected_character,
+    process("fixtures/lexer/unexpected-character.php")
+);
+snap!(
+    snapper,
+    lexer_invalid_halt_compiler,
+    process("fixtures/lexer/invalid-halt-compiler.php")
+);
+snap!(
+    snapper,
+    lexer_invalid_unicode_escape,
+    process("fixtures/lexer/invalid-unicode-escape.php")
+);
+snap!(
+    snapper,
+    lexer_invalid_octal_escape,
+    process("fixtures/lexer/invalid-octal-escape.php")
+);
+
 pub fn snapper() -> Snapper {
     Snapper::new(format!("{}/{}", env!("CARGO_MANIFEST_DIR"), "tests/__snapshots__").into())
 }
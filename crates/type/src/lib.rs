@@ -2,9 +2,10 @@ use std::fmt::{Debug, Display};
 
 use pxp_bytestring::ByteString;
 use pxp_span::Span;
+use serde::{Deserialize, Serialize};
 use strum::EnumIs;
 
-#[derive(Debug, PartialEq, Eq, Clone, Hash, Default, EnumIs)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default, EnumIs, Serialize, Deserialize)]
 pub enum Type<N: Debug + Display> {
     Named(N),
     Generic(Box<Type<N>>, Vec<GenericTypeArgument<N>>),
@@ -70,7 +71,7 @@ pub enum Type<N: Debug + Display> {
     Invalid,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ConstExpr<N: Debug + Display> {
     NegativeInteger(ByteString),
     Integer(ByteString),
@@ -91,7 +92,7 @@ impl<N: Debug + Display> Display for ConstExpr<N> {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct GenericTypeArgument<N: Debug + Display> {
     pub r#type: Type<N>,
     pub variance: Option<GenericTypeArgumentVariance>,
@@ -107,7 +108,7 @@ impl<N: Debug + Display> Display for GenericTypeArgument<N> {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum GenericTypeArgumentVariance {
     Invariant,
     Covariant,
@@ -126,20 +127,20 @@ impl Display for GenericTypeArgumentVariance {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ShapeItem<N: Debug + Display> {
     pub key_name: Option<ShapeItemKey>,
     pub value_type: Type<N>,
     pub optional: bool,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ShapeItemKey {
     Integer(ByteString),
     String(ByteString),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ShapeUnsealedType<N: Debug + Display> {
     pub key_type: Option<Type<N>>,
     pub value_type: Type<N>,
@@ -166,7 +167,7 @@ impl Display for ShapeItemKey {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct CallableParameter<N: Debug + Display> {
     pub r#type: Type<N>,
     pub ellipsis: Option<Span>,
@@ -238,6 +239,7 @@ impl<N: Debug + Display> Type<N> {
     pub fn is_object_like(&self) -> bool {
         match self {
             Type::Named(_) | Type::Object => true,
+            Type::Generic(base, _) => base.is_object_like(),
             Type::Nullable(inner) => inner.is_object_like(),
             Type::Union(inner) => inner.iter().any(|t| t.is_object_like()),
             Type::Intersection(inner) => inner.iter().any(|t| t.is_object_like()),
@@ -251,6 +253,97 @@ impl<N: Debug + Display> Type<N> {
     }
 }
 
+impl<N: Debug + Display + PartialEq> Type<N> {
+    /// Check if this type is a more specific version of another type, such as a docblock type that
+    /// describes a native type in more detail.
+    ///
+    /// Class-likes can't be compared without an index, so they only refine each other when they're equal.
+    pub fn refines(&self, other: &Type<N>) -> bool {
+        self.refines_with(other, &|_, _| false)
+    }
+
+    /// Same as [`Type::refines`], but uses `is_subclass` to check whether one class-like extends or
+    /// implements another when their names are different.
+    pub fn refines_with(&self, other: &Type<N>, is_subclass: &impl Fn(&N, &N) -> bool) -> bool {
+        if self == other {
+            return true;
+        }
+
+        match (self, other) {
+            (_, Type::Mixed) | (Type::Never, _) => true,
+            // Every part of a union needs to fit into the other type.
+            (Type::Union(types), _) => types.iter().all(|ty| ty.refines_with(other, is_subclass)),
+            (Type::Nullable(inner), _) => {
+                Type::Null.refines_with(other, is_subclass)
+                    && inner.refines_with(other, is_subclass)
+            }
+            (_, Type::Nullable(inner)) => {
+                matches!(self, Type::Null) || self.refines_with(inner, is_subclass)
+            }
+            (_, Type::Union(types)) => types.iter().any(|ty| self.refines_with(ty, is_subclass)),
+            (Type::Intersection(types), _) => {
+                types.iter().any(|ty| ty.refines_with(other, is_subclass))
+            }
+            (Type::Generic(base, _), _) | (Type::CallableSignature(base, _, _), _) => {
+                base.refines_with(other, is_subclass)
+            }
+            (Type::Shaped { base, .. }, _) => base.refines_with(other, is_subclass),
+            (
+                Type::ConditionalForParameter {
+                    then, otherwise, ..
+                }
+                | Type::Conditional {
+                    then, otherwise, ..
+                },
+                _,
+            ) => {
+                then.refines_with(other, is_subclass) && otherwise.refines_with(other, is_subclass)
+            }
+            (Type::ConstExpr(inner), _) => match inner.as_ref() {
+                ConstExpr::Integer(_) | ConstExpr::NegativeInteger(_) => {
+                    Type::Integer.refines_with(other, is_subclass)
+                }
+                ConstExpr::Float(_) => Type::Float.refines_with(other, is_subclass),
+                ConstExpr::String(_) => Type::String.refines_with(other, is_subclass),
+                ConstExpr::ConstFetch(..) => false,
+            },
+            (Type::True | Type::False, Type::Boolean)
+            | (Type::NonNegativeInteger, Type::Integer)
+            | (Type::Integer | Type::String, Type::ArrayKey)
+            | (
+                Type::LiteralString(_)
+                | Type::NumericString
+                | Type::NonEmptyString
+                | Type::ClassString
+                | Type::CallableString,
+                Type::String,
+            )
+            | (
+                Type::List | Type::NonEmptyList | Type::NonEmptyArray | Type::TypedArray(..),
+                Type::Array,
+            )
+            | (
+                Type::Array
+                | Type::List
+                | Type::NonEmptyList
+                | Type::NonEmptyArray
+                | Type::TypedArray(..),
+                Type::Iterable,
+            )
+            | (
+                Type::Named(_) | Type::This | Type::StaticReference | Type::SelfReference,
+                Type::Object,
+            )
+            | (Type::This | Type::StaticReference | Type::SelfReference, Type::Named(_))
+            | (Type::This, Type::StaticReference | Type::SelfReference)
+            | (Type::StaticReference, Type::SelfReference)
+            | (Type::CallableString, Type::Callable) => true,
+            (Type::Named(name), Type::Named(other)) => is_subclass(name, other),
+            _ => false,
+        }
+    }
+}
+
 impl<N: Debug + Display> Display for Type<N> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self {
@@ -377,7 +470,23 @@ impl<N: Debug + Display> Display for Type<N> {
                     otherwise
                 )
             }
-            Type::Conditional { .. } => todo!(),
+            Type::Conditional {
+                subject,
+                negated,
+                target,
+                then,
+                otherwise,
+            } => {
+                write!(
+                    f,
+                    "{} is {}{} ? {} : {}",
+                    subject,
+                    if *negated { "not " } else { "" },
+                    target,
+                    then,
+                    otherwise
+                )
+            }
             Type::Missing => write!(f, "<missing>"),
             Type::Invalid => write!(f, "<invalid>"),
         }
@@ -0,0 +1,235 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::{Component, Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::reporter::Problem;
+
+/// The name of the baseline file that `pxp check --generate-baseline` writes by default.
+pub(crate) const BASELINE_FILE: &str = "pxp.baseline.json";
+
+/// A record of the problems that already existed in a project, so that `pxp check` only reports
+/// new ones.
+///
+/// Problems are matched by their identifier and a fingerprint of the problem's message and the
+/// contents of the line it starts on, rather than by line number, so that an entry keeps matching
+/// when code above it is added or removed.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct Baseline {
+    /// The directory that contains the baseline file. Paths in the baseline are relative to it.
+    #[serde(skip)]
+    root: PathBuf,
+    /// The files that have been checked against the baseline, using the same relative paths.
+    #[serde(skip)]
+    checked: HashSet<String>,
+    files: BTreeMap<String, Vec<BaselineEntry>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct BaselineEntry {
+    pub(crate) identifier: String,
+    pub(crate) fingerprint: String,
+    /// The number of identical problems. The same mistake can easily appear more than once on a
+    /// single line.
+    pub(crate) count: usize,
+}
+
+impl Baseline {
+    /// Create an empty baseline that will be written to the given path.
+    pub(crate) fn new(path: &Path) -> anyhow::Result<Self> {
+        Ok(Self {
+            root: root_of(path)?,
+            ..Default::default()
+        })
+    }
+
+    pub(crate) fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|error| {
+            anyhow::anyhow!("Unable to read baseline `{}`: {}", path.display(), error)
+        })?;
+
+        let mut baseline: Baseline = serde_json::from_str(&contents).map_err(|error| {
+            anyhow::anyhow!("Invalid baseline in `{}`: {}", path.display(), error)
+        })?;
+
+        baseline.root = root_of(path)?;
+
+        Ok(baseline)
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let mut json = serde_json::to_string_pretty(self)?;
+        json.push('\n');
+
+        std::fs::write(path, json)?;
+
+        Ok(())
+    }
+
+    /// The total number of problems recorded in the baseline.
+    pub(crate) fn len(&self) -> usize {
+        self.files
+            .values()
+            .flat_map(|entries| entries.iter().map(|entry| entry.count))
+            .sum()
+    }
+
+    /// Record a problem in the baseline.
+    pub(crate) fn add(&mut self, path: &Path, contents: &[u8], problem: &Problem) {
+        let file = self.relative_path(path);
+        let fingerprint = fingerprint(problem, contents);
+        let entries = self.files.entry(file).or_default();
+
+        match entries.iter_mut().find(|entry| {
+            entry.identifier == problem.identifier && entry.fingerprint == fingerprint
+        }) {
+            Some(entry) => entry.count += 1,
+            None => entries.push(BaselineEntry {
+                identifier: problem.identifier.clone(),
+                fingerprint,
+                count: 1,
+            }),
+        }
+    }
+
+    /// Remove any problems that are recorded in the baseline, returning the new ones.
+    ///
+    /// Every problem that matches uses up one of the entry's occurrences, so a file that gains
+    /// another copy of an existing problem still has that copy reported.
+    pub(crate) fn filter(
+        &mut self,
+        path: &Path,
+        contents: &[u8],
+        problems: Vec<Problem>,
+    ) -> Vec<Problem> {
+        let file = self.relative_path(path);
+        self.checked.insert(file.clone());
+
+        let Some(entries) = self.files.get_mut(&file) else {
+            return problems;
+        };
+
+        problems
+            .into_iter()
+            .filter(|problem| {
+                let fingerprint = fingerprint(problem, contents);
+
+                match entries.iter_mut().find(|entry| {
+                    entry.count > 0
+                        && entry.identifier == problem.identifier
+                        && entry.fingerprint == fingerprint
+                }) {
+                    Some(entry) => {
+                        entry.count -= 1;
+                        false
+                    }
+                    None => true,
+                }
+            })
+            .collect()
+    }
+
+    /// The entries that didn't match a problem, along with the file they belong to.
+    ///
+    /// Only files that were checked, or that no longer exist, are considered. Otherwise checking
+    /// part of a project would make the rest of the baseline look stale.
+    pub(crate) fn unmatched(&self) -> Vec<(&str, &BaselineEntry)> {
+        self.files
+            .iter()
+            .filter(|(file, _)| self.checked.contains(*file) || !self.root.join(file).exists())
+            .flat_map(|(file, entries)| {
+                entries
+                    .iter()
+                    .filter(|entry| entry.count > 0)
+                    .map(move |entry| (file.as_str(), entry))
+            })
+            .collect()
+    }
+
+    /// The path of a file relative to the baseline, always using forward slashes so that the same
+    /// baseline works on every platform.
+    fn relative_path(&self, path: &Path) -> String {
+        let path = absolute(path).unwrap_or_else(|_| path.to_path_buf());
+        let relative = path.strip_prefix(&self.root).unwrap_or(&path);
+
+        relative
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(part) => Some(part.to_string_lossy()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+fn root_of(path: &Path) -> anyhow::Result<PathBuf> {
+    let path = absolute(path)?;
+
+    Ok(path.parent().map(Path::to_path_buf).unwrap_or_default())
+}
+
+fn absolute(path: &Path) -> std::io::Result<PathBuf> {
+    if path.is_absolute() {
+        Ok(path.to_path_buf())
+    } else {
+        Ok(std::env::current_dir()?.join(path))
+    }
+}
+
+/// A fingerprint for a problem that doesn't depend on where the problem is in the file.
+///
+/// The line's indentation is ignored too, since wrapping code in a new block shouldn't make an
+/// old problem look new.
+fn fingerprint(problem: &Problem, contents: &[u8]) -> String {
+    let start = problem.span.start.min(contents.len());
+    let line_start = contents[..start]
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(0, |newline| newline + 1);
+    let line_end = contents[start..]
+        .iter()
+        .position(|byte| *byte == b'\n')
+        .map_or(contents.len(), |newline| start + newline);
+    let line = &contents[line_start..line_end];
+    let line = match line.iter().position(|byte| !byte.is_ascii_whitespace()) {
+        Some(first) => {
+            let last = line
+                .iter()
+                .rposition(|byte| !byte.is_ascii_whitespace())
+                .unwrap();
+            &line[first..=last]
+        }
+        None => &[],
+    };
+
+    let mut hash = Fnv::default();
+    hash.write(problem.identifier.as_bytes());
+    hash.write(&[0]);
+    hash.write(problem.message.as_bytes());
+    hash.write(&[0]);
+    hash.write(line);
+
+    format!("{:016x}", hash.0)
+}
+
+/// A 64-bit FNV-1a hash. The standard library's hashers aren't guaranteed to produce the same
+/// output between releases, which would invalidate every baseline after a compiler upgrade.
+struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+}
+
+impl Fnv {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+}
@@ -0,0 +1,424 @@
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::Parser as Args;
+use colored::Colorize;
+use pxp_diagnostics::{Diagnostic, DiagnosticKind, Severity};
+use pxp_lexer::Lexer;
+use pxp_parser::Parser;
+
+use crate::{
+    baseline::{Baseline, BASELINE_FILE},
+    config::{self, resolve_paths, Config},
+    reporter::{CheckedFile, Format, Problem, Summary},
+    suppression::Suppressions,
+    utils::find_php_files,
+};
+
+#[derive(Debug, Args)]
+#[command(version, about = "Check a file or directory for problems.")]
+pub struct Check {
+    #[arg(help = "The path to a file or directory. Defaults to the paths in pxp.config.toml.")]
+    path: Option<PathBuf>,
+
+    #[arg(
+        short,
+        long,
+        value_enum,
+        default_value_t,
+        help = "The format to report problems in."
+    )]
+    format: Format,
+
+    #[arg(
+        long,
+        help = "The baseline file to use. Defaults to the baseline in pxp.config.toml."
+    )]
+    baseline: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "Record every problem that's found in the baseline file instead of reporting them."
+    )]
+    generate_baseline: bool,
+
+    #[arg(
+        long,
+        conflicts_with = "generate_baseline",
+        help = "Report every problem, including the ones in the baseline."
+    )]
+    ignore_baseline: bool,
+}
+
+/// Whether `pxp check` found anything that should fail the build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Passed,
+    Failed,
+}
+
+impl From<Status> for ExitCode {
+    fn from(status: Status) -> Self {
+        match status {
+            Status::Passed => ExitCode::SUCCESS,
+            Status::Failed => ExitCode::FAILURE,
+        }
+    }
+}
+
+pub fn check(args: Check) -> anyhow::Result<Status> {
+    let config = Config::discover_from_cwd()?;
+
+    run(&args, config.as_ref())
+}
+
+fn run(args: &Check, config: Option<&Config>) -> anyhow::Result<Status> {
+    let paths = resolve_paths(args.path.as_deref(), config)?;
+    let files = find_php_files(&paths, config)?;
+
+    let baseline_path = baseline_path(args, config);
+
+    if args.generate_baseline {
+        generate_baseline(&baseline_path, &files, config)?;
+
+        return Ok(Status::Passed);
+    }
+
+    // A baseline that was asked for explicitly has to exist, but the default one is optional.
+    let mut baseline =
+        if !args.ignore_baseline && (args.baseline.is_some() || baseline_path.exists()) {
+            Some(Baseline::load(&baseline_path)?)
+        } else {
+            None
+        };
+
+    let mut reporter = args.format.reporter();
+    let mut summary = Summary {
+        files: files.len(),
+        ..Default::default()
+    };
+
+    for path in files.iter() {
+        let contents = std::fs::read(path)?;
+        let mut problems = problems_in(&contents, config);
+
+        if let Some(baseline) = baseline.as_mut() {
+            problems = baseline.filter(path, &contents, problems);
+        }
+
+        for problem in problems.iter() {
+            summary.add(problem.severity);
+        }
+
+        reporter.report(&CheckedFile {
+            path,
+            contents: &contents,
+            problems,
+        })?;
+    }
+
+    reporter.finish(&summary)?;
+
+    if let Some(baseline) = baseline.as_ref() {
+        warn_about_unmatched(baseline);
+    }
+
+    Ok(status_of(&summary))
+}
+
+/// The baseline to generate or check against. Without a configuration file, it lives in the
+/// current directory.
+fn baseline_path(args: &Check, config: Option<&Config>) -> PathBuf {
+    args.baseline
+        .clone()
+        .or_else(|| config.map(Config::baseline))
+        .unwrap_or_else(|| PathBuf::from(BASELINE_FILE))
+}
+
+/// Only errors fail the check, warnings and anything less serious are just reported.
+fn status_of(summary: &Summary) -> Status {
+    if summary.errors > 0 {
+        Status::Failed
+    } else {
+        Status::Passed
+    }
+}
+
+fn problems_in(contents: &[u8], config: Option<&Config>) -> Vec<Problem> {
+    let result = Parser::parse(Lexer::new(contents));
+    let mut suppressions = Suppressions::collect(&result.ast, contents);
+
+    let mut problems = result
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| !suppressions.is_suppressed(diagnostic))
+        .filter_map(|diagnostic| {
+            severity_of(diagnostic, config)
+                .map(|severity| Problem::new(diagnostic, severity, contents))
+        })
+        .collect::<Vec<_>>();
+
+    problems.extend(suppressions.unused().iter().filter_map(|diagnostic| {
+        severity_of(diagnostic, config).map(|severity| Problem::new(diagnostic, severity, contents))
+    }));
+
+    problems
+}
+
+fn generate_baseline(
+    path: &Path,
+    files: &[PathBuf],
+    config: Option<&Config>,
+) -> anyhow::Result<()> {
+    let mut baseline = Baseline::new(path)?;
+
+    for file in files {
+        let contents = std::fs::read(file)?;
+
+        for problem in problems_in(&contents, config) {
+            baseline.add(file, &contents, &problem);
+        }
+    }
+
+    baseline.save(path)?;
+
+    println!(
+        "{} Recorded {} {} in `{}`.",
+        "Baseline generated.".green().bold(),
+        baseline.len(),
+        if baseline.len() == 1 {
+            "problem"
+        } else {
+            "problems"
+        },
+        path.display()
+    );
+
+    Ok(())
+}
+
+/// Entries that no longer match anything should be removed from the baseline, otherwise they could
+/// end up hiding a new problem that happens to look the same.
+///
+/// These go to stderr so that they don't end up in machine-readable output.
+fn warn_about_unmatched(baseline: &Baseline) {
+    let unmatched = baseline.unmatched();
+
+    if unmatched.is_empty() {
+        return;
+    }
+
+    for (file, entry) in unmatched.iter() {
+        eprintln!(
+            "{} Baseline entry for `{}` in `{}` no longer matches {}.",
+            "warning:".yellow().bold(),
+            entry.identifier,
+            file,
+            if entry.count == 1 {
+                "a problem".to_string()
+            } else {
+                format!("{} problems", entry.count)
+            }
+        );
+    }
+
+    eprintln!("Run `pxp check --generate-baseline` to update the baseline.");
+}
+
+/// Apply the severity configured for the diagnostic's rule, using either its identifier or its code.
+///
+/// Returns `None` if the rule has been turned off.
+fn severity_of<K: DiagnosticKind>(
+    diagnostic: &Diagnostic<K>,
+    config: Option<&Config>,
+) -> Option<Severity> {
+    let configured = config.and_then(|config| {
+        config
+            .severity(&diagnostic.kind.get_identifier())
+            .or_else(|| config.severity(&diagnostic.kind.get_code()))
+    });
+
+    match configured {
+        None => Some(diagnostic.severity),
+        Some(config::Severity::Off) => None,
+        Some(config::Severity::Error) => Some(Severity::Error),
+        Some(config::Severity::Warning) => Some(Severity::Warning),
+        Some(config::Severity::Info) => Some(Severity::Information),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(path: &Path) -> Check {
+        Check {
+            path: Some(path.to_path_buf()),
+            format: Format::Json,
+            baseline: None,
+            generate_baseline: false,
+            ignore_baseline: false,
+        }
+    }
+
+    fn config(toml: &str) -> Config {
+        toml::from_str(toml).unwrap()
+    }
+
+    fn temp_file(name: &str, contents: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("pxp-check-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let path = directory.join("file.php");
+        std::fs::write(&path, contents).unwrap();
+
+        path
+    }
+
+    #[test]
+    fn it_reports_parser_diagnostics() {
+        let problems = problems_in(b"<?php function foo(int $a = ) {}", None);
+
+        assert!(!problems.is_empty());
+        assert!(problems
+            .iter()
+            .all(|problem| problem.identifier.starts_with("parser.")));
+        assert!(problems_in(b"<?php function foo(int $a) {}", None).is_empty());
+    }
+
+    #[test]
+    fn it_applies_configured_severities() {
+        let contents = b"<?php function foo(int $a = ) {}";
+        let identifier = problems_in(contents, None)[0].identifier.clone();
+
+        let off = config(&format!("[check.rules]\n\"{}\" = \"off\"", identifier));
+        assert!(problems_in(contents, Some(&off))
+            .iter()
+            .all(|problem| problem.identifier != identifier));
+
+        let warning = config(&format!("[check.rules]\n\"{}\" = \"warning\"", identifier));
+        assert!(problems_in(contents, Some(&warning))
+            .iter()
+            .filter(|problem| problem.identifier == identifier)
+            .all(|problem| problem.severity == Severity::Warning));
+    }
+
+    #[test]
+    fn it_reports_unused_suppressions() {
+        let problems = problems_in(b"<?php\n// @pxp-ignore-next-line\n$a = 1;", None);
+
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].code, "S001");
+        assert_eq!(problems[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn it_only_fails_when_there_are_errors() {
+        assert_eq!(status_of(&Summary::default()), Status::Passed);
+        assert_eq!(
+            status_of(&Summary {
+                warnings: 2,
+                information: 1,
+                ..Default::default()
+            }),
+            Status::Passed
+        );
+        assert_eq!(
+            status_of(&Summary {
+                errors: 1,
+                ..Default::default()
+            }),
+            Status::Failed
+        );
+    }
+
+    #[test]
+    fn it_fails_when_a_file_has_errors() {
+        let broken = temp_file("broken", "<?php function foo(int $a = ) {}");
+        let valid = temp_file("valid", "<?php function foo(int $a) {}");
+
+        let broken_status = run(&args(&broken), None).unwrap();
+        let valid_status = run(&args(&valid), None).unwrap();
+
+        std::fs::remove_dir_all(broken.parent().unwrap()).unwrap();
+        std::fs::remove_dir_all(valid.parent().unwrap()).unwrap();
+
+        assert_eq!(broken_status, Status::Failed);
+        assert_eq!(valid_status, Status::Passed);
+    }
+
+    #[test]
+    fn it_generates_a_baseline_and_checks_against_it() {
+        let broken = temp_file("baseline", "<?php function foo(int $a = ) {}");
+        let baseline = broken.with_file_name(BASELINE_FILE);
+
+        let generate = Check {
+            baseline: Some(baseline.clone()),
+            generate_baseline: true,
+            ..args(&broken)
+        };
+        let check = Check {
+            baseline: Some(baseline.clone()),
+            ..args(&broken)
+        };
+        let ignore = Check {
+            baseline: Some(baseline.clone()),
+            ignore_baseline: true,
+            ..args(&broken)
+        };
+
+        let generated = run(&generate, None).unwrap();
+        let checked = run(&check, None).unwrap();
+        let ignored = run(&ignore, None).unwrap();
+        std::fs::remove_dir_all(broken.parent().unwrap()).unwrap();
+
+        assert_eq!(generated, Status::Passed);
+        assert_eq!(checked, Status::Passed);
+        assert_eq!(ignored, Status::Failed);
+    }
+
+    #[test]
+    fn it_uses_the_same_default_baseline_for_generating_and_checking() {
+        let path = Path::new("file.php");
+        let generate = Check {
+            generate_baseline: true,
+            ..args(path)
+        };
+
+        assert_eq!(
+            baseline_path(&args(path), None),
+            PathBuf::from(BASELINE_FILE)
+        );
+        assert_eq!(baseline_path(&generate, None), PathBuf::from(BASELINE_FILE));
+
+        let config = config("[check]\nbaseline = \"baseline.json\"");
+
+        assert_eq!(
+            baseline_path(&args(path), Some(&config)),
+            baseline_path(&generate, Some(&config))
+        );
+    }
+
+    #[test]
+    fn it_passes_when_errors_are_turned_off() {
+        let broken = temp_file("off", "<?php function foo(int $a = ) {}");
+        let rules = problems_in(b"<?php function foo(int $a = ) {}", None)
+            .into_iter()
+            .map(|problem| problem.identifier)
+            .collect::<std::collections::BTreeSet<_>>()
+            .iter()
+            .map(|identifier| format!("\"{}\" = \"off\"\n", identifier))
+            .collect::<String>();
+
+        let status = run(
+            &args(&broken),
+            Some(&config(&format!("[check.rules]\n{}", rules))),
+        );
+        std::fs::remove_dir_all(broken.parent().unwrap()).unwrap();
+
+        assert_eq!(status.unwrap(), Status::Passed);
+    }
+}
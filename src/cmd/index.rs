@@ -1,11 +1,15 @@
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 
 use clap::Parser;
 use colored::Colorize;
-use pxp_index::Index as Indexer;
+use pxp_composer::Project;
+use pxp_index::{Extension, HasLocation, Index as Indexer};
 use rustyline::{error::ReadlineError, CompletionType, Config, DefaultEditor};
 
-use crate::utils::{find_php_files_in, pxp_home_dir, ProgressBar};
+use crate::{
+    config::{resolve_paths, Config as PxpConfig},
+    utils::{find_php_files, index_cache_path, pxp_home_dir, ProgressBar},
+};
 
 #[derive(Debug, Parser)]
 #[command(
@@ -14,21 +18,35 @@ use crate::utils::{find_php_files_in, pxp_home_dir, ProgressBar};
     after_help = "This command is only intended for use during development and testing."
 )]
 pub struct Index {
-    #[clap(help = "The path to a file or directory.")]
-    path: PathBuf,
+    #[clap(help = "The path to a file or directory. Defaults to the paths in pxp.config.toml.")]
+    path: Option<PathBuf>,
 
     #[clap(short, long, help = "Do not show progress bar.")]
     no_progress: bool,
+
+    #[clap(long, help = "Do not read from or write to the index cache.")]
+    no_cache: bool,
+
+    #[clap(
+        long,
+        help = "Do not load stubs for PHP's built-in functions and classes."
+    )]
+    no_stubs: bool,
 }
 
 pub fn index(args: Index) -> anyhow::Result<()> {
-    if !args.path.exists() {
-        anyhow::bail!("The path `{}` does not exist.", args.path.display());
+    let config = PxpConfig::discover_from_cwd()?;
+    let paths = resolve_paths(args.path.as_deref(), config.as_ref())?;
+
+    for path in paths.iter() {
+        if !path.exists() {
+            anyhow::bail!("The path `{}` does not exist.", path.display());
+        }
     }
 
     let mut index = Indexer::new();
 
-    perform(&args, &mut index, &args.path)?;
+    perform(&args, &mut index, &paths, config.as_ref())?;
     repl(&index)?;
 
     Ok(())
@@ -93,28 +111,109 @@ fn handle(command: &str, index: &Indexer) -> anyhow::Result<()> {
                 None => println!("Class `{}` not found.", name.bold()),
             }
         }
+        ["count", "constants"] => println!(
+            "There are {} constants in the index.",
+            index.number_of_constants().to_string().bold().underline()
+        ),
+        ["get", "constant", name] => {
+            let constant = index.get_constant(*name);
+
+            match constant {
+                Some(constant) => println!(
+                    "{:#?}\nDefined in {}",
+                    constant,
+                    index.get_file_path_unchecked(constant.location()).display()
+                ),
+                None => println!("Constant `{}` not found.", name.bold()),
+            }
+        }
         _ => println!("Unrecognised command: `{}`", command.red().bold()),
     }
 
     Ok(())
 }
 
-fn perform(args: &Index, index: &mut Indexer, path: &Path) -> anyhow::Result<()> {
-    if path.is_file() {
-        index.index_file(path);
-    } else {
-        let files = find_php_files_in(path)?;
-        let bar = ProgressBar::new(!args.no_progress, files.len() as u64);
+/// The number of files to index in parallel before updating the progress bar.
+const BATCH_SIZE: usize = 512;
+
+fn perform(
+    args: &Index,
+    index: &mut Indexer,
+    paths: &[PathBuf],
+    config: Option<&PxpConfig>,
+) -> anyhow::Result<()> {
+    let extensions = match config {
+        _ if args.no_stubs => Vec::new(),
+        Some(config) => config.extensions(),
+        None => Extension::REQUIRED.to_vec(),
+    };
+
+    if let [path] = paths {
+        if path.is_file() {
+            index.load_stubs(&extensions);
+            index.index_file(path)?;
+
+            return Ok(());
+        }
+    }
 
-        for file in files {
-            bar.set_message(file.display().to_string());
+    // Paths from the configuration file share a single cache for the whole project.
+    let project = match (&args.path, config) {
+        (None, Some(config)) => config.root(),
+        _ => paths[0].as_path(),
+    };
 
-            index.index_file(&file);
+    let cache = index_cache_path(project)?;
+
+    if !args.no_cache {
+        match Indexer::load_cache(&cache) {
+            Ok(Some(cached)) => *index = cached,
+            Ok(None) => {}
+            Err(error) => eprintln!("{}", error.to_string().yellow()),
+        }
+    }
 
-            bar.inc(1);
+    index.load_stubs(&extensions);
+
+    let mut files = Vec::new();
+
+    for path in paths {
+        // Composer projects only need the files that can be autoloaded, which leaves out things
+        // like tests inside of vendor packages.
+        if path.is_dir() && Project::exists(path) {
+            files.extend(
+                Project::load(path)?
+                    .files()
+                    .into_iter()
+                    .filter(|file| !config.is_some_and(|config| config.is_excluded(file))),
+            );
+        } else {
+            files.append(&mut find_php_files(std::slice::from_ref(path), config)?);
         }
+    }
+
+    let bar = ProgressBar::new(!args.no_progress, files.len() as u64);
+    let mut errors = Vec::new();
+
+    for batch in files.chunks(BATCH_SIZE) {
+        bar.set_message(batch[0].display().to_string());
+
+        errors.extend(index.index_paths(batch));
+
+        bar.inc(batch.len() as u64);
+    }
+
+    bar.finish_and_clear();
+
+    // Files that were deleted since the cache was written shouldn't stick around in the index.
+    index.prune_files(&files);
+
+    for error in errors {
+        eprintln!("{}", error.to_string().yellow());
+    }
 
-        bar.finish_and_clear();
+    if !args.no_cache {
+        index.save_cache(&cache)?;
     }
 
     Ok(())
@@ -2,6 +2,7 @@ use std::path::{Path, PathBuf};
 
 use clap::Parser;
 use colored::Colorize;
+use pxp_composer::Project;
 
 #[derive(Parser, Debug)]
 #[command(version, about = "Initialise a new project.")]
@@ -42,6 +43,10 @@ pub fn init(args: Init) -> anyhow::Result<()> {
 }
 
 fn find_interesting_directories_in(path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    if Project::exists(path) {
+        return find_autoload_paths_in(path);
+    }
+
     let mut paths = Vec::new();
 
     if path.join("src").exists() {
@@ -70,3 +75,19 @@ fn find_interesting_directories_in(path: &Path) -> anyhow::Result<Vec<PathBuf>>
     paths.dedup();
     Ok(paths)
 }
+
+/// Use the root package's autoload configuration to find the project's source directories.
+fn find_autoload_paths_in(path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let project = Project::load(path)?;
+    let mut paths = Vec::new();
+
+    for autoload_path in project.root_package().autoload().paths() {
+        let relative = autoload_path.strip_prefix(path)?.to_path_buf();
+
+        if !paths.contains(&relative) {
+            paths.push(relative);
+        }
+    }
+
+    Ok(paths)
+}
@@ -0,0 +1,14 @@
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(
+    version,
+    about = "Start a language server that communicates over stdin and stdout."
+)]
+pub struct Lsp {}
+
+pub fn lsp(_: Lsp) -> anyhow::Result<()> {
+    pxp_lsp::run_stdio()?;
+
+    Ok(())
+}
@@ -1,9 +1,13 @@
+mod check;
 mod index;
 mod init;
+mod lsp;
 mod parse;
 mod tokenise;
 
+pub use check::{check, Check};
 pub use index::{index, Index};
 pub use init::{init, Init};
+pub use lsp::{lsp, Lsp};
 pub use parse::{parse, Parse};
 pub use tokenise::{tokenise, Tokenise};
@@ -6,13 +6,16 @@ use pxp_lexer::Lexer;
 use pxp_parser::Parser;
 use pxp_span::IsSpanned;
 
-use crate::utils::find_php_files_in;
+use crate::{
+    config::{resolve_paths, Config},
+    utils::find_php_files,
+};
 
 #[derive(Debug, Args)]
 #[command(version, about = "Parse a file or directory.")]
 pub struct Parse {
-    #[arg(help = "The path to a file or directory.")]
-    path: PathBuf,
+    #[arg(help = "The path to a file or directory. Defaults to the paths in pxp.config.toml.")]
+    path: Option<PathBuf>,
 
     #[arg(short, long, help = "Dump the AST to stdout.")]
     dump: bool,
@@ -25,11 +28,9 @@ pub struct Parse {
 }
 
 pub fn parse(args: Parse) -> anyhow::Result<()> {
-    let files = if args.path.is_dir() {
-        find_php_files_in(&args.path)?
-    } else {
-        vec![args.path]
-    };
+    let config = Config::discover_from_cwd()?;
+    let paths = resolve_paths(args.path.as_deref(), config.as_ref())?;
+    let files = find_php_files(&paths, config.as_ref())?;
 
     for file in files {
         if args.print_filenames {
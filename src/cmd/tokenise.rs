@@ -4,24 +4,25 @@ use clap::Parser;
 use pxp_lexer::Lexer;
 use pxp_token::TokenKind;
 
-use crate::utils::find_php_files_in;
+use crate::{
+    config::{resolve_paths, Config},
+    utils::find_php_files,
+};
 
 #[derive(Parser, Debug)]
 #[command(version, about = "Tokenise a file or directory.")]
 pub struct Tokenise {
-    #[arg(help = "The path to a file or directory.")]
-    path: PathBuf,
+    #[arg(help = "The path to a file or directory. Defaults to the paths in pxp.config.toml.")]
+    path: Option<PathBuf>,
 
     #[arg(short, long, help = "Dump the tokens to stdout.")]
     dump: bool,
 }
 
 pub fn tokenise(args: Tokenise) -> anyhow::Result<()> {
-    let files = if args.path.is_dir() {
-        find_php_files_in(&args.path)?
-    } else {
-        vec![args.path]
-    };
+    let config = Config::discover_from_cwd()?;
+    let paths = resolve_paths(args.path.as_deref(), config.as_ref())?;
+    let files = find_php_files(&paths, config.as_ref())?;
 
     for file in files {
         tokenise_file(&file, args.dump)?;
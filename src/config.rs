@@ -0,0 +1,260 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use pxp_index::Extension;
+use serde::Deserialize;
+
+use crate::baseline::BASELINE_FILE;
+
+/// The name of the configuration file written by `pxp init`.
+pub(crate) const CONFIG_FILE: &str = "pxp.config.toml";
+
+/// The settings in a project's `pxp.config.toml` file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct Config {
+    /// The directory that contains the configuration file. Relative paths are resolved against it.
+    #[serde(skip)]
+    root: PathBuf,
+    php: PhpConfig,
+    check: CheckConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct PhpConfig {
+    extensions: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct CheckConfig {
+    paths: Vec<PathBuf>,
+    exclude: Vec<PathBuf>,
+    baseline: Option<PathBuf>,
+    rules: HashMap<String, Severity>,
+}
+
+impl Config {
+    /// Find the nearest `pxp.config.toml`, starting in `directory` and working up through its parents.
+    pub(crate) fn discover(directory: &Path) -> anyhow::Result<Option<Self>> {
+        for ancestor in directory.ancestors() {
+            let path = ancestor.join(CONFIG_FILE);
+
+            if path.is_file() {
+                return Self::load(&path).map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Discover the configuration file for the current working directory.
+    pub(crate) fn discover_from_cwd() -> anyhow::Result<Option<Self>> {
+        Self::discover(&std::env::current_dir()?)
+    }
+
+    pub(crate) fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+
+        let mut config: Config = toml::from_str(&contents).map_err(|error| {
+            anyhow::anyhow!("Invalid configuration in `{}`: {}", path.display(), error)
+        })?;
+
+        for extension in config.php.extensions.iter() {
+            if Extension::from_name(extension).is_none() {
+                anyhow::bail!(
+                    "Invalid configuration in `{}`: unknown extension `{}`.",
+                    path.display(),
+                    extension
+                );
+            }
+        }
+
+        config.root = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        Ok(config)
+    }
+
+    /// The directory that contains the configuration file.
+    pub(crate) fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The paths that commands should work on when they aren't given one explicitly.
+    pub(crate) fn paths(&self) -> Vec<PathBuf> {
+        self.check
+            .paths
+            .iter()
+            .map(|path| self.root.join(path))
+            .collect()
+    }
+
+    /// Check whether the given path is inside of one of the excluded paths.
+    ///
+    /// Excluded paths are relative to the configuration file, but the path might be relative to the
+    /// current directory, so both are resolved before they're compared.
+    pub(crate) fn is_excluded(&self, path: &Path) -> bool {
+        let path = canonicalize(path);
+
+        self.check
+            .exclude
+            .iter()
+            .any(|exclude| path.starts_with(canonicalize(&self.root.join(exclude))))
+    }
+
+    /// The baseline file for the project. Defaults to `pxp.baseline.json` next to the configuration file.
+    pub(crate) fn baseline(&self) -> PathBuf {
+        self.root.join(
+            self.check
+                .baseline
+                .as_deref()
+                .unwrap_or(Path::new(BASELINE_FILE)),
+        )
+    }
+
+    /// The extensions that the project can use. PHP's required extensions are always included.
+    pub(crate) fn extensions(&self) -> Vec<Extension> {
+        let mut extensions = Extension::REQUIRED.to_vec();
+
+        for extension in self
+            .php
+            .extensions
+            .iter()
+            .filter_map(|name| Extension::from_name(name))
+        {
+            if !extensions.contains(&extension) {
+                extensions.push(extension);
+            }
+        }
+
+        extensions
+    }
+
+    /// The severity that has been configured for the given rule, if any.
+    pub(crate) fn severity(&self, rule: &str) -> Option<Severity> {
+        self.check.rules.get(rule).copied()
+    }
+}
+
+/// Resolve the paths that a command should work on.
+///
+/// An explicit path always wins, otherwise the paths from the nearest configuration file are used.
+pub(crate) fn resolve_paths(
+    path: Option<&Path>,
+    config: Option<&Config>,
+) -> anyhow::Result<Vec<PathBuf>> {
+    if let Some(path) = path {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    match config {
+        Some(config) if !config.check.paths.is_empty() => Ok(config.paths()),
+        Some(config) => anyhow::bail!(
+            "No path given and `{}` doesn't list any paths.",
+            config.root().join(CONFIG_FILE).display()
+        ),
+        None => anyhow::bail!(
+            "No path given and no `{}` file found. Run `pxp init` to create one.",
+            CONFIG_FILE
+        ),
+    }
+}
+
+/// Paths that don't exist can't be canonicalized, and are used as they are.
+fn canonicalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// How seriously a rule's violations should be treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Severity {
+    Error,
+    Warning,
+    Info,
+    Off,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write a configuration file to a fresh directory and load it.
+    fn load(name: &str, toml: &str) -> Config {
+        let directory =
+            std::env::temp_dir().join(format!("pxp-config-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let path = directory.join(CONFIG_FILE);
+        std::fs::write(&path, toml).unwrap();
+
+        let config = Config::load(&path).unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        config
+    }
+
+    #[test]
+    fn it_prefers_an_explicit_path() {
+        let config = load("explicit", "[check]\npaths = [\"src\"]");
+
+        assert_eq!(
+            resolve_paths(Some(Path::new("tests")), Some(&config)).unwrap(),
+            vec![PathBuf::from("tests")]
+        );
+        assert_eq!(
+            resolve_paths(Some(Path::new("tests")), None).unwrap(),
+            vec![PathBuf::from("tests")]
+        );
+    }
+
+    #[test]
+    fn it_resolves_configured_paths_against_the_config_file() {
+        let config = load("paths", "[check]\npaths = [\"src\", \"tests\"]");
+
+        assert_eq!(
+            resolve_paths(None, Some(&config)).unwrap(),
+            vec![config.root().join("src"), config.root().join("tests")]
+        );
+        assert_eq!(config.baseline(), config.root().join(BASELINE_FILE));
+    }
+
+    /// A configuration file in the root of this crate, which is where tests are run from.
+    fn crate_config(toml: &str) -> Config {
+        Config {
+            root: PathBuf::from(env!("CARGO_MANIFEST_DIR")),
+            ..toml::from_str(toml).unwrap()
+        }
+    }
+
+    #[test]
+    fn it_excludes_absolute_and_relative_paths() {
+        let config = crate_config("[check]\nexclude = [\"src/cmd\"]");
+        let root = config.root().to_path_buf();
+
+        assert!(config.is_excluded(&root.join("src/cmd/check.rs")));
+        assert!(config.is_excluded(Path::new("src/cmd/check.rs")));
+        assert!(config.is_excluded(Path::new("./src/reporter/../cmd/check.rs")));
+        assert!(!config.is_excluded(&root.join("src/config.rs")));
+        assert!(!config.is_excluded(Path::new("src/config.rs")));
+    }
+
+    #[test]
+    fn it_only_excludes_whole_path_components() {
+        let config = crate_config("[check]\nexclude = [\"src/re\", \"missing\"]");
+
+        assert!(!config.is_excluded(Path::new("src/reporter/mod.rs")));
+        assert!(!config.is_excluded(Path::new("src/config.rs")));
+    }
+
+    #[test]
+    fn it_needs_a_path_when_none_are_configured() {
+        let config = load("empty", "");
+
+        assert!(resolve_paths(None, Some(&config)).is_err());
+        assert!(resolve_paths(None, None).is_err());
+    }
+}
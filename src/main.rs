@@ -1,9 +1,15 @@
+use std::process::ExitCode;
+
 use clap::{
     builder::{styling::AnsiColor, Styles},
     Parser,
 };
 
+mod baseline;
 mod cmd;
+mod config;
+mod reporter;
+mod suppression;
 mod utils;
 
 const STYLES: Styles = Styles::styled()
@@ -26,15 +32,21 @@ enum Command {
     Parse(cmd::Parse),
     Init(cmd::Init),
     Index(cmd::Index),
+    Check(cmd::Check),
+    Lsp(cmd::Lsp),
 }
 
-fn main() -> anyhow::Result<()> {
+fn main() -> anyhow::Result<ExitCode> {
     let parsed = Args::parse();
 
     match parsed.cmd {
-        Command::Tokenise(args) => cmd::tokenise(args),
-        Command::Parse(args) => cmd::parse(args),
-        Command::Init(args) => cmd::init(args),
-        Command::Index(args) => cmd::index(args),
+        Command::Tokenise(args) => cmd::tokenise(args)?,
+        Command::Parse(args) => cmd::parse(args)?,
+        Command::Init(args) => cmd::init(args)?,
+        Command::Index(args) => cmd::index(args)?,
+        Command::Check(args) => return cmd::check(args).map(ExitCode::from),
+        Command::Lsp(args) => cmd::lsp(args)?,
     }
+
+    Ok(ExitCode::SUCCESS)
 }
@@ -0,0 +1,92 @@
+use pxp_diagnostics::Severity;
+
+use super::{escape_xml, CheckedFile, Reporter, Summary};
+
+/// Writes a Checkstyle XML document once every file has been checked.
+#[derive(Default)]
+pub(crate) struct CheckstyleReporter {
+    output: String,
+}
+
+impl Reporter for CheckstyleReporter {
+    fn report(&mut self, file: &CheckedFile) -> anyhow::Result<()> {
+        if file.problems.is_empty() {
+            return Ok(());
+        }
+
+        self.output.push_str(&format!(
+            "  <file name=\"{}\">\n",
+            escape_xml(&file.path.display().to_string())
+        ));
+
+        for problem in file.problems.iter() {
+            // Checkstyle only knows about errors, warnings and info.
+            let severity = match problem.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Information | Severity::Hint => "info",
+            };
+
+            self.output.push_str(&format!(
+                "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"{}\" />\n",
+                problem.start.line,
+                problem.start.column,
+                severity,
+                escape_xml(&problem.message),
+                escape_xml(&problem.identifier),
+            ));
+        }
+
+        self.output.push_str("  </file>\n");
+
+        Ok(())
+    }
+
+    fn finish(&mut self, _: &Summary) -> anyhow::Result<()> {
+        print!("{}", self.document());
+
+        Ok(())
+    }
+}
+
+impl CheckstyleReporter {
+    fn document(&self) -> String {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle version=\"4.3\">\n{}</checkstyle>\n",
+            self.output
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::reporter::tests::problem;
+
+    #[test]
+    fn it_escapes_xml() {
+        let contents = b"<?php\n$a = 1;";
+        let mut reporter = CheckstyleReporter::default();
+
+        reporter
+            .report(&CheckedFile {
+                path: Path::new("src/<a>&b.php"),
+                contents,
+                problems: vec![problem(contents, 6, 8, "expected \"<\" & '>'")],
+            })
+            .unwrap();
+
+        assert_eq!(
+            reporter.document(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<checkstyle version=\"4.3\">
+  <file name=\"src/&lt;a&gt;&amp;b.php\">
+    <error line=\"2\" column=\"1\" severity=\"error\" message=\"expected &quot;&lt;&quot; &amp; &apos;&gt;&apos;\" source=\"test.problem\" />
+  </file>
+</checkstyle>
+"
+        );
+    }
+}
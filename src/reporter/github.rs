@@ -0,0 +1,70 @@
+use pxp_diagnostics::Severity;
+
+use super::{CheckedFile, Problem, Reporter, Summary};
+
+/// Prints GitHub Actions workflow commands, which GitHub shows as annotations on the affected lines.
+pub(crate) struct GithubReporter;
+
+impl Reporter for GithubReporter {
+    fn report(&mut self, file: &CheckedFile) -> anyhow::Result<()> {
+        let path = file.path.display().to_string();
+
+        for problem in file.problems.iter() {
+            println!("{}", command(&path, problem));
+        }
+
+        Ok(())
+    }
+
+    fn finish(&mut self, _: &Summary) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// The workflow command that annotates a single problem.
+fn command(path: &str, problem: &Problem) -> String {
+    let command = match problem.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Information | Severity::Hint => "notice",
+    };
+
+    format!(
+        "::{} file={},line={},col={},endLine={},endColumn={},title={}::{}",
+        command,
+        escape_property(path),
+        problem.start.line,
+        problem.start.column,
+        problem.end.line,
+        problem.end.column,
+        escape_property(&format!("{} ({})", problem.code, problem.identifier)),
+        escape_data(&problem.message),
+    )
+}
+
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::tests::problem;
+
+    #[test]
+    fn it_escapes_workflow_commands() {
+        let problem = problem(b"<?php\n$a = 1;", 6, 8, "100% wrong:\nreally, wrong");
+
+        assert_eq!(
+            command("src/a,b:c.php", &problem),
+            "::error file=src/a%2Cb%3Ac.php,line=2,col=1,endLine=2,endColumn=3,title=T001 (test.problem)::100%25 wrong:%0Areally, wrong"
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use serde_json::{json, Value};
+
+use super::{severity_name, CheckedFile, Reporter, Summary};
+
+/// Writes a single JSON document once every file has been checked.
+#[derive(Default)]
+pub(crate) struct JsonReporter {
+    files: Vec<Value>,
+}
+
+impl Reporter for JsonReporter {
+    fn report(&mut self, file: &CheckedFile) -> anyhow::Result<()> {
+        if file.problems.is_empty() {
+            return Ok(());
+        }
+
+        let problems = file
+            .problems
+            .iter()
+            .map(|problem| {
+                json!({
+                    "code": problem.code,
+                    "identifier": problem.identifier,
+                    "message": problem.message,
+                    "help": problem.help,
+                    "severity": severity_name(problem.severity),
+                    "line": problem.start.line,
+                    "column": problem.start.column,
+                    "endLine": problem.end.line,
+                    "endColumn": problem.end.column,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        self.files.push(json!({
+            "path": file.path.display().to_string(),
+            "problems": problems,
+        }));
+
+        Ok(())
+    }
+
+    fn finish(&mut self, summary: &Summary) -> anyhow::Result<()> {
+        let document = json!({
+            "files": self.files,
+            "summary": {
+                "files": summary.files,
+                "errors": summary.errors,
+                "warnings": summary.warnings,
+                "information": summary.information,
+                "hints": summary.hints,
+            },
+        });
+
+        println!("{}", serde_json::to_string_pretty(&document)?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use super::{escape_xml, severity_name, CheckedFile, Reporter, Summary};
+
+/// Writes a JUnit XML document once every file has been checked, with a test case for each file
+/// and a failure for each problem.
+#[derive(Default)]
+pub(crate) struct JunitReporter {
+    output: String,
+}
+
+impl Reporter for JunitReporter {
+    fn report(&mut self, file: &CheckedFile) -> anyhow::Result<()> {
+        let name = escape_xml(&file.path.display().to_string());
+
+        if file.problems.is_empty() {
+            self.output
+                .push_str(&format!("    <testcase name=\"{}\" />\n", name));
+
+            return Ok(());
+        }
+
+        self.output
+            .push_str(&format!("    <testcase name=\"{}\">\n", name));
+
+        for problem in file.problems.iter() {
+            self.output.push_str(&format!(
+                "      <failure type=\"{}\" message=\"{}\">{} on line {}, column {} [{}]</failure>\n",
+                severity_name(problem.severity),
+                escape_xml(&problem.message),
+                escape_xml(&problem.message),
+                problem.start.line,
+                problem.start.column,
+                escape_xml(&problem.code),
+            ));
+        }
+
+        self.output.push_str("    </testcase>\n");
+
+        Ok(())
+    }
+
+    fn finish(&mut self, summary: &Summary) -> anyhow::Result<()> {
+        print!("{}", self.document(summary));
+
+        Ok(())
+    }
+}
+
+impl JunitReporter {
+    fn document(&self, summary: &Summary) -> String {
+        let mut document = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+
+        document.push_str(&format!(
+            "<testsuites name=\"pxp\" tests=\"{}\" failures=\"{}\">\n",
+            summary.files,
+            summary.problems()
+        ));
+        document.push_str(&format!(
+            "  <testsuite name=\"pxp check\" tests=\"{}\" failures=\"{}\" errors=\"0\">\n",
+            summary.files,
+            summary.problems()
+        ));
+        document.push_str(&self.output);
+        document.push_str("  </testsuite>\n");
+        document.push_str("</testsuites>\n");
+
+        document
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::reporter::tests::problem;
+
+    #[test]
+    fn it_escapes_xml() {
+        let contents = b"<?php\n$a = 1;";
+        let mut reporter = JunitReporter::default();
+        let mut summary = Summary {
+            files: 2,
+            ..Default::default()
+        };
+
+        reporter
+            .report(&CheckedFile {
+                path: Path::new("src/<a>&b.php"),
+                contents,
+                problems: vec![problem(contents, 6, 8, "expected \"<\" & '>'")],
+            })
+            .unwrap();
+        reporter
+            .report(&CheckedFile {
+                path: Path::new("src/c.php"),
+                contents,
+                problems: vec![],
+            })
+            .unwrap();
+        summary.add(pxp_diagnostics::Severity::Error);
+
+        assert_eq!(
+            reporter.document(&summary),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<testsuites name=\"pxp\" tests=\"2\" failures=\"1\">
+  <testsuite name=\"pxp check\" tests=\"2\" failures=\"1\" errors=\"0\">
+    <testcase name=\"src/&lt;a&gt;&amp;b.php\">
+      <failure type=\"error\" message=\"expected &quot;&lt;&quot; &amp; &apos;&gt;&apos;\">expected &quot;&lt;&quot; &amp; &apos;&gt;&apos; on line 2, column 1 [T001]</failure>
+    </testcase>
+    <testcase name=\"src/c.php\" />
+  </testsuite>
+</testsuites>
+"
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use std::path::Path;
+
+use clap::ValueEnum;
+use pxp_diagnostics::{Diagnostic, DiagnosticKind, DiagnosticLabel, Severity};
+use pxp_span::Span;
+
+mod checkstyle;
+mod github;
+mod json;
+mod junit;
+mod sarif;
+mod text;
+
+/// The formats that `pxp check` can report problems in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum Format {
+    /// Human-readable reports with source snippets.
+    #[default]
+    Text,
+    /// A JSON document with every problem in every file.
+    Json,
+    /// Checkstyle XML.
+    Checkstyle,
+    /// JUnit XML, with a test case for each file.
+    Junit,
+    /// SARIF 2.1.0, for code scanning tools.
+    Sarif,
+    /// GitHub Actions workflow commands, which show up as annotations on pull requests.
+    Github,
+}
+
+impl Format {
+    pub(crate) fn reporter(&self) -> Box<dyn Reporter> {
+        match self {
+            Self::Text => Box::new(text::TextReporter),
+            Self::Json => Box::<json::JsonReporter>::default(),
+            Self::Checkstyle => Box::<checkstyle::CheckstyleReporter>::default(),
+            Self::Junit => Box::<junit::JunitReporter>::default(),
+            Self::Sarif => Box::<sarif::SarifReporter>::default(),
+            Self::Github => Box::new(github::GithubReporter),
+        }
+    }
+}
+
+/// Something that can report the problems found by `pxp check`.
+pub(crate) trait Reporter {
+    /// Report the problems found in a single file. This is called for every file that was checked,
+    /// even if there weren't any problems in it.
+    fn report(&mut self, file: &CheckedFile) -> anyhow::Result<()>;
+
+    /// Called once every file has been checked.
+    fn finish(&mut self, summary: &Summary) -> anyhow::Result<()>;
+}
+
+/// A file that has been checked, along with the problems that were found in it.
+pub(crate) struct CheckedFile<'a> {
+    pub(crate) path: &'a Path,
+    pub(crate) contents: &'a [u8],
+    pub(crate) problems: Vec<Problem>,
+}
+
+/// A diagnostic that's ready to be reported, with its severity resolved and its span converted
+/// into lines and columns.
+#[derive(Debug, Clone)]
+pub(crate) struct Problem {
+    pub(crate) code: String,
+    pub(crate) identifier: String,
+    pub(crate) message: String,
+    pub(crate) help: Option<String>,
+    pub(crate) labels: Vec<DiagnosticLabel>,
+    pub(crate) severity: Severity,
+    pub(crate) span: Span,
+    pub(crate) start: Position,
+    pub(crate) end: Position,
+}
+
+impl Problem {
+    pub(crate) fn new<K: DiagnosticKind>(
+        diagnostic: &Diagnostic<K>,
+        severity: Severity,
+        contents: &[u8],
+    ) -> Self {
+        Self {
+            code: diagnostic.kind.get_code(),
+            identifier: diagnostic.kind.get_identifier(),
+            message: diagnostic.kind.get_message(),
+            help: diagnostic.kind.get_help(),
+            labels: diagnostic.kind.get_labels(),
+            severity,
+            span: diagnostic.span,
+            start: Position::at(contents, diagnostic.span.start),
+            end: Position::at(contents, diagnostic.span.end),
+        }
+    }
+}
+
+/// A 1-based line and column in a file. Columns are counted in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Position {
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+
+impl Position {
+    fn at(contents: &[u8], offset: usize) -> Self {
+        let before = &contents[..offset.min(contents.len())];
+        let line_start = before
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+            .map_or(0, |newline| newline + 1);
+
+        Self {
+            line: before.iter().filter(|byte| **byte == b'\n').count() + 1,
+            column: before.len() - line_start + 1,
+        }
+    }
+}
+
+/// The number of files that were checked and the number of problems reported at each severity.
+#[derive(Debug, Default)]
+pub(crate) struct Summary {
+    pub(crate) files: usize,
+    pub(crate) errors: usize,
+    pub(crate) warnings: usize,
+    pub(crate) information: usize,
+    pub(crate) hints: usize,
+}
+
+impl Summary {
+    pub(crate) fn add(&mut self, severity: Severity) {
+        match severity {
+            Severity::Error => self.errors += 1,
+            Severity::Warning => self.warnings += 1,
+            Severity::Information => self.information += 1,
+            Severity::Hint => self.hints += 1,
+        }
+    }
+
+    pub(crate) fn problems(&self) -> usize {
+        self.errors + self.warnings + self.information + self.hints
+    }
+}
+
+/// The name of a severity in machine-readable output.
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Information => "info",
+        Severity::Hint => "hint",
+    }
+}
+
+/// Escape a string for use in XML text or attribute values.
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// An error that spans the given offsets in `contents`.
+    pub(crate) fn problem(contents: &[u8], start: usize, end: usize, message: &str) -> Problem {
+        Problem {
+            code: String::from("T001"),
+            identifier: String::from("test.problem"),
+            message: message.to_string(),
+            help: None,
+            labels: Vec::new(),
+            severity: Severity::Error,
+            span: Span::new(start, end),
+            start: Position::at(contents, start),
+            end: Position::at(contents, end),
+        }
+    }
+
+    #[test]
+    fn it_escapes_xml() {
+        assert_eq!(
+            escape_xml("<a href=\"x\">Tom & Jerry's\n</a>"),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&apos;s&#10;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn it_counts_lines_and_columns_in_bytes() {
+        let contents = "<?php\n$ü = 1;".as_bytes();
+
+        assert_eq!(Position::at(contents, 0), Position { line: 1, column: 1 });
+        assert_eq!(Position::at(contents, 6), Position { line: 2, column: 1 });
+        assert_eq!(Position::at(contents, 9), Position { line: 2, column: 4 });
+    }
+}
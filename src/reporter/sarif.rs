@@ -0,0 +1,155 @@
+use pxp_diagnostics::Severity;
+use serde_json::{json, Value};
+
+use super::{CheckedFile, Reporter, Summary};
+
+/// Writes a SARIF 2.1.0 log once every file has been checked.
+#[derive(Default)]
+pub(crate) struct SarifReporter {
+    rules: Vec<Value>,
+    rule_ids: Vec<String>,
+    results: Vec<Value>,
+}
+
+impl Reporter for SarifReporter {
+    fn report(&mut self, file: &CheckedFile) -> anyhow::Result<()> {
+        let uri = file.path.display().to_string().replace('\\', "/");
+
+        for problem in file.problems.iter() {
+            let index = match self.rule_ids.iter().position(|id| id == &problem.code) {
+                Some(index) => index,
+                None => {
+                    self.rule_ids.push(problem.code.clone());
+                    self.rules.push(json!({
+                        "id": problem.code,
+                        "name": problem.identifier,
+                        "shortDescription": { "text": problem.identifier },
+                    }));
+                    self.rules.len() - 1
+                }
+            };
+
+            let level = match problem.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Information | Severity::Hint => "note",
+            };
+
+            self.results.push(json!({
+                "ruleId": problem.code,
+                "ruleIndex": index,
+                "level": level,
+                "message": { "text": problem.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": uri },
+                        "region": {
+                            "startLine": problem.start.line,
+                            "startColumn": column(file.contents, problem.span.start),
+                            "endLine": problem.end.line,
+                            "endColumn": column(file.contents, problem.span.end),
+                        },
+                    },
+                }],
+            }));
+        }
+
+        Ok(())
+    }
+
+    fn finish(&mut self, _: &Summary) -> anyhow::Result<()> {
+        println!("{}", serde_json::to_string_pretty(&self.log())?);
+
+        Ok(())
+    }
+}
+
+impl SarifReporter {
+    fn log(&self) -> Value {
+        json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "pxp",
+                        "informationUri": "https://github.com/pxp-lang/pxp",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": self.rules,
+                    },
+                },
+                "columnKind": "unicodeCodePoints",
+                "results": self.results,
+            }],
+        })
+    }
+}
+
+/// The 1-based column of an offset, counted in code points.
+///
+/// SARIF counts columns in UTF-16 code units unless the run says otherwise, while problems count
+/// them in bytes, so every line with non-ASCII text would be off.
+fn column(contents: &[u8], offset: usize) -> usize {
+    let before = &contents[..offset.min(contents.len())];
+    let line_start = before
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(0, |newline| newline + 1);
+
+    // Continuation bytes are part of the code point that started before them.
+    before[line_start..]
+        .iter()
+        .filter(|byte| **byte & 0xC0 != 0x80)
+        .count()
+        + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::reporter::tests::problem;
+
+    #[test]
+    fn it_counts_columns_in_code_points() {
+        let contents = "<?php\n$naïve = 'ü'; oops".as_bytes();
+        let start = contents.len() - 4;
+        let mut reporter = SarifReporter::default();
+
+        reporter
+            .report(&CheckedFile {
+                path: Path::new("src\\a.php"),
+                contents,
+                problems: vec![problem(
+                    contents,
+                    start,
+                    contents.len(),
+                    "unexpected \"oops\"",
+                )],
+            })
+            .unwrap();
+
+        let log = reporter.log();
+        let run = &log["runs"][0];
+        let result = &run["results"][0];
+        let location = &result["locations"][0]["physicalLocation"];
+
+        assert_eq!(run["columnKind"], "unicodeCodePoints");
+        assert_eq!(location["artifactLocation"]["uri"], "src/a.php");
+        assert_eq!(location["region"]["startColumn"], 15);
+        assert_eq!(location["region"]["endColumn"], 19);
+        assert_eq!(result["message"]["text"], "unexpected \"oops\"");
+        assert!(serde_json::to_string(&log)
+            .unwrap()
+            .contains(r#""text":"unexpected \"oops\"""#));
+    }
+
+    #[test]
+    fn it_counts_columns_from_the_start_of_the_line() {
+        assert_eq!(column("ü\nab".as_bytes(), 0), 1);
+        assert_eq!(column("ü\nab".as_bytes(), 2), 2);
+        assert_eq!(column("ü\nab".as_bytes(), 4), 2);
+        assert_eq!(column("ü\nab".as_bytes(), 100), 3);
+    }
+}
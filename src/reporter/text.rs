@@ -0,0 +1,115 @@
+use ariadne::{Color, Config, IndexType, Label, Report, ReportKind, Source};
+use colored::Colorize;
+use pxp_diagnostics::{DiagnosticLabelStyle, Severity};
+
+use super::{CheckedFile, Problem, Reporter, Summary};
+
+/// Renders each problem with a source snippet and prints a summary at the end.
+pub(crate) struct TextReporter;
+
+impl Reporter for TextReporter {
+    fn report(&mut self, file: &CheckedFile) -> anyhow::Result<()> {
+        if file.problems.is_empty() {
+            return Ok(());
+        }
+
+        // Spans are byte offsets, so if the file isn't valid UTF-8 every non-ASCII byte is replaced
+        // with a single character to keep the offsets lined up.
+        let source = Source::from(match std::str::from_utf8(file.contents) {
+            Ok(text) => text.to_string(),
+            Err(_) => file
+                .contents
+                .iter()
+                .map(|byte| if byte.is_ascii() { *byte as char } else { '?' })
+                .collect(),
+        });
+        let id = file.path.display().to_string();
+
+        for problem in file.problems.iter() {
+            render(problem, &id).eprint((id.clone(), source.clone()))?;
+        }
+
+        Ok(())
+    }
+
+    fn finish(&mut self, summary: &Summary) -> anyhow::Result<()> {
+        let counts = [
+            (summary.errors, "error", "errors"),
+            (summary.warnings, "warning", "warnings"),
+            (summary.information, "info", "infos"),
+            (summary.hints, "hint", "hints"),
+        ]
+        .into_iter()
+        .filter(|(count, _, _)| *count > 0)
+        .map(|(count, singular, plural)| {
+            format!("{} {}", count, if count == 1 { singular } else { plural })
+        })
+        .collect::<Vec<_>>();
+
+        let checked = format!(
+            "Checked {} {}.",
+            summary.files,
+            if summary.files == 1 { "file" } else { "files" }
+        );
+
+        if counts.is_empty() {
+            println!("{} {}", checked, "No problems found.".green().bold());
+        } else if summary.errors > 0 {
+            println!(
+                "{} {}",
+                checked,
+                format!("Found {}.", counts.join(", ")).red().bold()
+            );
+        } else {
+            println!(
+                "{} {}",
+                checked,
+                format!("Found {}.", counts.join(", ")).yellow().bold()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn render<'a>(problem: &Problem, id: &'a str) -> Report<'a, (String, std::ops::Range<usize>)> {
+    let (kind, color) = match problem.severity {
+        Severity::Error => (ReportKind::Error, Color::Red),
+        Severity::Warning => (ReportKind::Warning, Color::Yellow),
+        Severity::Information | Severity::Hint => (ReportKind::Advice, Color::Cyan),
+    };
+
+    let span = |span: pxp_span::Span| (id.to_string(), span.start..span.end);
+
+    let mut builder = Report::build(kind, span(problem.span))
+        .with_config(Config::default().with_index_type(IndexType::Byte))
+        .with_code(&problem.code)
+        .with_message(&problem.message);
+
+    if problem.labels.is_empty() {
+        builder.add_label(
+            Label::new(span(problem.span))
+                .with_message(&problem.message)
+                .with_color(color),
+        );
+    }
+
+    for label in problem.labels.iter() {
+        let label_color = match label.style {
+            DiagnosticLabelStyle::Primary => color,
+            DiagnosticLabelStyle::Secondary => Color::Blue,
+        };
+
+        builder.add_label(
+            Label::new(span(label.span))
+                .with_message(&label.message)
+                .with_color(label_color),
+        );
+    }
+
+    if let Some(help) = &problem.help {
+        builder.set_help(help);
+    }
+
+    builder.finish()
+}
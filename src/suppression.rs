@@ -0,0 +1,496 @@
+use std::collections::HashSet;
+
+use pxp_ast::{
+    visitor::{walk_expression, walk_statement, Visitor},
+    Comment, CommentGroup, CommentKind, CommentStatement, DocBlockNode, DocBlockTag, Expression,
+    HasId, HashMarkComment, NodeId, SingleLineComment, Statement,
+};
+use pxp_diagnostics::{Diagnostic, DiagnosticKind, Severity};
+use pxp_span::Span;
+
+/// Silences the diagnostics on the line after the comment.
+const IGNORE_NEXT_LINE: &str = "@pxp-ignore-next-line";
+
+/// Silences the diagnostics inside of the statement or expression that the comment belongs to.
+const IGNORE: &str = "@pxp-ignore";
+
+/// The suppression comments in a file, e.g. `// @pxp-ignore-next-line parser.missing-type`.
+///
+/// Suppressions can list any number of diagnostic identifiers or codes. A suppression without any
+/// silences every diagnostic in its scope.
+pub(crate) struct Suppressions {
+    suppressions: Vec<Suppression>,
+    lines: Vec<usize>,
+}
+
+struct Suppression {
+    span: Span,
+    scope: Scope,
+    rules: Vec<Rule>,
+    used: bool,
+}
+
+enum Scope {
+    /// A 0-based line number.
+    Line(usize),
+    Span(Span),
+}
+
+struct Rule {
+    name: String,
+    used: bool,
+}
+
+impl Suppressions {
+    pub(crate) fn collect(ast: &[Statement], contents: &[u8]) -> Self {
+        let lines = std::iter::once(0)
+            .chain(
+                contents
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, byte)| **byte == b'\n')
+                    .map(|(offset, _)| offset + 1),
+            )
+            .collect();
+
+        let mut collector = Collector {
+            suppressions: Self {
+                suppressions: Vec::new(),
+                lines,
+            },
+            seen: HashSet::new(),
+        };
+
+        collector.visit(ast);
+        collector.suppressions
+    }
+
+    /// Check whether the diagnostic has been suppressed, marking any suppressions that match it as used.
+    pub(crate) fn is_suppressed<K: DiagnosticKind>(&mut self, diagnostic: &Diagnostic<K>) -> bool {
+        let line = line_of(&self.lines, diagnostic.span.start);
+        let identifier = diagnostic.kind.get_identifier();
+        let code = diagnostic.kind.get_code();
+        let mut suppressed = false;
+
+        for suppression in self.suppressions.iter_mut() {
+            let in_scope = match suppression.scope {
+                Scope::Line(scope) => line == scope,
+                Scope::Span(span) => {
+                    diagnostic.span.start >= span.start && diagnostic.span.start < span.end
+                }
+            };
+
+            if !in_scope {
+                continue;
+            }
+
+            if suppression.rules.is_empty() {
+                suppression.used = true;
+                suppressed = true;
+            }
+
+            for rule in suppression.rules.iter_mut() {
+                if rule.name == identifier || rule.name == code {
+                    rule.used = true;
+                    suppressed = true;
+                }
+            }
+        }
+
+        suppressed
+    }
+
+    /// Diagnostics for the suppressions that didn't silence anything.
+    pub(crate) fn unused(&self) -> Vec<Diagnostic<SuppressionDiagnostic>> {
+        let mut diagnostics = Vec::new();
+
+        for suppression in self.suppressions.iter() {
+            if suppression.rules.is_empty() && !suppression.used {
+                diagnostics.push(Diagnostic::new(
+                    SuppressionDiagnostic::UnusedSuppression { rule: None },
+                    Severity::Warning,
+                    suppression.span,
+                ));
+            }
+
+            for rule in suppression.rules.iter().filter(|rule| !rule.used) {
+                diagnostics.push(Diagnostic::new(
+                    SuppressionDiagnostic::UnusedSuppression {
+                        rule: Some(rule.name.clone()),
+                    },
+                    Severity::Warning,
+                    suppression.span,
+                ));
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// The 0-based line that contains the given offset.
+fn line_of(lines: &[usize], offset: usize) -> usize {
+    lines
+        .partition_point(|start| *start <= offset)
+        .saturating_sub(1)
+}
+
+struct Collector {
+    suppressions: Suppressions,
+    /// Comments can be attached to more than one node, e.g. a statement and the expression at the
+    /// start of it, so each one is only collected from the outermost node.
+    seen: HashSet<NodeId>,
+}
+
+impl Collector {
+    fn collect(&mut self, comments: &CommentGroup, span: Span) {
+        for comment in comments.iter() {
+            self.collect_comment(comment, span);
+        }
+    }
+
+    fn collect_comment(&mut self, comment: &Comment, span: Span) {
+        if !self.seen.insert(comment.id()) {
+            return;
+        }
+
+        // Single-line comments include the newline at the end of them, which shouldn't count as
+        // part of the comment's line.
+        let comment_span = match &comment.kind {
+            CommentKind::SingleLine(SingleLineComment { content, .. })
+            | CommentKind::HashMark(HashMarkComment { content, .. })
+                if content.ends_with(b"\n") =>
+            {
+                Span::new(comment.span.start, comment.span.end - 1)
+            }
+            _ => comment.span,
+        };
+
+        for (directive, rules) in directives(comment) {
+            let scope = if directive == IGNORE_NEXT_LINE {
+                Scope::Line(line_of(&self.suppressions.lines, comment_span.end) + 1)
+            } else {
+                Scope::Span(span)
+            };
+
+            self.suppressions.suppressions.push(Suppression {
+                span: comment_span,
+                scope,
+                rules: rules
+                    .into_iter()
+                    .map(|name| Rule { name, used: false })
+                    .collect(),
+                used: false,
+            });
+        }
+    }
+}
+
+impl Visitor for Collector {
+    fn visit_statement(&mut self, node: &Statement) {
+        self.collect(&node.comments, node.span);
+
+        walk_statement(self, node);
+    }
+
+    fn visit_expression(&mut self, node: &Expression) {
+        self.collect(&node.comments, node.span);
+
+        walk_expression(self, node);
+    }
+
+    // Comments that aren't attached to anything, e.g. at the end of a block, only have themselves
+    // as a scope.
+    fn visit_comment_statement(&mut self, node: &CommentStatement) {
+        self.collect_comment(&node.comment, node.comment.span);
+    }
+}
+
+/// Find the suppression directives in a comment, along with the rules that each one lists.
+fn directives(comment: &Comment) -> Vec<(&'static str, Vec<String>)> {
+    match &comment.kind {
+        CommentKind::DocBlock(docblock) => docblock
+            .doc
+            .nodes
+            .iter()
+            .filter_map(|node| match node {
+                DocBlockNode::Tag(node) => match &node.tag {
+                    DocBlockTag::Generic(tag) => {
+                        let directive = directive(tag.tag.symbol.as_bytes())?;
+                        let text = tag
+                            .text
+                            .as_ref()
+                            .map(|text| String::from_utf8_lossy(text.as_bytes()))
+                            .unwrap_or_default();
+
+                        Some((directive, rules(text.split_whitespace())))
+                    }
+                    _ => None,
+                },
+                DocBlockNode::Text(_) => None,
+            })
+            .collect(),
+        CommentKind::SingleLine(comment) => parse(comment.content.as_bytes()),
+        CommentKind::MultiLine(comment) => parse(comment.content.as_bytes()),
+        CommentKind::HashMark(comment) => parse(comment.content.as_bytes()),
+    }
+}
+
+fn directive(word: &[u8]) -> Option<&'static str> {
+    match word {
+        b"@pxp-ignore-next-line" => Some(IGNORE_NEXT_LINE),
+        b"@pxp-ignore" => Some(IGNORE),
+        _ => None,
+    }
+}
+
+fn parse(content: &[u8]) -> Vec<(&'static str, Vec<String>)> {
+    let content = String::from_utf8_lossy(content);
+    let mut words = content
+        .split(|c: char| c.is_whitespace() || c == '*' || c == '/')
+        .filter(|word| !word.is_empty())
+        .peekable();
+    let mut directives = Vec::new();
+
+    while let Some(word) = words.next() {
+        if let Some(found) = directive(word.as_bytes()) {
+            let mut following = Vec::new();
+
+            while let Some(word) = words.next_if(|word| directive(word.as_bytes()).is_none()) {
+                following.push(word);
+            }
+
+            directives.push((found, rules(following.into_iter())));
+        }
+    }
+
+    directives
+}
+
+/// The rules listed after a directive, separated by whitespace or commas. Anything that follows
+/// them, e.g. an explanation of why the diagnostic is being suppressed, is ignored.
+fn rules<'a>(words: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut rules = Vec::new();
+
+    for word in words {
+        let listed = word
+            .split(',')
+            .filter(|rule| !rule.is_empty())
+            .collect::<Vec<_>>();
+
+        if listed.is_empty() || !listed.iter().all(|rule| is_rule(rule)) {
+            break;
+        }
+
+        rules.extend(listed.into_iter().map(String::from));
+    }
+
+    rules
+}
+
+/// Rules are either identifiers, like `parser.missing-type`, or codes, like `P001`.
+fn is_rule(word: &str) -> bool {
+    let is_identifier = word.contains('.')
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+    let is_code = word.len() > 1
+        && word.starts_with(|c: char| c.is_ascii_uppercase())
+        && word[1..].chars().all(|c| c.is_ascii_digit());
+
+    is_identifier || is_code
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum SuppressionDiagnostic {
+    UnusedSuppression { rule: Option<String> },
+}
+
+impl DiagnosticKind for SuppressionDiagnostic {
+    fn get_code(&self) -> String {
+        String::from(match self {
+            Self::UnusedSuppression { .. } => "S001",
+        })
+    }
+
+    fn get_identifier(&self) -> String {
+        String::from(match self {
+            Self::UnusedSuppression { .. } => "suppression.unused",
+        })
+    }
+
+    fn get_message(&self) -> String {
+        match self {
+            Self::UnusedSuppression { rule: Some(rule) } => {
+                format!("unused suppression, nothing here reports `{}`", rule)
+            }
+            Self::UnusedSuppression { rule: None } => {
+                String::from("unused suppression, nothing here reports a diagnostic")
+            }
+        }
+    }
+
+    fn get_help(&self) -> Option<String> {
+        Some(String::from("Remove the suppression comment."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pxp_lexer::Lexer;
+    use pxp_parser::Parser;
+
+    use super::*;
+
+    /// Reports P008, `parser.cannot-have-multiple-default-arms-in-match`.
+    const INVALID: &str = "match ($a) { default => 1, default => 2 };";
+
+    /// The codes of the diagnostics that weren't suppressed, and the messages for unused suppressions.
+    fn check(code: &str) -> (Vec<String>, Vec<String>) {
+        let code = format!("<?php\n{}", code);
+        let result = Parser::parse(Lexer::new(code.as_bytes()));
+        let mut suppressions = Suppressions::collect(&result.ast, code.as_bytes());
+
+        let reported = result
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| !suppressions.is_suppressed(diagnostic))
+            .map(|diagnostic| diagnostic.kind.get_code())
+            .collect();
+        let unused = suppressions
+            .unused()
+            .iter()
+            .map(|diagnostic| {
+                assert_eq!(diagnostic.kind.get_code(), "S001");
+                assert_eq!(diagnostic.kind.get_identifier(), "suppression.unused");
+
+                diagnostic.kind.get_message()
+            })
+            .collect();
+
+        (reported, unused)
+    }
+
+    #[test]
+    fn it_reports_diagnostics_without_suppressions() {
+        assert_eq!(check(INVALID), (vec![String::from("P008")], vec![]));
+    }
+
+    #[test]
+    fn it_ignores_the_next_line_by_code_or_identifier() {
+        assert_eq!(
+            check(&format!("// @pxp-ignore-next-line P008\n{}", INVALID)),
+            (vec![], vec![])
+        );
+        assert_eq!(
+            check(&format!(
+                "// @pxp-ignore-next-line parser.cannot-have-multiple-default-arms-in-match\n{}",
+                INVALID
+            )),
+            (vec![], vec![])
+        );
+        assert_eq!(
+            check(&format!(
+                "# @pxp-ignore-next-line P001, P008 because reasons\n{}",
+                INVALID
+            )),
+            (
+                vec![],
+                vec![String::from(
+                    "unused suppression, nothing here reports `P001`"
+                )]
+            )
+        );
+    }
+
+    #[test]
+    fn it_ignores_every_diagnostic_on_the_next_line_without_a_rule() {
+        assert_eq!(
+            check(&format!("/* @pxp-ignore-next-line */\n{}", INVALID)),
+            (vec![], vec![])
+        );
+    }
+
+    #[test]
+    fn it_keeps_diagnostics_that_dont_match_the_suppression() {
+        assert_eq!(
+            check(&format!("// @pxp-ignore-next-line P001\n{}", INVALID)),
+            (
+                vec![String::from("P008")],
+                vec![String::from(
+                    "unused suppression, nothing here reports `P001`"
+                )]
+            )
+        );
+    }
+
+    #[test]
+    fn it_only_ignores_the_line_directly_after_the_comment() {
+        let unused = vec![String::from(
+            "unused suppression, nothing here reports `P008`",
+        )];
+
+        assert_eq!(
+            check(&format!("// @pxp-ignore-next-line P008\n\n{}", INVALID)),
+            (vec![String::from("P008")], unused.clone())
+        );
+        assert_eq!(
+            check(&format!(
+                "// @pxp-ignore-next-line P008\n// Both arms are needed.\n{}",
+                INVALID
+            )),
+            (vec![String::from("P008")], unused)
+        );
+    }
+
+    #[test]
+    fn it_ignores_diagnostics_inside_of_the_statement() {
+        assert_eq!(
+            check(&format!("// @pxp-ignore P008\n{}", INVALID)),
+            (vec![], vec![])
+        );
+        assert_eq!(
+            check(&format!("/** @pxp-ignore P008 */\n{}", INVALID)),
+            (vec![], vec![])
+        );
+        assert_eq!(
+            check(&format!(
+                "// @pxp-ignore P008\nif (true) {{\n    {}\n}}",
+                INVALID
+            )),
+            (vec![], vec![])
+        );
+        assert_eq!(
+            check(&format!("// @pxp-ignore P001\n{}", INVALID)),
+            (
+                vec![String::from("P008")],
+                vec![String::from(
+                    "unused suppression, nothing here reports `P001`"
+                )]
+            )
+        );
+    }
+
+    #[test]
+    fn it_reports_unused_suppressions() {
+        assert_eq!(
+            check("// @pxp-ignore-next-line\n$a = 1;"),
+            (
+                vec![],
+                vec![String::from(
+                    "unused suppression, nothing here reports a diagnostic"
+                )]
+            )
+        );
+        assert_eq!(
+            check("// @pxp-ignore P008\n$a = 1;\n// @pxp-ignore-next-line P008\n$b = 2;"),
+            (
+                vec![],
+                vec![
+                    String::from("unused suppression, nothing here reports `P008`"),
+                    String::from("unused suppression, nothing here reports `P008`"),
+                ]
+            )
+        );
+    }
+}
@@ -1,7 +1,12 @@
-use std::path::{Path, PathBuf};
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    path::{Path, PathBuf},
+};
 
 use indicatif::{ProgressDrawTarget, ProgressStyle};
 
+use crate::config::Config;
+
 pub(crate) struct ProgressBar {
     bar: indicatif::ProgressBar,
 }
@@ -32,20 +37,27 @@ impl ProgressBar {
     }
 }
 
-pub(crate) fn find_php_files_in(path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+/// Find every PHP file in the given paths, skipping anything excluded by the configuration.
+///
+/// Paths that point at a file are kept as they are, even if they don't have a `.php` extension.
+pub(crate) fn find_php_files(
+    paths: &[PathBuf],
+    config: Option<&Config>,
+) -> anyhow::Result<Vec<PathBuf>> {
     let mut files = vec![];
 
-    for entry in path.read_dir()? {
-        let entry = entry?;
-        let path = entry.path();
-
+    for path in paths {
         if path.is_dir() {
-            files.append(&mut find_php_files_in(&path)?);
-        } else if path.extension().map_or(false, |ext| ext == "php") {
-            files.push(path);
+            files.append(&mut pxp_composer::find_php_files(path)?);
+        } else {
+            files.push(path.clone());
         }
     }
 
+    if let Some(config) = config {
+        files.retain(|file| !config.is_excluded(file));
+    }
+
     Ok(files)
 }
 
@@ -62,3 +74,19 @@ pub(crate) fn pxp_home_dir() -> anyhow::Result<PathBuf> {
 
     Ok(pxp)
 }
+
+/// Get the path of the index cache for the given project directory.
+///
+/// Each project gets its own cache file inside of `~/.pxp/cache`, named after a hash of the project's path.
+pub(crate) fn index_cache_path(project: &Path) -> anyhow::Result<PathBuf> {
+    let cache = pxp_home_dir()?.join("cache");
+
+    if !cache.exists() {
+        std::fs::create_dir(&cache)?;
+    }
+
+    let mut hasher = DefaultHasher::new();
+    project.canonicalize()?.hash(&mut hasher);
+
+    Ok(cache.join(format!("{:016x}.index", hasher.finish())))
+}